@@ -0,0 +1,20 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use stringmatch::{Needle, StringMatch};
+
+fn bench_compiled(c: &mut Criterion) {
+    let haystack = "The quick brown fox jumps over the lazy dog. ".repeat(50) + "THE NEEDLE";
+    let needle = "A Fairly  Long Needle\tThat Has To Be Normalized ".repeat(10) + "the needle";
+    let sm = StringMatch::from(needle).partial().case_insensitive().normalize_whitespace();
+    let compiled = sm.clone().compile();
+
+    let mut group = c.benchmark_group("case_insensitive_partial");
+    group.bench_function("StringMatch", |b| b.iter(|| sm.is_match(black_box(&haystack))));
+    group.bench_function("CompiledStringMatch", |b| {
+        b.iter(|| compiled.is_match(black_box(&haystack)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_compiled);
+criterion_main!(benches);
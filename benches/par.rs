@@ -0,0 +1,25 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use stringmatch::{NeedleIter, ParNeedleIter, StringMatch};
+
+fn bench_par(c: &mut Criterion) {
+    let haystacks: Vec<String> =
+        (0..100_000).map(|i| format!("2024-01-01 INFO request {} took {}ms", i, i % 97)).collect();
+    let needle = StringMatch::from("TOOK 42MS").partial().case_insensitive();
+
+    let mut group = c.benchmark_group("filter_matching");
+    group.sample_size(20);
+    group.bench_function("sequential", |b| {
+        b.iter(|| needle.filter_matching(haystacks.iter().map(String::as_str)).count())
+    });
+    for threads in [1, 2, 4, 8] {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        group.bench_with_input(BenchmarkId::new("parallel", threads), &threads, |b, _| {
+            b.iter(|| pool.install(|| needle.par_filter_matching(black_box(&haystacks)).len()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_par);
+criterion_main!(benches);
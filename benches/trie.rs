@@ -0,0 +1,27 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use stringmatch::{AnyNeedle, Needle, StringMatch, TrieNeedle};
+
+fn bench_trie(c: &mut Criterion) {
+    let prefixes: Vec<String> = (0..10_000).map(|i| format!("/api/v{}/", i)).collect();
+    let trie = TrieNeedle::new(&prefixes);
+    let any: AnyNeedle = prefixes.iter().map(|p| StringMatch::from(p.as_str()).prefix()).collect();
+
+    let mut group = c.benchmark_group("prefixes_10k");
+    group.bench_function("TrieNeedle_hit", |b| {
+        b.iter(|| trie.is_match(black_box("/api/v9876/users")))
+    });
+    group.bench_function("TrieNeedle_miss", |b| {
+        b.iter(|| trie.is_match(black_box("/static/index.html")))
+    });
+    group.bench_function("AnyNeedle_hit", |b| {
+        b.iter(|| any.is_match(black_box("/api/v9876/users")))
+    });
+    group.bench_function("AnyNeedle_miss", |b| {
+        b.iter(|| any.is_match(black_box("/static/index.html")))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_trie);
+criterion_main!(benches);
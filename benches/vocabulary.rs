@@ -0,0 +1,18 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use stringmatch::{Needle, VocabularyNeedle};
+
+fn bench_vocabulary(c: &mut Criterion) {
+    let vocab: VocabularyNeedle = (0..100_000).map(|i| format!("Word{}", i)).collect();
+    let ci = vocab.clone().case_insensitive();
+
+    let mut group = c.benchmark_group("vocabulary_100k");
+    group.bench_function("hit", |b| b.iter(|| vocab.is_match(black_box("Word54321"))));
+    group.bench_function("miss", |b| b.iter(|| vocab.is_match(black_box("Word123456"))));
+    group
+        .bench_function("case_insensitive_hit", |b| b.iter(|| ci.is_match(black_box("WORD54321"))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_vocabulary);
+criterion_main!(benches);
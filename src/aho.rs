@@ -0,0 +1,123 @@
+use crate::{Needle, NeedleIter};
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, BuildError};
+
+/// Needle that matches if the haystack contains any of a set of substrings.
+///
+/// The patterns are compiled into an Aho-Corasick automaton, so matching takes time
+/// proportional to the haystack length rather than the number of patterns. This makes it
+/// much faster than an `AnyNeedle` of partial `StringMatch` needles when there are many
+/// patterns.
+#[derive(Debug, Clone)]
+pub struct AhoCorasickNeedle {
+    automaton: AhoCorasick,
+    /// If true, use a case-sensitive match.
+    case_sensitive: bool,
+}
+
+impl AhoCorasickNeedle {
+    /// Build a case-sensitive automaton from the patterns.
+    pub fn new<P: AsRef<str>>(patterns: &[P]) -> Result<Self, BuildError> {
+        Self::new_with_case(patterns, true)
+    }
+
+    /// Build an automaton from the patterns with explicit case sensitivity.
+    ///
+    /// Case-insensitive matching only folds ASCII letters.
+    pub fn new_with_case<P: AsRef<str>>(
+        patterns: &[P],
+        case_sensitive: bool,
+    ) -> Result<Self, BuildError> {
+        let automaton = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(!case_sensitive)
+            .build(patterns.iter().map(|p| p.as_ref()))?;
+        Ok(Self {
+            automaton,
+            case_sensitive,
+        })
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// The number of patterns.
+    pub fn pattern_count(&self) -> usize {
+        self.automaton.patterns_len()
+    }
+
+    /// The indices of all patterns that occur in the haystack, in ascending order.
+    pub fn matched_pattern_indices(&self, haystack: &str) -> Vec<usize> {
+        let mut matched = vec![false; self.pattern_count()];
+        for m in self.automaton.find_overlapping_iter(haystack) {
+            matched[m.pattern().as_usize()] = true;
+        }
+        matched.iter().enumerate().filter_map(|(i, m)| m.then_some(i)).collect()
+    }
+}
+
+impl Needle for AhoCorasickNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.automaton.is_match(haystack)
+    }
+}
+
+impl NeedleIter for AhoCorasickNeedle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnyNeedle, StringMatch};
+
+    #[test]
+    fn test_aho_corasick() {
+        let needle = AhoCorasickNeedle::new(&["apple", "banana", "cherry"]).unwrap();
+        assert!(needle.is_case_sensitive());
+        assert_eq!(needle.pattern_count(), 3);
+        assert!(needle.is_match("I like bananas"));
+        assert!(needle.is_match("cherry"));
+        assert!(!needle.is_match("I like Bananas"));
+        assert!(!needle.is_match(""));
+        assert!(needle.is_match_in(&mut vec!["grape", "pineapple"].into_iter()));
+
+        let needle = AhoCorasickNeedle::new_with_case(&["apple", "banana"], false).unwrap();
+        assert!(!needle.is_case_sensitive());
+        assert!(needle.is_match("I like BANANAS"));
+
+        let patterns: Vec<String> = Vec::new();
+        assert!(!AhoCorasickNeedle::new(&patterns).unwrap().is_match("anything"));
+        assert!(AhoCorasickNeedle::new(&[""]).unwrap().is_match(""));
+    }
+
+    #[test]
+    fn test_matched_pattern_indices() {
+        let needle = AhoCorasickNeedle::new(&["he", "she", "hers", "xyz", "s"]).unwrap();
+        assert_eq!(needle.matched_pattern_indices("ushers"), vec![0, 1, 2, 4]);
+        assert_eq!(needle.matched_pattern_indices("the"), vec![0]);
+        assert_eq!(needle.matched_pattern_indices("abc"), Vec::<usize>::new());
+        assert_eq!(needle.matched_pattern_indices("hehe"), vec![0]);
+    }
+
+    #[test]
+    fn test_matches_any_needle() {
+        let patterns = ["foo", "bar", "Baz", "o b", "ééé", "a"];
+        let haystacks =
+            ["", "foo", "FOO", "a", "xbarx", "baz", "BAZ", "lo bo", "ééé", "ÉÉÉ", "xyz", "f o o"];
+        for case_sensitive in [true, false] {
+            let needle = AhoCorasickNeedle::new_with_case(&patterns, case_sensitive).unwrap();
+            let any: AnyNeedle = patterns
+                .iter()
+                .map(|p| StringMatch::from(*p).partial().with_case_sensitive(case_sensitive))
+                .collect();
+            for haystack in haystacks
+                .into_iter()
+                .filter(|h| case_sensitive || h.is_ascii() || h.to_lowercase() == *h)
+            {
+                assert_eq!(
+                    needle.is_match(haystack),
+                    any.is_match(haystack),
+                    "case_sensitive={case_sensitive} haystack={haystack:?}"
+                );
+            }
+        }
+    }
+}
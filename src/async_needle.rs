@@ -0,0 +1,131 @@
+use crate::{Needle, StringMatch};
+use core::future::Future;
+
+/// A needle whose matching logic is asynchronous, e.g. because it needs to call a remote
+/// service or query a database.
+///
+/// This is implemented for `StringMatch`, which matches immediately, and for async closures
+/// taking a `&str` and returning a `bool`.
+pub trait AsyncNeedle {
+    fn is_match_async<'a>(&'a self, haystack: &'a str) -> impl Future<Output = bool> + 'a;
+}
+
+impl AsyncNeedle for StringMatch {
+    fn is_match_async<'a>(&'a self, haystack: &'a str) -> impl Future<Output = bool> + 'a {
+        core::future::ready(self.is_match(haystack))
+    }
+}
+
+impl<F> AsyncNeedle for F
+where
+    F: AsyncFn(&str) -> bool,
+{
+    fn is_match_async<'a>(&'a self, haystack: &'a str) -> impl Future<Output = bool> + 'a {
+        self(haystack)
+    }
+}
+
+/// Methods for matching an `AsyncNeedle` against several haystacks.
+pub trait AsyncNeedleIter: AsyncNeedle {
+    /// Return true if any item matches.
+    ///
+    /// The items are matched one at a time, in order, and matching stops at the first match.
+    fn is_match_in_async<'a, I>(&'a self, haystacks: I) -> impl Future<Output = bool> + 'a
+    where
+        I: IntoIterator<Item = &'a str> + 'a,
+    {
+        async move {
+            for haystack in haystacks {
+                if self.is_match_async(haystack).await {
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+impl<T> AsyncNeedleIter for T where T: AsyncNeedle + ?Sized {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+    use tokio::sync::oneshot;
+
+    /// A needle that waits for each answer to arrive over a oneshot channel, recording the
+    /// haystacks it was asked about.
+    struct ChannelNeedle {
+        replies: Mutex<VecDeque<oneshot::Receiver<bool>>>,
+        asked: Mutex<Vec<String>>,
+    }
+
+    impl ChannelNeedle {
+        fn new(replies: impl IntoIterator<Item = oneshot::Receiver<bool>>) -> Self {
+            Self {
+                replies: Mutex::new(replies.into_iter().collect()),
+                asked: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn asked(&self) -> Vec<String> {
+            self.asked.lock().unwrap().clone()
+        }
+
+        /// Wait until the needle has been asked about `n` haystacks.
+        async fn wait_for_asked(&self, n: usize) {
+            while self.asked.lock().unwrap().len() < n {
+                tokio::task::yield_now().await;
+            }
+        }
+    }
+
+    impl AsyncNeedle for ChannelNeedle {
+        async fn is_match_async(&self, haystack: &str) -> bool {
+            self.asked.lock().unwrap().push(haystack.into());
+            let reply = self.replies.lock().unwrap().pop_front().expect("no reply channel left");
+            reply.await.unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_needle() {
+        let needle = StringMatch::from("test").case_insensitive();
+        assert!(needle.is_match_async("TEST").await);
+        assert!(!needle.is_match_async("tests").await);
+        assert!(needle.is_match_in_async(vec!["a", "Test"]).await);
+        assert!(!needle.is_match_in_async(Vec::new()).await);
+
+        let closure = async |s: &str| s.len() > 3;
+        assert!(closure.is_match_async("long").await);
+        assert!(!closure.is_match_async("abc").await);
+        assert!(closure.is_match_in_async(["a", "abcd"]).await);
+    }
+
+    #[tokio::test]
+    async fn test_async_needle_waits() {
+        let (tx, rx) = oneshot::channel();
+        let needle = ChannelNeedle::new([rx]);
+        let (matched, ()) = tokio::join!(needle.is_match_async("a"), async {
+            needle.wait_for_asked(1).await;
+            tx.send(true).unwrap();
+        });
+        assert!(matched);
+
+        // Each haystack is only tried after the previous answer arrives.
+        let (txs, rxs): (Vec<_>, Vec<_>) = (0..3).map(|_| oneshot::channel()).unzip();
+        let needle = ChannelNeedle::new(rxs);
+        let mut txs = txs.into_iter();
+        let (matched, ()) = tokio::join!(needle.is_match_in_async(["a", "b", "c"]), async {
+            needle.wait_for_asked(1).await;
+            assert_eq!(needle.asked(), vec!["a"]);
+            txs.next().unwrap().send(false).unwrap();
+            needle.wait_for_asked(2).await;
+            assert_eq!(needle.asked(), vec!["a", "b"]);
+            txs.next().unwrap().send(true).unwrap();
+        });
+        assert!(matched);
+        assert_eq!(needle.asked(), vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{Needle, NeedleIter};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// Needle that remembers the result of `is_match()` for each haystack it has seen.
+///
+/// This is useful when the same haystacks are tested repeatedly with an expensive needle,
+/// such as classifying log lines that repeat often. When the cache reaches its capacity it is
+/// cleared before the next result is stored.
+///
+/// The cache uses interior mutability and is not thread-safe, so `CachedNeedle` is not `Sync`.
+#[derive(Debug)]
+pub struct CachedNeedle<N> {
+    inner: N,
+    capacity: usize,
+    cache: RefCell<HashMap<String, bool>>,
+    hits: Cell<usize>,
+    misses: Cell<usize>,
+}
+
+impl<N> CachedNeedle<N>
+where
+    N: Needle,
+{
+    /// The capacity used by `CachedNeedle::new()`.
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    pub fn new(inner: N) -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY, inner)
+    }
+
+    /// Cache the results for at most `capacity` haystacks.
+    pub fn with_capacity(capacity: usize, inner: N) -> Self {
+        Self {
+            inner,
+            capacity,
+            cache: RefCell::new(HashMap::new()),
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> N {
+        self.inner
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of haystacks currently cached.
+    pub fn len(&self) -> usize {
+        self.cache.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.borrow().is_empty()
+    }
+
+    /// The number of calls to `is_match()` that were answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits.get()
+    }
+
+    /// The number of calls to `is_match()` that had to ask the inner needle.
+    pub fn misses(&self) -> usize {
+        self.misses.get()
+    }
+
+    /// Remove all cached results. The hit and miss counts are not reset.
+    pub fn clear(&self) {
+        self.cache.borrow_mut().clear();
+    }
+}
+
+/// Cloning produces a `CachedNeedle` with the same inner needle and capacity, but an empty
+/// cache.
+impl<N> Clone for CachedNeedle<N>
+where
+    N: Needle + Clone,
+{
+    fn clone(&self) -> Self {
+        Self::with_capacity(self.capacity, self.inner.clone())
+    }
+}
+
+impl<N> Needle for CachedNeedle<N>
+where
+    N: Needle,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        if let Some(&matched) = self.cache.borrow().get(haystack) {
+            self.hits.set(self.hits.get() + 1);
+            return matched;
+        }
+
+        self.misses.set(self.misses.get() + 1);
+        let matched = self.inner.is_match(haystack);
+        if self.capacity > 0 {
+            let mut cache = self.cache.borrow_mut();
+            if cache.len() >= self.capacity {
+                cache.clear();
+            }
+            cache.insert(haystack.to_string(), matched);
+        }
+        matched
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.inner.complexity_estimate()
+    }
+}
+
+impl<N> NeedleIter for CachedNeedle<N> where N: Needle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StringMatch;
+
+    #[test]
+    fn test_cached_needle() {
+        let needle = CachedNeedle::new(StringMatch::from("error").partial());
+        assert_eq!(needle.complexity_estimate(), 3);
+        assert!(needle.is_empty());
+        assert!(needle.is_match("an error occurred"));
+        assert!(!needle.is_match("all good"));
+        assert_eq!((needle.hits(), needle.misses()), (0, 2));
+
+        assert!(needle.is_match("an error occurred"));
+        assert!(!needle.is_match("all good"));
+        assert_eq!((needle.hits(), needle.misses()), (2, 2));
+        assert_eq!(needle.len(), 2);
+
+        // Clearing the cache doesn't change the results.
+        needle.clear();
+        assert!(needle.is_empty());
+        assert!(needle.is_match("an error occurred"));
+        assert!(!needle.is_match("all good"));
+        assert_eq!((needle.hits(), needle.misses()), (2, 4));
+
+        assert!(needle.is_match_in(&mut vec!["ok", "error"].into_iter()));
+        assert_eq!(needle.inner().text(), "error");
+    }
+
+    #[test]
+    fn test_cached_needle_capacity() {
+        let needle = CachedNeedle::with_capacity(2, |s: &str| s.len() > 1);
+        assert_eq!(needle.capacity(), 2);
+        assert!(!needle.is_match("a"));
+        assert!(needle.is_match("ab"));
+        assert_eq!(needle.len(), 2);
+        // The cache is full, so it is cleared before storing the next result.
+        assert!(needle.is_match("abc"));
+        assert_eq!(needle.len(), 1);
+        assert!(!needle.is_match("a"));
+        assert_eq!(needle.misses(), 4);
+
+        let uncached = CachedNeedle::with_capacity(0, |s: &str| s.is_empty());
+        assert!(uncached.is_match(""));
+        assert!(uncached.is_match(""));
+        assert!(uncached.is_empty());
+        assert_eq!(uncached.misses(), 2);
+    }
+
+    #[test]
+    fn test_cached_needle_clone() {
+        let needle = CachedNeedle::with_capacity(10, StringMatch::from("a"));
+        assert!(needle.is_match("a"));
+        let cloned = needle.clone();
+        assert!(cloned.is_empty());
+        assert_eq!(cloned.capacity(), 10);
+        assert_eq!((cloned.hits(), cloned.misses()), (0, 0));
+        assert!(cloned.is_match("a"));
+        assert_eq!(needle.len(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{Needle, SerializableRegex, StringMatch};
+use regex::Regex;
+
+/// The capture groups of a single match, returned by `CaptureNeedle::captures()`.
+///
+/// Group 0 is always the whole match.
+#[derive(Debug)]
+pub struct Captures<'h>(regex::Captures<'h>);
+
+impl<'h> Captures<'h> {
+    /// Return the text of the capture group with the given index, or `None` if the group does
+    /// not exist or did not participate in the match.
+    pub fn get(&self, index: usize) -> Option<&'h str> {
+        self.0.get(index).map(|m| m.as_str())
+    }
+
+    /// Return the text of the capture group with the given name, or `None` if the group does
+    /// not exist or did not participate in the match.
+    pub fn named_capture(&self, name: &str) -> Option<&'h str> {
+        self.0.name(name).map(|m| m.as_str())
+    }
+
+    /// The number of capture groups, including group 0 and groups that did not participate in
+    /// the match.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Always false, since group 0 is always present.
+    pub fn is_empty(&self) -> bool {
+        self.0.len() == 0
+    }
+
+    pub fn into_inner(self) -> regex::Captures<'h> {
+        self.0
+    }
+}
+
+impl<'h> From<regex::Captures<'h>> for Captures<'h> {
+    fn from(captures: regex::Captures<'h>) -> Self {
+        Self(captures)
+    }
+}
+
+/// A needle that can extract capture groups from the haystack.
+pub trait CaptureNeedle: Needle {
+    /// Return the capture groups of the first match in the haystack, or `None` if there is no
+    /// match or the needle has no groups to extract.
+    fn captures<'h>(&self, haystack: &'h str) -> Option<Captures<'h>>;
+}
+
+impl CaptureNeedle for Regex {
+    fn captures<'h>(&self, haystack: &'h str) -> Option<Captures<'h>> {
+        Regex::captures(self, haystack).map(Captures)
+    }
+}
+
+impl CaptureNeedle for SerializableRegex {
+    fn captures<'h>(&self, haystack: &'h str) -> Option<Captures<'h>> {
+        Regex::captures(self, haystack).map(Captures)
+    }
+}
+
+/// A `StringMatch` has no capture groups, so this always returns `None`.
+impl CaptureNeedle for StringMatch {
+    fn captures<'h>(&self, _haystack: &'h str) -> Option<Captures<'h>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regex_captures() {
+        let re = Regex::new(r"(\d+)-(?<word>[a-z]+)(x)?").unwrap();
+        let caps = CaptureNeedle::captures(&re, "id 12-abc!").unwrap();
+        assert_eq!(caps.len(), 4);
+        assert!(!caps.is_empty());
+        assert_eq!(caps.get(0), Some("12-abc"));
+        assert_eq!(caps.get(1), Some("12"));
+        assert_eq!(caps.get(2), Some("abc"));
+        assert_eq!(caps.named_capture("word"), Some("abc"));
+        // The optional group did not participate in the match.
+        assert_eq!(caps.get(3), None);
+        assert_eq!(caps.get(4), None);
+        assert_eq!(caps.named_capture("missing"), None);
+        assert_eq!(caps.into_inner().get(1).map(|m| m.start()), Some(3));
+
+        assert!(CaptureNeedle::captures(&re, "no digits").is_none());
+
+        let re = SerializableRegex::try_from(r"(?<n>\d)").unwrap();
+        assert_eq!(re.captures("a1").unwrap().named_capture("n"), Some("1"));
+    }
+
+    #[test]
+    fn test_dyn_capture_needle() {
+        let needles: Vec<Box<dyn CaptureNeedle>> = vec![
+            Box::new(StringMatch::from("ab").partial()),
+            Box::new(Regex::new(r"a(b)").unwrap()),
+        ];
+        assert!(needles.iter().all(|n| n.is_match("xab")));
+        let groups: Vec<_> =
+            needles.iter().map(|n| n.captures("xab").and_then(|c| c.get(1))).collect();
+        assert_eq!(groups, vec![None, Some("b")]);
+    }
+}
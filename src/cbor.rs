@@ -0,0 +1,50 @@
+use crate::StringMatch;
+
+impl StringMatch {
+    /// Serialize to the CBOR binary format.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, ciborium::ser::Error<std::io::Error>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserialize from the CBOR binary format, as written by `to_cbor()`.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ciborium::de::Error<std::io::Error>> {
+        ciborium::from_reader(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Needle;
+
+    #[test]
+    fn test_cbor_roundtrip() {
+        let needles = [
+            StringMatch::from("test"),
+            StringMatch::from("Test").case_insensitive(),
+            StringMatch::from("es").partial(),
+            StringMatch::from("te").prefix().case_insensitive(),
+            StringMatch::from("st").suffix(),
+            StringMatch::from("a b").word_with_separators(",\t "),
+            StringMatch::from(" a  b ").trim().normalize_whitespace(),
+            StringMatch::from("b").line_by_line().min_length(1).max_length(10).ascii_only(),
+            StringMatch::from("a,b").partial().ignore_punctuation().ignore_numbers(),
+            StringMatch::from("t*t").wildcard().collapse_whitespace(),
+        ];
+        let haystacks =
+            ["", "test", "Test", "TEST", "testing", "a test", "a,b", "a  b ", "a\nb", "é b"];
+        for orig in &needles {
+            let bytes = orig.to_cbor().unwrap();
+            let deserialized = StringMatch::from_cbor(&bytes).unwrap();
+            assert_eq!(&deserialized, orig);
+            for haystack in haystacks {
+                assert_eq!(deserialized.is_match(haystack), orig.is_match(haystack));
+            }
+        }
+
+        assert!(StringMatch::from_cbor(&[]).is_err());
+        assert!(StringMatch::from_cbor(&[0xff, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{BoxedNeedle, Needle, NeedleExt, NeedleIter, StringMatch};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A runtime collection of needles that matches if any of them match.
+///
+/// An empty `AnyNeedle` never matches.
+#[derive(Default)]
+pub struct AnyNeedle(Vec<BoxedNeedle>);
+
+impl AnyNeedle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push<N>(&mut self, needle: N)
+    where
+        N: Needle + Send + Sync + 'static,
+    {
+        self.0.push(Box::new(needle));
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Needle for AnyNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.0.iter().any(|n| n.is_match(haystack))
+    }
+
+    /// The sum of the estimates of the needles, the same as for a slice of them.
+    fn complexity_estimate(&self) -> u32 {
+        self.0.as_slice().complexity_estimate()
+    }
+}
+
+impl NeedleIter for AnyNeedle {}
+
+impl FromIterator<StringMatch> for AnyNeedle {
+    fn from_iter<T: IntoIterator<Item = StringMatch>>(iter: T) -> Self {
+        Self(iter.into_iter().map(|m| m.into_boxed_needle()).collect())
+    }
+}
+
+/// A runtime collection of needles that matches only if all of them match.
+///
+/// An empty `AllNeedle` always matches.
+#[derive(Default)]
+pub struct AllNeedle(Vec<BoxedNeedle>);
+
+impl AllNeedle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push<N>(&mut self, needle: N)
+    where
+        N: Needle + Send + Sync + 'static,
+    {
+        self.0.push(Box::new(needle));
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Needle for AllNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.0.iter().all(|n| n.is_match(haystack))
+    }
+
+    /// The sum of the estimates of the needles, the same as for a slice of them.
+    fn complexity_estimate(&self) -> u32 {
+        self.0.as_slice().complexity_estimate()
+    }
+}
+
+impl NeedleIter for AllNeedle {}
+
+impl FromIterator<StringMatch> for AllNeedle {
+    fn from_iter<T: IntoIterator<Item = StringMatch>>(iter: T) -> Self {
+        Self(iter.into_iter().map(|m| m.into_boxed_needle()).collect())
+    }
+}
+
+/// A runtime collection of named needles that matches if any of them match.
+///
+/// Use `triggered_names()` to find out which needles matched. Names are unique: adding a
+/// needle with an existing name replaces it. An empty `MultiNeedle` never matches.
+#[derive(Default)]
+pub struct MultiNeedle(Vec<(String, BoxedNeedle)>);
+
+impl MultiNeedle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a needle with the given name, replacing any existing needle with that name.
+    pub fn add<N>(&mut self, name: impl Into<String>, needle: N)
+    where
+        N: Needle + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let needle: BoxedNeedle = Box::new(needle);
+        match self.0.iter_mut().find(|(n, _)| *n == name) {
+            Some(entry) => entry.1 = needle,
+            None => self.0.push((name, needle)),
+        }
+    }
+
+    /// Remove the needle with the given name. Returns false if there was no such needle.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.0.len();
+        self.0.retain(|(n, _)| n != name);
+        self.0.len() != len
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|(n, _)| n == name)
+    }
+
+    /// The names of all needles, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(n, _)| n.as_str())
+    }
+
+    /// The names of all needles that match the haystack, in the order they were added.
+    pub fn triggered_names(&self, haystack: &str) -> Vec<&str> {
+        self.0.iter().filter(|(_, n)| n.is_match(haystack)).map(|(n, _)| n.as_str()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Needle for MultiNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.0.iter().any(|(_, n)| n.is_match(haystack))
+    }
+
+    /// The sum of the estimates of the needles, the same as for a slice of them.
+    fn complexity_estimate(&self) -> u32 {
+        self.0.iter().fold(0, |total, (_, n)| total.saturating_add(n.complexity_estimate()))
+    }
+}
+
+impl NeedleIter for MultiNeedle {}
+
+/// A slice of boxed needles matches if any of them match, like `AnyNeedle`.
+///
+/// An empty slice never matches. Needles are tried in order and evaluation stops at the
+/// first match. The complexity estimate is the sum of the elements' estimates, as they may
+/// all be tried.
+impl<'a> Needle for &[Box<dyn Needle + 'a>] {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.iter().any(|n| n.is_match(haystack))
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.iter().fold(0, |total, n| total.saturating_add(n.complexity_estimate()))
+    }
+}
+
+impl<'a> NeedleIter for &[Box<dyn Needle + 'a>] {}
+
+/// A slice of `BoxedNeedle` matches if any of them match, like `AnyNeedle`.
+impl<'a> Needle for &[Box<dyn Needle + Send + Sync + 'a>] {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.iter().any(|n| n.is_match(haystack))
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.iter().fold(0, |total, n| total.saturating_add(n.complexity_estimate()))
+    }
+}
+
+impl<'a> NeedleIter for &[Box<dyn Needle + Send + Sync + 'a>] {}
+
+/// A slice of needle references matches if any of them match, like `AnyNeedle`.
+impl Needle for &[&dyn Needle] {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.iter().any(|n| n.is_match(haystack))
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.iter().fold(0, |total, n| total.saturating_add(n.complexity_estimate()))
+    }
+}
+
+impl NeedleIter for &[&dyn Needle] {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use regex::Regex;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_any_needle() {
+        let mut any = AnyNeedle::new();
+        assert!(any.is_empty());
+        assert!(!any.is_match(""));
+        assert!(!any.is_match("Test"));
+
+        any.push(Regex::new(r"^\d+$").unwrap());
+        any.push(StringMatch::from("test").case_insensitive());
+        any.push(|s: &str| s.len() > 10);
+        assert_eq!(any.len(), 3);
+        assert!(!any.is_empty());
+        assert!(any.is_match("123"));
+        assert!(any.is_match("Test"));
+        assert!(any.is_match("a long haystack"));
+        assert!(!any.is_match("Tests"));
+        assert!(!any.is_match(""));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_all_needle() {
+        let mut all = AllNeedle::new();
+        assert!(all.is_empty());
+        assert!(all.is_match(""));
+        assert!(all.is_match("Test"));
+
+        all.push(Regex::new(r"^T").unwrap());
+        all.push(StringMatch::from("es").partial());
+        all.push(|s: &str| s.len() < 6);
+        assert_eq!(all.len(), 3);
+        assert!(!all.is_empty());
+        assert!(all.is_match("Test"));
+        assert!(all.is_match("Tes"));
+        assert!(!all.is_match("test"));
+        assert!(!all.is_match("Tent"));
+        assert!(!all.is_match("Testing"));
+    }
+
+    #[test]
+    fn test_collection_complexity_estimate() {
+        let a = StringMatch::from("a");
+        let b = StringMatch::from("b").word().case_insensitive();
+        let c = |s: &str| s.is_empty();
+        let slice: Vec<BoxedNeedle> = vec![Box::new(a.clone()), Box::new(b.clone()), Box::new(c)];
+        let expected = slice.as_slice().complexity_estimate();
+        assert_eq!(expected, 1 + 6 + 20);
+
+        let mut any = AnyNeedle::new();
+        let mut all = AllNeedle::new();
+        let mut multi = MultiNeedle::new();
+        assert_eq!(any.complexity_estimate(), 0);
+        assert_eq!(all.complexity_estimate(), 0);
+        assert_eq!(multi.complexity_estimate(), 0);
+        any.push(a.clone());
+        any.push(b.clone());
+        any.push(c);
+        all.push(a.clone());
+        all.push(b.clone());
+        all.push(c);
+        multi.add("a", a);
+        multi.add("b", b);
+        multi.add("c", c);
+        assert_eq!(any.complexity_estimate(), expected);
+        assert_eq!(all.complexity_estimate(), expected);
+        assert_eq!(multi.complexity_estimate(), expected);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let any: AnyNeedle =
+            vec![StringMatch::from("a"), StringMatch::from("b").partial()].into_iter().collect();
+        assert_eq!(any.len(), 2);
+        assert!(any.is_match("a"));
+        assert!(any.is_match("abc"));
+        assert!(!any.is_match("c"));
+
+        let all: AllNeedle =
+            vec![StringMatch::from("a").partial(), StringMatch::from("b").partial()]
+                .into_iter()
+                .collect();
+        assert_eq!(all.len(), 2);
+        assert!(all.is_match("ab"));
+        assert!(!all.is_match("a"));
+        assert!(all.is_match_in(&mut vec!["a", "ba"].into_iter()));
+
+        assert!(!AnyNeedle::from_iter(Vec::new()).is_match("a"));
+        assert!(AllNeedle::from_iter(Vec::new()).is_match("a"));
+    }
+
+    #[test]
+    fn test_multi_needle() {
+        let mut multi = MultiNeedle::new();
+        assert!(multi.is_empty());
+        assert!(!multi.is_match(""));
+        assert!(multi.triggered_names("anything").is_empty());
+
+        multi.add("greeting", StringMatch::from("hello").word());
+        multi.add(String::from("farewell"), StringMatch::from("bye").word());
+        multi.add("long", |s: &str| s.len() > 10);
+        assert_eq!(multi.len(), 3);
+        assert!(multi.contains("farewell"));
+        assert_eq!(multi.names().collect::<Vec<_>>(), vec!["greeting", "farewell", "long"]);
+        assert!(multi.is_match("hello"));
+        assert!(!multi.is_match("hi"));
+        assert_eq!(multi.triggered_names("hello and bye"), vec!["greeting", "farewell", "long"]);
+        assert_eq!(multi.triggered_names("bye"), vec!["farewell"]);
+        assert!(multi.triggered_names("hi").is_empty());
+
+        multi.add("greeting", StringMatch::from("hi").word());
+        assert_eq!(multi.len(), 3);
+        assert_eq!(multi.triggered_names("hi"), vec!["greeting"]);
+
+        assert!(multi.remove("farewell"));
+        assert!(!multi.remove("farewell"));
+        assert!(!multi.contains("farewell"));
+        assert_eq!(multi.len(), 2);
+        assert!(!multi.is_match("bye"));
+        assert!(multi.triggered_names("bye").is_empty());
+    }
+
+    fn panic_needle(_: &str) -> bool {
+        panic!("needle should not have been evaluated")
+    }
+
+    fn check_any<N: Needle>(needle: N, haystack: &str) -> bool {
+        needle.is_match(haystack)
+    }
+
+    #[test]
+    fn test_needle_slices() {
+        let boxed: Vec<Box<dyn Needle>> =
+            vec![Box::new("a"), Box::new(StringMatch::from("b").partial())];
+        let slice = boxed.as_slice();
+        assert!(!slice.is_match("c"));
+        assert!(slice.is_match("a"));
+        assert!(slice.is_match("abc"));
+        assert!(check_any(slice, "bb"));
+        assert!(slice.is_match_in(&mut vec!["x", "a"].into_iter()));
+        assert_eq!(slice.complexity_estimate(), 1 + 3);
+
+        let all_true: Vec<Box<dyn Needle>> = vec![Box::new("a"), Box::new(|_: &str| true)];
+        assert!(all_true.as_slice().is_match("a"));
+
+        let empty: &[Box<dyn Needle>] = &[];
+        assert!(!empty.is_match(""));
+
+        let sync: Vec<BoxedNeedle> = vec!["a".into_boxed_needle(), "b".into_boxed_needle()];
+        assert!(sync.as_slice().is_match("b"));
+        assert!(!sync.as_slice().is_match("c"));
+        assert_eq!(sync.as_slice().complexity_estimate(), 2);
+
+        let first = StringMatch::from("x");
+        let second = |s: &str| s.len() == 3;
+        let refs: [&dyn Needle; 2] = [&first, &second];
+        assert!(refs.as_slice().is_match("x"));
+        assert!(refs.as_slice().is_match("abc"));
+        assert!(!refs.as_slice().is_match("ab"));
+        assert_eq!(refs.as_slice().complexity_estimate(), 1 + 20);
+        let empty: &[&dyn Needle] = &[];
+        assert!(!empty.is_match(""));
+    }
+
+    #[test]
+    fn test_needle_slices_short_circuit() {
+        let boxed: Vec<Box<dyn Needle>> = vec![Box::new("a"), Box::new(panic_needle)];
+        assert!(boxed.as_slice().is_match("a"));
+        let refs: [&dyn Needle; 3] = [&"b", &"a", &panic_needle];
+        assert!(refs.as_slice().is_match("a"));
+    }
+}
@@ -0,0 +1,421 @@
+use crate::{Needle, NeedleIter, StringMatch};
+use core::ops;
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Serialize};
+
+/// Needle that matches every haystack.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct AlwaysNeedle;
+
+impl Needle for AlwaysNeedle {
+    fn is_match(&self, _haystack: &str) -> bool {
+        true
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        0
+    }
+}
+
+impl NeedleIter for AlwaysNeedle {}
+
+/// Needle that never matches any haystack.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct NeverNeedle;
+
+impl Needle for NeverNeedle {
+    fn is_match(&self, _haystack: &str) -> bool {
+        false
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        0
+    }
+}
+
+impl NeedleIter for NeverNeedle {}
+
+/// Needle that matches only if both inner needles match.
+///
+/// The second needle is not evaluated if the first one does not match.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct AndNeedle<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> AndNeedle<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+        }
+    }
+}
+
+impl<A, B> Needle for AndNeedle<A, B>
+where
+    A: Needle,
+    B: Needle,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        self.a.is_match(haystack) && self.b.is_match(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.a.complexity_estimate().saturating_add(self.b.complexity_estimate())
+    }
+}
+
+impl<A, B> NeedleIter for AndNeedle<A, B>
+where
+    A: Needle,
+    B: Needle,
+{
+}
+
+/// Needle that matches if at least one of the inner needles matches.
+///
+/// The second needle is not evaluated if the first one matches.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct OrNeedle<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> OrNeedle<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+        }
+    }
+}
+
+impl<A, B> Needle for OrNeedle<A, B>
+where
+    A: Needle,
+    B: Needle,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        self.a.is_match(haystack) || self.b.is_match(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.a.complexity_estimate().saturating_add(self.b.complexity_estimate())
+    }
+}
+
+impl<A, B> NeedleIter for OrNeedle<A, B>
+where
+    A: Needle,
+    B: Needle,
+{
+}
+
+/// Needle that matches only if the inner needle does not match.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct NotNeedle<N> {
+    inner: N,
+}
+
+impl<N> NotNeedle<N> {
+    pub fn new(inner: N) -> Self {
+        Self {
+            inner,
+        }
+    }
+}
+
+impl<N> Needle for NotNeedle<N>
+where
+    N: Needle,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        !self.inner.is_match(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.inner.complexity_estimate()
+    }
+}
+
+impl<N> NeedleIter for NotNeedle<N> where N: Needle {}
+
+/// Implement `Needle` for a tuple of needles, matching only if every element matches. The
+/// elements are evaluated left to right, stopping at the first one that does not match.
+macro_rules! impl_tuple_needle {
+    ($($name:ident),+) => {
+        impl<$($name),+> Needle for ($($name,)+)
+        where
+            $($name: Needle,)+
+        {
+            #[allow(non_snake_case)]
+            fn is_match(&self, haystack: &str) -> bool {
+                let ($($name,)+) = self;
+                $($name.is_match(haystack))&&+
+            }
+
+            #[allow(non_snake_case)]
+            fn complexity_estimate(&self) -> u32 {
+                let ($($name,)+) = self;
+                0u32$(.saturating_add($name.complexity_estimate()))+
+            }
+        }
+
+        impl<$($name),+> NeedleIter for ($($name,)+) where $($name: Needle,)+ {}
+    };
+}
+
+impl_tuple_needle!(A, B);
+impl_tuple_needle!(A, B, C);
+impl_tuple_needle!(A, B, C, D);
+
+/// Implement the `|`, `&` and `!` operators for a needle type, so that `a | b`, `a & b` and `!a`
+/// are the same as `a.or(b)`, `a.and(b)` and `a.not()`. The needles are moved into the
+/// combinator, not copied.
+macro_rules! impl_needle_ops {
+    ($ty:ident $(<$($param:ident),+>)?) => {
+        impl<$($($param,)+)? R> ops::BitOr<R> for $ty $(<$($param),+>)?
+        where
+            R: Needle,
+        {
+            type Output = OrNeedle<Self, R>;
+
+            fn bitor(self, rhs: R) -> Self::Output {
+                OrNeedle::new(self, rhs)
+            }
+        }
+
+        impl<$($($param,)+)? R> ops::BitAnd<R> for $ty $(<$($param),+>)?
+        where
+            R: Needle,
+        {
+            type Output = AndNeedle<Self, R>;
+
+            fn bitand(self, rhs: R) -> Self::Output {
+                AndNeedle::new(self, rhs)
+            }
+        }
+
+        impl$(<$($param),+>)? ops::Not for $ty $(<$($param),+>)? {
+            type Output = NotNeedle<Self>;
+
+            fn not(self) -> Self::Output {
+                NotNeedle::new(self)
+            }
+        }
+    };
+}
+
+impl_needle_ops!(StringMatch);
+impl_needle_ops!(AndNeedle<A, B>);
+impl_needle_ops!(OrNeedle<A, B>);
+impl_needle_ops!(NotNeedle<N>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NeedleExt;
+    #[cfg(feature = "std")]
+    use regex::Regex;
+
+    fn panic_needle(_: &str) -> bool {
+        panic!("needle should not have been evaluated")
+    }
+
+    #[test]
+    fn test_combinators() {
+        assert!(AndNeedle::new("Test", StringMatch::from("te").partial().case_insensitive())
+            .is_match("Test"));
+        assert!(!AndNeedle::new("Test", StringMatch::from("te").partial()).is_match("Test"));
+        assert!(!AndNeedle::new("test", "Test").is_match("Test"));
+
+        assert!(OrNeedle::new("test", "Test").is_match("Test"));
+        assert!(OrNeedle::new("Test", "test").is_match("Test"));
+        assert!(!OrNeedle::new("test", "TEST").is_match("Test"));
+
+        assert!(NotNeedle::new("test").is_match("Test"));
+        assert!(!NotNeedle::new("Test").is_match("Test"));
+    }
+
+    #[test]
+    fn test_always_never() {
+        assert!(AlwaysNeedle.is_match(""));
+        assert!(AlwaysNeedle.is_match("Test"));
+        assert!(!NeverNeedle.is_match(""));
+        assert!(!NeverNeedle.is_match("Test"));
+        assert!(StringMatch::always().is_match("Test"));
+        assert!(!StringMatch::never().is_match("Test"));
+
+        assert!(AlwaysNeedle.is_match_in(&mut vec!["a"].into_iter()));
+        assert!(!AlwaysNeedle.is_match_in(&mut Vec::<&str>::new().into_iter()));
+        assert!(!NeverNeedle.is_match_in(&mut vec!["a"].into_iter()));
+        assert!(NotNeedle::new(NeverNeedle).is_match_in(&mut vec!["a"].into_iter()));
+
+        // Identity elements.
+        assert!("Test".and(AlwaysNeedle).is_match("Test"));
+        assert!("Test".or(NeverNeedle).is_match("Test"));
+        assert!(!"test".and(AlwaysNeedle).is_match("Test"));
+        assert!(!"test".or(NeverNeedle).is_match("Test"));
+    }
+
+    #[cfg(feature = "serde_derive")]
+    #[test]
+    fn test_serde() {
+        let serialized = serde_json::to_string(&AlwaysNeedle).unwrap();
+        assert_eq!(serde_json::from_str::<AlwaysNeedle>(&serialized).unwrap(), AlwaysNeedle);
+        let serialized = serde_json::to_string(&NeverNeedle).unwrap();
+        assert_eq!(serde_json::from_str::<NeverNeedle>(&serialized).unwrap(), NeverNeedle);
+
+        let combined =
+            StringMatch::from("a").partial().or(String::from("b")).and(NeverNeedle.not());
+        let serialized = serde_json::to_string(&combined).unwrap();
+        let deserialized: AndNeedle<OrNeedle<StringMatch, String>, NotNeedle<NeverNeedle>> =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, combined);
+        assert!(deserialized.is_match("cat"));
+        assert!(!deserialized.is_match("dog"));
+    }
+
+    #[test]
+    fn test_eq_hash() {
+        use std::collections::HashSet;
+
+        assert_eq!(AndNeedle::new("a", "b"), "a".and("b"));
+        assert_ne!(AndNeedle::new("a", "b"), AndNeedle::new("b", "a"));
+        assert_eq!(OrNeedle::new("a", 'b'), "a".or('b'));
+        assert_ne!(OrNeedle::new("a", 'b'), OrNeedle::new("a", 'c'));
+        assert_eq!(NotNeedle::new(AlwaysNeedle), AlwaysNeedle.not());
+
+        let set: HashSet<_> = ["a".or("b"), "a".or("b"), "b".or("a")].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        let set: HashSet<_> = [NotNeedle::new("a"), "a".not()].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_needle_ext() {
+        assert!("Test".and(Regex::new("^T").unwrap()).is_match("Test"));
+        assert!(!"Test".and(Regex::new("^t").unwrap()).is_match("Test"));
+        assert!("test".or(Regex::new("^T").unwrap()).is_match("Test"));
+        assert!(!"test".or(Regex::new("^t").unwrap()).is_match("Test"));
+        assert!("test".not().is_match("Test"));
+        assert!(!"Test".not().is_match("Test"));
+
+        let combined: OrNeedle<AndNeedle<&str, StringMatch>, Regex> =
+            "test".and(StringMatch::from("t").prefix()).or(Regex::new("^T.+t$").unwrap());
+        assert!(combined.is_match("Test"));
+        assert!(combined.is_match("Tent"));
+        assert!(!combined.is_match("Tens"));
+        assert!(combined.not().is_match("Tens"));
+    }
+
+    #[test]
+    fn test_operators() {
+        let combined = StringMatch::new("hello").partial() | StringMatch::new("world").partial();
+        assert!(combined.is_match("hello there"));
+        assert!(combined.is_match("hello world"));
+        assert!(combined.is_match("the world"));
+        assert!(!combined.is_match("goodbye"));
+        assert_eq!(
+            combined,
+            OrNeedle::new(StringMatch::new("hello").partial(), StringMatch::new("world").partial())
+        );
+
+        let combined = StringMatch::new("hello").partial() & StringMatch::new("world").partial();
+        assert!(combined.is_match("hello world"));
+        assert!(!combined.is_match("hello there"));
+        assert!(!combined.is_match("the world"));
+
+        let negated = !StringMatch::new("hello").partial();
+        assert!(negated.is_match("goodbye"));
+        assert!(!negated.is_match("hello there"));
+        assert_eq!(negated, NotNeedle::new(StringMatch::new("hello").partial()));
+
+        // Combinators can be chained, and mixed with other needle types.
+        let combined = (StringMatch::new("a").prefix() | "xyz") & !StringMatch::new("z").suffix();
+        assert!(combined.is_match("abc"));
+        assert!(!combined.is_match("abz"));
+        assert!(!combined.is_match("xyz"));
+        assert!(!combined.is_match("bcd"));
+        assert!((!!StringMatch::new("a")).is_match("a"));
+        assert!((!(StringMatch::new("a") & NeverNeedle)).is_match("a"));
+        assert!((StringMatch::new("a") | StringMatch::new("b") | 'c').is_match("c"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_operators_regex() {
+        let combined = StringMatch::new("test") | Regex::new("^T.+t$").unwrap();
+        assert!(combined.is_match("test"));
+        assert!(combined.is_match("Tent"));
+        assert!(!combined.is_match("Tens"));
+
+        let combined = StringMatch::new("t").prefix() & Regex::new("s").unwrap();
+        assert!(combined.is_match("tests"));
+        assert!(!combined.is_match("tent"));
+    }
+
+    #[test]
+    fn test_tuples() {
+        let needle = (StringMatch::from("a").prefix(), StringMatch::from("z").suffix());
+        assert!(needle.is_match("abcz"));
+        assert!(!needle.is_match("abc"));
+        assert!(!needle.is_match("bcz"));
+        assert!(needle.is_match_in(&mut vec!["a", "az"].into_iter()));
+        assert_eq!(needle.complexity_estimate(), 2);
+
+        let needle = (StringMatch::from("ab").partial(), |s: &str| s.len() < 5, "xaby");
+        assert!(needle.is_match("xaby"));
+        assert!(!needle.is_match("xab"));
+        let needle = (AlwaysNeedle, 'a', NotNeedle::new("b"), AlwaysNeedle);
+        assert!(needle.is_match("a"));
+        assert!(!needle.is_match("b"));
+
+        // Elements are evaluated left to right, stopping at the first mismatch.
+        assert!(!("a", panic_needle).is_match("b"));
+        assert!(!("a", "a", panic_needle).is_match("b"));
+        assert!(!(AlwaysNeedle, "a", NeverNeedle, panic_needle).is_match("a"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_tuples_regex() {
+        let needle = (StringMatch::from("GET ").prefix(), Regex::new(r"/\d+$").unwrap());
+        assert!(needle.is_match("GET /users/42"));
+        assert!(!needle.is_match("POST /users/42"));
+        assert!(!needle.is_match("GET /users"));
+
+        let needle = ("GET /", Regex::new("^GET").unwrap(), StringMatch::from("/").suffix());
+        assert!(needle.is_match("GET /"));
+        assert!(!needle.is_match("GET /a"));
+        assert_eq!(needle.complexity_estimate(), 12);
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        assert!("Test".or(panic_needle).is_match("Test"));
+        assert!(!"test".and(panic_needle).is_match("Test"));
+        assert!(OrNeedle::new(|_: &str| true, panic_needle).is_match("Test"));
+        assert!(!AndNeedle::new(|_: &str| false, panic_needle).is_match("Test"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_no_short_circuit() {
+        "test".or(panic_needle).is_match("Test");
+    }
+
+    #[test]
+    fn test_needle_iter() {
+        assert!("a".or("b").is_match_in(&mut vec!["c", "b"].into_iter()));
+        assert!(!"a".and("b").is_match_in(&mut vec!["a", "b"].into_iter()));
+    }
+}
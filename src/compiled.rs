@@ -0,0 +1,153 @@
+use crate::{Needle, NeedleIter, StringMatch};
+use alloc::string::String;
+
+/// A `StringMatch` with its needle text precomputed for repeated matching.
+///
+/// Created by `StringMatch::compile()`. The needle is normalized (lowercased, and with any
+/// whitespace or diacritic options applied) once, rather than on every call to `is_match()`.
+/// Matching behaves exactly as it does for the source `StringMatch`.
+#[derive(Debug, Clone)]
+pub struct CompiledStringMatch {
+    source: StringMatch,
+    /// The needle text with all of the source's options applied.
+    needle: String,
+}
+
+impl CompiledStringMatch {
+    pub fn new(source: StringMatch) -> Self {
+        let needle = source.compiled_text().into_owned();
+        Self {
+            source,
+            needle,
+        }
+    }
+
+    /// The `StringMatch` this was compiled from.
+    pub fn source(&self) -> &StringMatch {
+        &self.source
+    }
+
+    /// Mutable access to the source `StringMatch`.
+    ///
+    /// Call `recompile()` after making changes, otherwise they won't take effect.
+    pub fn source_mut(&mut self) -> &mut StringMatch {
+        &mut self.source
+    }
+
+    /// Recompute the normalized needle from the source `StringMatch`.
+    pub fn recompile(&mut self) {
+        self.needle = self.source.compiled_text().into_owned();
+    }
+
+    /// Return the source `StringMatch`.
+    pub fn into_inner(self) -> StringMatch {
+        self.source
+    }
+}
+
+impl From<StringMatch> for CompiledStringMatch {
+    fn from(source: StringMatch) -> Self {
+        Self::new(source)
+    }
+}
+
+impl Needle for CompiledStringMatch {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.source.is_match_compiled(&self.needle, haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.source.complexity_estimate()
+    }
+}
+
+impl NeedleIter for CompiledStringMatch {}
+
+impl StringMatch {
+    /// Precompute the normalized needle text, for faster repeated matching.
+    pub fn compile(self) -> CompiledStringMatch {
+        CompiledStringMatch::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HAYSTACKS: &[&str] = &[
+        "",
+        "a",
+        "A",
+        "ab",
+        "ba",
+        "a b",
+        "b a b",
+        "ab ab",
+        "Test",
+        "test",
+        "TEST",
+        "Testing",
+        "a test case",
+        "a  Test\tcase ",
+        "  test  ",
+        "café",
+        "CAFÉ Cafe",
+        "ΣΑΣ σας",
+    ];
+
+    fn variants(text: &str) -> Vec<StringMatch> {
+        let base = StringMatch::from(text);
+        let lengths = [
+            base.clone().full(),
+            base.clone().partial(),
+            base.clone().word(),
+            base.clone().word_with_separators("\t "),
+            base.clone().prefix(),
+            base.clone().suffix(),
+        ];
+        let mut variants = Vec::new();
+        for sm in lengths {
+            variants.push(sm.clone().case_insensitive().trim().normalize_whitespace());
+            variants.push(sm.clone().case_insensitive());
+            variants.push(sm.clone().trim());
+            variants.push(sm.clone().normalize_whitespace());
+            #[cfg(feature = "unicode")]
+            variants.push(sm.clone().case_insensitive().strip_diacritics());
+            variants.push(sm);
+        }
+        variants
+    }
+
+    #[test]
+    fn test_compiled_matches_source() {
+        for text in ["", "a", "b", "a b", "ab", "test", "Test", "a  test", "café", "σας"] {
+            for sm in variants(text) {
+                let compiled = sm.clone().compile();
+                for haystack in HAYSTACKS {
+                    assert_eq!(
+                        compiled.is_match(haystack),
+                        sm.is_match(haystack),
+                        "{:?} on {:?}",
+                        sm,
+                        haystack
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_recompile() {
+        let mut compiled = StringMatch::from("Test").case_insensitive().compile();
+        assert!(compiled.is_match("TEST"));
+        assert_eq!(compiled.source().text(), "Test");
+        assert!(compiled.is_match_in(&mut vec!["x", "test"].into_iter()));
+
+        *compiled.source_mut() = StringMatch::from("other").partial();
+        compiled.recompile();
+        assert!(!compiled.is_match("TEST"));
+        assert!(compiled.is_match("another"));
+        assert!(!compiled.is_match("ANOTHER"));
+        assert_eq!(compiled.into_inner(), StringMatch::from("other").partial());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{Needle, NeedleIter, StringMatch};
+
+/// A needle whose result depends on some external context of type `C`, such as the current
+/// locale or the phase of a parse.
+///
+/// This is implemented for closures taking the haystack and a `&C`, and for `StringMatch`,
+/// which ignores the context.
+pub trait ContextNeedle<C> {
+    fn is_match_with_context(&self, haystack: &str, context: &C) -> bool;
+
+    /// Turn this into a plain `Needle` that is always called with `context`.
+    fn with_context(self, context: C) -> WithContext<Self, C>
+    where
+        Self: Sized,
+    {
+        WithContext::new(self, context)
+    }
+
+    /// Turn this into a plain `Needle` for when no context is available.
+    ///
+    /// The needle is always called with `C::default()` as its context.
+    fn ignore_context(self) -> WithContext<Self, C>
+    where
+        Self: Sized,
+        C: Default,
+    {
+        WithContext::new(self, C::default())
+    }
+}
+
+impl<C, F> ContextNeedle<C> for F
+where
+    F: Fn(&str, &C) -> bool,
+{
+    fn is_match_with_context(&self, haystack: &str, context: &C) -> bool {
+        self(haystack, context)
+    }
+}
+
+impl<C> ContextNeedle<C> for StringMatch {
+    fn is_match_with_context(&self, haystack: &str, _context: &C) -> bool {
+        self.is_match(haystack)
+    }
+}
+
+/// A `ContextNeedle` bound to a fixed context, which makes it a plain `Needle`.
+///
+/// Created by `ContextNeedle::with_context()` or `ContextNeedle::ignore_context()`.
+#[derive(Debug, Clone)]
+pub struct WithContext<N, C> {
+    needle: N,
+    context: C,
+}
+
+impl<N, C> WithContext<N, C>
+where
+    N: ContextNeedle<C>,
+{
+    pub fn new(needle: N, context: C) -> Self {
+        Self {
+            needle,
+            context,
+        }
+    }
+
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    /// Replace the context that the needle is called with.
+    pub fn set_context(&mut self, context: C) {
+        self.context = context;
+    }
+
+    pub fn into_inner(self) -> N {
+        self.needle
+    }
+}
+
+impl<N, C> Needle for WithContext<N, C>
+where
+    N: ContextNeedle<C>,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        self.needle.is_match_with_context(haystack, &self.context)
+    }
+}
+
+impl<N, C> NeedleIter for WithContext<N, C> where N: ContextNeedle<C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LengthIs;
+
+    impl ContextNeedle<usize> for LengthIs {
+        fn is_match_with_context(&self, haystack: &str, context: &usize) -> bool {
+            haystack.len() == *context
+        }
+    }
+
+    #[test]
+    fn test_context_needle() {
+        assert!(LengthIs.is_match_with_context("abc", &3));
+        assert!(!LengthIs.is_match_with_context("abc", &2));
+
+        let mut needle = LengthIs.with_context(2);
+        assert_eq!(*needle.context(), 2);
+        assert!(needle.is_match("ab"));
+        assert!(!needle.is_match("abc"));
+        assert!(needle.is_match_in(&mut vec!["a", "bc"].into_iter()));
+        needle.set_context(3);
+        assert!(needle.is_match("abc"));
+
+        // Without a context the needle gets the default context, 0.
+        let needle = LengthIs.ignore_context();
+        assert!(needle.is_match(""));
+        assert!(!needle.is_match("a"));
+
+        let closure = |s: &str, prefix: &&str| s.starts_with(prefix);
+        assert!(closure.is_match_with_context("foobar", &"foo"));
+        assert!(!closure.is_match_with_context("foobar", &"bar"));
+        assert!(closure.with_context("bar").is_match("barfoo"));
+        assert!(closure.ignore_context().is_match("anything"));
+
+        let sm = StringMatch::from("ab").partial();
+        assert!(sm.is_match_with_context("cab", &"ignored"));
+        assert!(ContextNeedle::<usize>::is_match_with_context(&sm, "ab", &0));
+        assert!(!ContextNeedle::<()>::is_match_with_context(&sm, "a", &()));
+        let needle: WithContext<_, ()> = sm.clone().ignore_context();
+        assert!(needle.is_match("xaby"));
+        assert_eq!(needle.into_inner(), sm);
+    }
+}
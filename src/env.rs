@@ -0,0 +1,67 @@
+use crate::{StringMatch, StringMatchParseError};
+use std::env::{self, VarError};
+
+impl StringMatch {
+    /// Create a full, case-sensitive `StringMatch` from the value of the environment
+    /// variable `var`.
+    ///
+    /// Changing the environment with `std::env::set_var()` is not thread-safe: on many
+    /// platforms it can race with any other thread reading the environment, including this
+    /// function. Set any variables before starting other threads.
+    pub fn from_env(var: &str) -> Result<Self, VarError> {
+        env::var(var).map(Self::new)
+    }
+
+    /// Parse a `StringMatch`, including any leading flags such as `[partial,ci]`, from the
+    /// value of the environment variable `var`. See `FromStr` for the format.
+    ///
+    /// As with `from_env()`, don't change the environment while other threads may be reading
+    /// it.
+    pub fn from_env_with_flags(var: &str) -> Result<Self, StringMatchParseError> {
+        env::var(var)?.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `env::set_var` affects the whole process and is not thread-safe, so each test uses its
+    // own variable names, which are set once and never removed.
+
+    #[test]
+    fn test_from_env() {
+        env::set_var("STRINGMATCH_TEST_FROM_ENV", "[partial] hello");
+        assert_eq!(
+            StringMatch::from_env("STRINGMATCH_TEST_FROM_ENV").unwrap(),
+            StringMatch::new("[partial] hello")
+        );
+        assert_eq!(
+            StringMatch::from_env("STRINGMATCH_TEST_FROM_ENV_MISSING"),
+            Err(VarError::NotPresent)
+        );
+    }
+
+    #[test]
+    fn test_from_env_with_flags() {
+        env::set_var("STRINGMATCH_TEST_WITH_FLAGS", "[partial, ci] hello");
+        env::set_var("STRINGMATCH_TEST_WITH_FLAGS_PLAIN", "hello");
+        env::set_var("STRINGMATCH_TEST_WITH_FLAGS_BAD", "[nope] hello");
+        assert_eq!(
+            StringMatch::from_env_with_flags("STRINGMATCH_TEST_WITH_FLAGS").unwrap(),
+            StringMatch::new("hello").partial().case_insensitive()
+        );
+        assert_eq!(
+            StringMatch::from_env_with_flags("STRINGMATCH_TEST_WITH_FLAGS_PLAIN").unwrap(),
+            StringMatch::new("hello")
+        );
+        assert_eq!(
+            StringMatch::from_env_with_flags("STRINGMATCH_TEST_WITH_FLAGS_BAD"),
+            Err(StringMatchParseError::UnknownFlag("nope".to_string()))
+        );
+
+        let err = StringMatch::from_env_with_flags("STRINGMATCH_TEST_WITH_FLAGS_MISSING");
+        assert_eq!(err, Err(StringMatchParseError::EnvVar(VarError::NotPresent)));
+        assert!(err.unwrap_err().to_string().starts_with("environment variable error"));
+    }
+}
@@ -0,0 +1,61 @@
+use alloc::string::String;
+use core::fmt;
+
+/// The error returned when a needle cannot be constructed or fails validation.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum StringMatchError {
+    /// A regular expression failed to compile.
+    #[cfg(feature = "std")]
+    InvalidRegex(regex::Error),
+    /// A glob pattern failed to compile.
+    InvalidGlob(String),
+    /// The `min_length()` is greater than the `max_length()`, so no haystack can match.
+    InvalidLengthBounds {
+        min: usize,
+        max: usize,
+    },
+    /// A `Word` match has an empty list of word separators.
+    EmptySeparators,
+}
+
+impl fmt::Display for StringMatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            StringMatchError::InvalidRegex(e) => write!(f, "invalid regex: {}", e),
+            StringMatchError::InvalidGlob(e) => write!(f, "invalid glob: {}", e),
+            StringMatchError::InvalidLengthBounds {
+                min,
+                max,
+            } => {
+                write!(f, "minimum length {} is greater than maximum length {}", min, max)
+            }
+            StringMatchError::EmptySeparators => write!(f, "word separators are empty"),
+        }
+    }
+}
+
+impl core::error::Error for StringMatchError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            StringMatchError::InvalidRegex(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<regex::Error> for StringMatchError {
+    fn from(e: regex::Error) -> Self {
+        StringMatchError::InvalidRegex(e)
+    }
+}
+
+#[cfg(feature = "glob")]
+impl From<crate::GlobError> for StringMatchError {
+    fn from(e: crate::GlobError) -> Self {
+        StringMatchError::InvalidGlob(e.to_string())
+    }
+}
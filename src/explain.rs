@@ -0,0 +1,271 @@
+use crate::find::FindableNeedle;
+use crate::{Needle, StringMatch, StringMatchLength};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+impl StringMatch {
+    /// Describe whether and where this needle matches the haystack, for debugging.
+    ///
+    /// e.g. `[Partial, case-insensitive] needle "ell" matched haystack "Hello" at byte offset 1`.
+    /// When the match fails, a hint is added if changing the case sensitivity or match length
+    /// would let it succeed. The exact wording is not stable and should not be parsed.
+    pub fn explain(&self, haystack: &str) -> String {
+        let case = match self.case_sensitive {
+            true => "case-sensitive",
+            false => "case-insensitive",
+        };
+        let matched = self.is_match(haystack);
+        let prefix = format!(
+            "[{:?}, {}] needle {:?} {} haystack {:?}",
+            self.match_length,
+            case,
+            self.text,
+            match matched {
+                true => "matched",
+                false => "did not match",
+            },
+            haystack
+        );
+
+        if matched {
+            let offset = match self.match_length {
+                StringMatchLength::Partial | StringMatchLength::Word => self.find(haystack),
+                _ => None,
+            };
+            return match offset {
+                Some(span) => format!("{} at byte offset {}", prefix, span.byte_start),
+                None => prefix,
+            };
+        }
+
+        match self.hint(haystack) {
+            Some(hint) => format!("{} (hint: try {})", prefix, hint),
+            None => prefix,
+        }
+    }
+
+    /// Describe this needle in plain English, for log messages, validation feedback and UI
+    /// tooltips.
+    ///
+    /// e.g. `Matches text that contains "foo" (case-insensitive)`. The needle text is quoted,
+    /// with quotes, backslashes and control characters escaped. The exact wording is not
+    /// stable and should not be parsed; use `Display` and `str::parse()` for that.
+    pub fn to_human_description(&self) -> String {
+        let text = match self.wildcard {
+            true => format!("the wildcard pattern \"{}\"", self.text.escape_debug()),
+            false => format!("\"{}\"", self.text.escape_debug()),
+        };
+        let mut description = match self.match_length {
+            StringMatchLength::Full if self.wildcard => format!("Matches {}", text),
+            StringMatchLength::Full => format!("Exactly equals {}", text),
+            StringMatchLength::Partial => format!("Matches text that contains {}", text),
+            StringMatchLength::Word if self.wildcard => {
+                format!("Matches text with whole words that match {}", text)
+            }
+            StringMatchLength::Word => format!("Matches text that contains the word {}", text),
+            StringMatchLength::Prefix => format!("Matches text that starts with {}", text),
+            StringMatchLength::Suffix => format!("Matches text that ends with {}", text),
+        };
+        if self.multiline {
+            description.push_str(" on any line");
+        }
+
+        let mut details = Vec::new();
+        if !self.case_sensitive {
+            details.push(String::from("case-insensitive"));
+        }
+        if let Some(separators) = self.separators() {
+            details.push(format!("words separated by any of \"{}\"", separators.escape_debug()));
+        }
+        if self.normalize_whitespace || self.collapse_whitespace {
+            details.push(String::from("ignoring repeated whitespace"));
+        }
+        if self.trim || self.collapse_whitespace {
+            details.push(String::from("ignoring leading and trailing whitespace"));
+        } else if self.trim_end {
+            details.push(String::from("ignoring trailing whitespace"));
+        }
+        if self.ignore_punctuation {
+            details.push(String::from("ignoring punctuation"));
+        }
+        if self.ignore_numbers {
+            details.push(String::from("ignoring numbers"));
+        }
+        if self.is_diacritic_insensitive() {
+            details.push(String::from("ignoring accents"));
+        }
+        if self.is_unicode_normalized() {
+            details.push(String::from("ignoring Unicode normalization differences"));
+        }
+        if self.ascii_only {
+            details.push(String::from("ASCII text only"));
+        }
+        match self.length_bounds() {
+            (Some(min), Some(max)) => {
+                details.push(format!("text between {} and {} characters long", min, max))
+            }
+            (Some(min), None) => details.push(format!("text at least {} characters long", min)),
+            (None, Some(max)) => details.push(format!("text at most {} characters long", max)),
+            (None, None) => {}
+        }
+
+        if !details.is_empty() {
+            description = format!("{} ({})", description, details.join(", "));
+        }
+        description
+    }
+
+    /// Suggest a builder method that would make the needle match the haystack.
+    fn hint(&self, haystack: &str) -> Option<&'static str> {
+        if self.case_sensitive && self.clone().case_insensitive().is_match(haystack) {
+            return Some("case_insensitive()");
+        }
+        if self.is_partial_match() {
+            return None;
+        }
+        let partial = self.clone().partial();
+        if partial.is_match(haystack) {
+            return Some("partial()");
+        }
+        (self.case_sensitive && partial.case_insensitive().is_match(haystack))
+            .then_some("partial().case_insensitive()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(
+            StringMatch::from("hello").explain("Hello"),
+            r#"[Full, case-sensitive] needle "hello" did not match haystack "Hello" (hint: try case_insensitive())"#
+        );
+        assert_eq!(
+            StringMatch::from("ell").partial().case_insensitive().explain("Hello"),
+            r#"[Partial, case-insensitive] needle "ell" matched haystack "Hello" at byte offset 1"#
+        );
+        assert_eq!(
+            StringMatch::from("hello").explain("hello"),
+            r#"[Full, case-sensitive] needle "hello" matched haystack "hello""#
+        );
+
+        let explanation = StringMatch::from("world").word().explain("hello world");
+        assert!(explanation.contains("[Word, case-sensitive]"));
+        assert!(explanation.ends_with("at byte offset 6"));
+
+        let explanation = StringMatch::from("ell").prefix().explain("Hello");
+        assert!(explanation.contains("[Prefix, case-sensitive]"));
+        assert!(explanation.contains("did not match"));
+        assert!(explanation.ends_with("(hint: try partial())"));
+
+        let explanation = StringMatch::from("LL").suffix().explain("Hello");
+        assert!(explanation.contains("[Suffix, case-sensitive]"));
+        assert!(explanation.ends_with("(hint: try partial().case_insensitive())"));
+
+        let explanation = StringMatch::from("x").partial().explain("Hello");
+        assert!(explanation.ends_with(r#"haystack "Hello""#));
+    }
+
+    #[test]
+    fn test_explain_unicode() {
+        let needles = ["é", "e\u{301}", "日本", "ß", "İ", "", "\"quoted\"", "\n"];
+        let haystacks = ["Café", "CAFE\u{301}", "日本語", "STRASSE", "i̇stanbul", "", "\u{0}"];
+        for text in needles {
+            let base = StringMatch::from(text);
+            let variants = [
+                base.clone(),
+                base.clone().partial(),
+                base.clone().word(),
+                base.clone().prefix(),
+                base.clone().suffix(),
+                base.clone().case_insensitive(),
+                base.clone().partial().case_insensitive(),
+                base.clone().word().case_insensitive(),
+            ];
+            for sm in variants {
+                for haystack in haystacks {
+                    let explanation = sm.explain(haystack);
+                    assert!(explanation.contains(&format!("{:?}", text)));
+                    assert!(explanation.contains(&format!("{:?}", haystack)));
+                    assert!(explanation.contains(&format!("{:?}", sm.match_length())));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_human_description() {
+        assert_eq!(
+            StringMatch::from("Hello World").to_human_description(),
+            r#"Exactly equals "Hello World""#
+        );
+        assert_eq!(
+            StringMatch::from("foo").partial().case_insensitive().to_human_description(),
+            r#"Matches text that contains "foo" (case-insensitive)"#
+        );
+        assert_eq!(
+            StringMatch::from("foo").word().to_human_description(),
+            r#"Matches text that contains the word "foo""#
+        );
+        assert_eq!(
+            StringMatch::from("foo").prefix().line_by_line().to_human_description(),
+            r#"Matches text that starts with "foo" on any line"#
+        );
+        assert_eq!(
+            StringMatch::from("foo")
+                .suffix()
+                .trim()
+                .normalize_whitespace()
+                .min_length(2)
+                .max_length(8)
+                .ascii_only()
+                .to_human_description(),
+            "Matches text that ends with \"foo\" (ignoring repeated whitespace, ignoring leading \
+             and trailing whitespace, ASCII text only, text between 2 and 8 characters long)"
+        );
+        assert_eq!(
+            StringMatch::from("a").word_with_separators(",\t").to_human_description(),
+            r#"Matches text that contains the word "a" (words separated by any of ",\t")"#
+        );
+
+        assert_eq!(
+            StringMatch::from_wildcard("a*b?").to_human_description(),
+            r#"Matches the wildcard pattern "a*b?""#
+        );
+        assert_eq!(
+            StringMatch::from_wildcard("a*").word().case_insensitive().to_human_description(),
+            r#"Matches text with whole words that match the wildcard pattern "a*" (case-insensitive)"#
+        );
+
+        assert_eq!(
+            StringMatch::from("a b").collapse_whitespace().to_human_description(),
+            "Exactly equals \"a b\" (ignoring repeated whitespace, ignoring leading and trailing \
+             whitespace)"
+        );
+
+        // Special characters in the needle are escaped.
+        assert_eq!(
+            StringMatch::from("say \"hi\"\n\\").partial().to_human_description(),
+            r#"Matches text that contains "say \"hi\"\n\\""#
+        );
+        for text in ["", "é", "日本", "\u{0}", "'\"'", "\u{202e}rtl"] {
+            for needle in [StringMatch::from(text), StringMatch::from(text).word()] {
+                let description = needle.to_human_description();
+                assert!(description.contains(&format!("\"{}\"", text.escape_debug())));
+                assert!(!description.chars().any(char::is_control));
+            }
+        }
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_to_human_description_unicode() {
+        assert_eq!(
+            StringMatch::from("café").partial().strip_diacritics().nfc().to_human_description(),
+            r#"Matches text that contains "café" (ignoring accents, ignoring Unicode normalization differences)"#
+        );
+    }
+}
@@ -0,0 +1,740 @@
+use crate::wildcard::find_pattern;
+use crate::{find_word, lines, Needle, StringMatch, StringMatchLength};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use regex::Regex;
+
+/// The location of a match within a haystack, as both byte and character offsets.
+///
+/// `&haystack[span.byte_start..span.byte_end]` is always valid UTF-8 for the haystack the
+/// span was produced from. The character offsets count Unicode scalar values.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct MatchSpan {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+impl MatchSpan {
+    /// Create a span from byte offsets within `haystack`, calculating the character offsets.
+    ///
+    /// Panics if the offsets are not on character boundaries.
+    pub fn new(haystack: &str, byte_start: usize, byte_end: usize) -> Self {
+        let char_start = haystack[..byte_start].chars().count();
+        Self {
+            byte_start,
+            byte_end,
+            char_start,
+            char_end: char_start + haystack[byte_start..byte_end].chars().count(),
+        }
+    }
+
+    /// The length of the match in bytes.
+    pub fn len(&self) -> usize {
+        self.byte_end - self.byte_start
+    }
+
+    /// The length of the match in characters.
+    pub fn char_len(&self) -> usize {
+        self.char_end - self.char_start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.byte_start == self.byte_end
+    }
+
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_start..self.byte_end
+    }
+
+    pub fn char_range(&self) -> Range<usize> {
+        self.char_start..self.char_end
+    }
+
+    /// Return the matched text within the haystack this span was produced from.
+    pub fn as_str<'h>(&self, haystack: &'h str) -> &'h str {
+        &haystack[self.byte_start..self.byte_end]
+    }
+
+    /// Move the span later by the given number of bytes and characters.
+    fn shift(self, bytes: usize, chars: usize) -> Self {
+        Self {
+            byte_start: self.byte_start + bytes,
+            byte_end: self.byte_end + bytes,
+            char_start: self.char_start + chars,
+            char_end: self.char_end + chars,
+        }
+    }
+}
+
+/// Converts to the byte range of the span.
+impl From<MatchSpan> for Range<usize> {
+    fn from(span: MatchSpan) -> Self {
+        span.byte_range()
+    }
+}
+
+/// Create spans relative to the start of a haystack from byte ranges in ascending order,
+/// counting characters incrementally.
+#[cfg(feature = "std")]
+fn spans_from_ranges<'a>(
+    haystack: &'a str,
+    ranges: impl Iterator<Item = Range<usize>> + 'a,
+) -> impl Iterator<Item = MatchSpan> + 'a {
+    let mut pos = 0;
+    let mut chars = 0;
+    ranges.map(move |range| {
+        chars += haystack[pos..range.start].chars().count();
+        pos = range.start;
+        MatchSpan::new(&haystack[range.start..], 0, range.len()).shift(range.start, chars)
+    })
+}
+
+/// A `Needle` that can also report where in the haystack it matched.
+pub trait FindableNeedle: Needle {
+    /// Return the span of the first match in the haystack, if any.
+    fn find(&self, haystack: &str) -> Option<MatchSpan>;
+
+    /// Return an iterator over all non-overlapping matches in the haystack.
+    ///
+    /// The default implementation repeatedly calls `find()` on the remainder of the haystack,
+    /// starting from the end of the previous match.
+    fn find_all<'a>(&'a self, haystack: &'a str) -> impl Iterator<Item = MatchSpan> + 'a
+    where
+        Self: Sized,
+    {
+        FindIter::new(move |rest| self.find(rest), haystack, false)
+    }
+}
+
+/// Iterator that repeatedly advances past the previous match, using `find` to search the
+/// remainder of the haystack.
+struct FindIter<'a, F> {
+    find: F,
+    haystack: &'a str,
+    pos: usize,
+    /// The number of characters before `pos`.
+    char_pos: usize,
+    /// If true, only a match at the very start of the haystack is allowed.
+    anchored: bool,
+}
+
+impl<'a, F> FindIter<'a, F>
+where
+    F: Fn(&str) -> Option<MatchSpan>,
+{
+    fn new(find: F, haystack: &'a str, anchored: bool) -> Self {
+        Self {
+            find,
+            haystack,
+            pos: 0,
+            char_pos: 0,
+            anchored,
+        }
+    }
+}
+
+impl<F> Iterator for FindIter<'_, F>
+where
+    F: Fn(&str) -> Option<MatchSpan>,
+{
+    type Item = MatchSpan;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos > self.haystack.len() || (self.anchored && self.pos > 0) {
+            return None;
+        }
+
+        let rest = &self.haystack[self.pos..];
+        match (self.find)(rest) {
+            Some(span) => {
+                let span = span.shift(self.pos, self.char_pos);
+                (self.pos, self.char_pos) = match self.haystack[span.byte_end..].chars().next() {
+                    // Step over the next character to avoid matching the same empty span forever.
+                    Some(c) if span.is_empty() => (span.byte_end + c.len_utf8(), span.char_end + 1),
+                    None if span.is_empty() => (span.byte_end + 1, span.char_end),
+                    _ => (span.byte_end, span.char_end),
+                };
+                Some(span)
+            }
+            None => {
+                self.pos = self.haystack.len() + 1;
+                None
+            }
+        }
+    }
+}
+
+/// Apply the trim, punctuation, number, whitespace, normalization, diacritic and case options
+/// of `sm` to the haystack `s`, returning the transformed string along with the byte offset in
+/// `s` of the character that produced each byte of the transformed string. A final entry holds
+/// the offset of the end of the content.
+fn transform_with_offsets(sm: &StringMatch, s: &str) -> (String, Vec<usize>) {
+    let base = match sm.trim {
+        true => s.len() - s.trim_start().len(),
+        false => 0,
+    };
+    let s = sm.trim_haystack(&s[base..]);
+
+    let mut transformed = String::with_capacity(s.len());
+    let mut offsets = Vec::with_capacity(s.len() + 1);
+    // Starting in whitespace drops any leading whitespace for `collapse_whitespace()`.
+    let mut in_whitespace = sm.collapse_whitespace;
+    let mut transform_char = |offset: usize, c: char| {
+        // Dropping a character leaves the whitespace state alone, so whitespace on either side
+        // of it is still collapsed.
+        if sm.is_ignored_char(c) {
+            return;
+        }
+        if (sm.normalize_whitespace || sm.collapse_whitespace) && c.is_whitespace() {
+            if !in_whitespace {
+                transformed.push(' ');
+                offsets.push(offset);
+            }
+            in_whitespace = true;
+            return;
+        }
+        in_whitespace = false;
+
+        let mut push = |c: char| match sm.case_sensitive {
+            true => {
+                transformed.push(c);
+                offsets.extend(core::iter::repeat_n(offset, c.len_utf8()));
+            }
+            false => {
+                for lc in c.to_lowercase() {
+                    transformed.push(lc);
+                    offsets.extend(core::iter::repeat_n(offset, lc.len_utf8()));
+                }
+            }
+        };
+        #[cfg(feature = "unicode")]
+        if sm.strip_diacritics {
+            crate::unicode::strip_char_diacritics(c).for_each(push);
+            return;
+        }
+        push(c);
+    };
+    #[cfg(feature = "unicode")]
+    let normalized = sm
+        .is_unicode_normalized()
+        .then(|| crate::unicode::normalize_with_offsets(s, sm.normalize_form()));
+    #[cfg(not(feature = "unicode"))]
+    let normalized: Option<Vec<(usize, char)>> = None;
+    match normalized {
+        Some(chars) => chars.into_iter().for_each(|(i, c)| transform_char(base + i, c)),
+        None => s.char_indices().for_each(|(i, c)| transform_char(base + i, c)),
+    }
+    let mut end = base + s.len();
+    if sm.collapse_whitespace && in_whitespace && transformed.ends_with(' ') {
+        // Drop the trailing whitespace, so the content ends where it started.
+        transformed.pop();
+        end = offsets.pop().unwrap_or(end);
+    }
+    offsets.push(end);
+    (transformed, offsets)
+}
+
+fn find_in_haystack(
+    needle: &str,
+    haystack: &str,
+    match_length: &StringMatchLength,
+    separators: Option<&str>,
+) -> Option<Range<usize>> {
+    match match_length {
+        StringMatchLength::Full => (haystack == needle).then_some(0..haystack.len()),
+        StringMatchLength::Partial => {
+            str::find(haystack, needle).map(|start| start..start + needle.len())
+        }
+        StringMatchLength::Word => find_word(needle, haystack, separators),
+        StringMatchLength::Prefix => haystack.starts_with(needle).then_some(0..needle.len()),
+        StringMatchLength::Suffix => {
+            haystack.ends_with(needle).then(|| haystack.len() - needle.len()..haystack.len())
+        }
+    }
+}
+
+impl FindableNeedle for StringMatch {
+    fn find(&self, haystack: &str) -> Option<MatchSpan> {
+        match self.is_haystack_allowed(haystack) {
+            true => self.find_unbounded(haystack),
+            false => None,
+        }
+    }
+
+    fn find_all<'a>(&'a self, haystack: &'a str) -> impl Iterator<Item = MatchSpan> + 'a {
+        let allowed = self.is_haystack_allowed(haystack);
+        let mut pos = 0;
+        let mut chars = 0;
+        lines(haystack, self.multiline).filter(move |_| allowed).flat_map(move |(offset, line)| {
+            chars += haystack[pos..offset].chars().count();
+            pos = offset;
+            let line_chars = chars;
+            FindIter::new(move |rest| self.find_in_line(rest), line, self.is_prefix_match())
+                .map(move |span| span.shift(offset, line_chars))
+        })
+    }
+}
+
+impl StringMatch {
+    /// Find the first match, ignoring the `ascii_only()`, `min_length()` and `max_length()`
+    /// options.
+    fn find_unbounded(&self, haystack: &str) -> Option<MatchSpan> {
+        lines(haystack, self.multiline).find_map(|(offset, line)| {
+            let span = self.find_in_line(line)?;
+            Some(span.shift(offset, haystack[..offset].chars().count()))
+        })
+    }
+
+    /// Find the first match in a single line, ignoring the `line_by_line()` option.
+    fn find_in_line(&self, haystack: &str) -> Option<MatchSpan> {
+        let range = self.find_range_in_line(haystack)?;
+        Some(MatchSpan::new(haystack, range.start, range.end))
+    }
+
+    /// Find the byte range of the first match in a single line.
+    fn find_range_in_line(&self, haystack: &str) -> Option<Range<usize>> {
+        if self.case_sensitive
+            && !self.trim
+            && !self.trim_end
+            && !self.normalize_whitespace
+            && !self.collapse_whitespace
+            && !self.is_diacritic_insensitive()
+            && !self.is_unicode_normalized()
+            && !self.has_ignored_chars()
+        {
+            return self.find_prepared(&self.text, haystack);
+        }
+
+        let (hs, offsets) = transform_with_offsets(self, haystack);
+        let needle = self.prepare_needle();
+        let needle: String = match self.case_sensitive {
+            true => needle.into_owned(),
+            false => needle.chars().flat_map(char::to_lowercase).collect(),
+        };
+        let range = self.find_prepared(&needle, &hs)?;
+        // Map the offsets in the transformed haystack back to the original haystack.
+        let start = offsets[range.start];
+        let end = match range.is_empty() {
+            true => start,
+            false => {
+                // Extend the match over any characters that were dropped entirely, such as
+                // stripped diacritics, unless the match ends part way through the expansion of
+                // a single character.
+                let last = offsets[range.end - 1];
+                let last_end = last + haystack[last..].chars().next().map_or(0, char::len_utf8);
+                last_end.max(offsets[range.end])
+            }
+        };
+        Some(start..end)
+    }
+
+    /// Find the byte range of the first match of a prepared needle in a prepared haystack.
+    fn find_prepared(&self, needle: &str, haystack: &str) -> Option<Range<usize>> {
+        match self.is_pattern(needle) {
+            true => find_pattern(self, needle, haystack),
+            false => find_in_haystack(needle, haystack, &self.match_length, self.separators()),
+        }
+    }
+}
+
+impl StringMatch {
+    /// Return an iterator over the spans of all non-overlapping matches in the haystack, in
+    /// left-to-right order.
+    ///
+    /// This is the same as `FindableNeedle::find_all()`. In `Full` mode it yields at most one
+    /// span covering the whole haystack.
+    pub fn all_spans<'a>(&'a self, haystack: &'a str) -> impl Iterator<Item = MatchSpan> + 'a {
+        self.find_all(haystack)
+    }
+
+    /// Return an iterator over the spans of all matches in the haystack, including overlapping
+    /// ones, ordered by their start.
+    ///
+    /// Only `Partial` matches can overlap, so for other modes this is the same as
+    /// `all_spans()`.
+    pub fn all_spans_overlapping<'a>(
+        &'a self,
+        haystack: &'a str,
+    ) -> impl Iterator<Item = MatchSpan> + 'a {
+        let overlapping = self.is_partial_match() && self.is_haystack_allowed(haystack);
+        let mut pos = 0;
+        let mut chars = 0;
+        let mut done = !overlapping;
+        let overlapping_spans = core::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let span = self.find_unbounded(&haystack[pos..])?.shift(pos, chars);
+            // Restart the search one character after the start of this match.
+            chars = span.char_start;
+            pos = span.byte_start;
+            match haystack[pos..].chars().next() {
+                Some(c) => {
+                    pos += c.len_utf8();
+                    chars += 1;
+                }
+                None => done = true,
+            }
+            Some(span)
+        });
+        let spans = (!overlapping).then(|| self.find_all(haystack));
+        overlapping_spans.chain(spans.into_iter().flatten())
+    }
+
+    /// Return the number of non-overlapping matches in the haystack.
+    ///
+    /// This is 0 whenever `is_match()` would return false. In `Full` mode it is at most 1.
+    pub fn count(&self, haystack: &str) -> usize {
+        self.find_all(haystack).count()
+    }
+
+    /// Return the number of matches in the haystack, including overlapping ones.
+    ///
+    /// Only `Partial` matches can overlap, so for other modes this is the same as `count()`.
+    pub fn count_overlapping(&self, haystack: &str) -> usize {
+        self.all_spans_overlapping(haystack).count()
+    }
+}
+
+#[cfg(feature = "std")]
+impl FindableNeedle for Regex {
+    fn find(&self, haystack: &str) -> Option<MatchSpan> {
+        Regex::find(self, haystack).map(|m| MatchSpan::new(haystack, m.start(), m.end()))
+    }
+
+    fn find_all<'a>(&'a self, haystack: &'a str) -> impl Iterator<Item = MatchSpan> + 'a {
+        spans_from_ranges(haystack, self.find_iter(haystack).map(|m| m.range()))
+    }
+}
+
+impl FindableNeedle for &str {
+    fn find(&self, haystack: &str) -> Option<MatchSpan> {
+        (*self == haystack).then(|| MatchSpan::new(haystack, 0, haystack.len()))
+    }
+}
+
+impl FindableNeedle for String {
+    fn find(&self, haystack: &str) -> Option<MatchSpan> {
+        (self == haystack).then(|| MatchSpan::new(haystack, 0, haystack.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans<N: FindableNeedle>(needle: &N, haystack: &str) -> Vec<Range<usize>> {
+        needle.find_all(haystack).map(Range::from).collect()
+    }
+
+    #[test]
+    fn test_find_whitespace_options() {
+        let sm = StringMatch::from("hello world").partial().normalize_whitespace();
+        let haystack = "say hello \t world!";
+        let span = sm.find(haystack).unwrap();
+        assert_eq!(span.as_str(haystack), "hello \t world");
+        assert_eq!(sm.case_insensitive().count("HELLO   WORLD hello\nworld"), 2);
+
+        let sm = StringMatch::from("hello").trim();
+        assert_eq!(sm.find("  hello\n").map(Range::from), Some(2..7));
+        assert_eq!(sm.find("  hello there"), None);
+        assert_eq!(StringMatch::from("").trim().find("   ").map(Range::from), Some(3..3));
+        assert_eq!(
+            StringMatch::from("hi").prefix().trim().find(" hi hi").map(Range::from),
+            Some(1..3)
+        );
+        assert_eq!(
+            StringMatch::from("hi").suffix().trim().find("hi hi ").map(Range::from),
+            Some(3..5)
+        );
+        let sm = StringMatch::from("hi").suffix().trim_end_before_match();
+        assert_eq!(sm.find(" hi hi \n").map(Range::from), Some(4..6));
+    }
+
+    #[test]
+    fn test_find_line_by_line() {
+        let sm = StringMatch::from("ab").line_by_line();
+        assert_eq!(sm.find("x\nab\r\nab").map(Range::from), Some(2..4));
+        assert_eq!(spans(&sm, "x\nab\r\nab"), vec![2..4, 6..8]);
+        assert_eq!(sm.find("abc\nxab"), None);
+
+        // Prefix matches are anchored to the start of each line.
+        let sm = StringMatch::from("a").prefix().line_by_line();
+        assert_eq!(spans(&sm, "aa\nba\nab"), vec![0..1, 6..7]);
+        assert_eq!(sm.replace_all("aa\nba\nab", "x"), "xa\nba\nxb");
+
+        let sm = StringMatch::from("A").partial().case_insensitive().line_by_line();
+        assert_eq!(spans(&sm, "aa\nba"), vec![0..1, 1..2, 4..5]);
+        assert_eq!(sm.count("aa\nba"), 3);
+    }
+
+    #[test]
+    fn test_match_span() {
+        let span = MatchSpan::new("Test", 1, 3);
+        assert_eq!((span.byte_start, span.byte_end), (1, 3));
+        assert_eq!((span.char_start, span.char_end), (1, 3));
+        assert_eq!(span.len(), 2);
+        assert_eq!(span.char_len(), 2);
+        assert!(!span.is_empty());
+        assert!(MatchSpan::new("Test", 2, 2).is_empty());
+        assert_eq!(span.as_str("Test"), "es");
+        let range: Range<usize> = span.into();
+        assert_eq!(range, 1..3);
+
+        let haystack = "日本語 text";
+        let span = MatchSpan::new(haystack, 3, 9);
+        assert_eq!(span.as_str(haystack), "本語");
+        assert_eq!(span.byte_range(), 3..9);
+        assert_eq!(span.char_range(), 1..3);
+        assert_eq!(span.len(), 6);
+        assert_eq!(span.char_len(), 2);
+    }
+
+    #[test]
+    fn test_char_offsets() {
+        let haystack = "héllo wörld, 日本語 wörld";
+        let sm = StringMatch::from("wörld").partial();
+        let found: Vec<MatchSpan> = sm.find_all(haystack).collect();
+        assert_eq!(found.len(), 2);
+        for span in &found {
+            assert_eq!(&haystack[span.byte_start..span.byte_end], "wörld");
+            let chars: String =
+                haystack.chars().skip(span.char_start).take(span.char_len()).collect();
+            assert_eq!(chars, "wörld");
+        }
+        assert_eq!(found[0].char_range(), 6..11);
+        assert_eq!(found[1].char_range(), 17..22);
+        assert_eq!(sm.find(haystack), Some(found[0]));
+
+        let sm = StringMatch::from("Ö").partial().case_insensitive().line_by_line();
+        let haystack = "ö\r\nxö\nö";
+        let found: Vec<Range<usize>> = sm.find_all(haystack).map(|s| s.char_range()).collect();
+        assert_eq!(found, vec![0..1, 4..5, 6..7]);
+        assert_eq!(sm.find("x\nxö").map(|s| s.char_range()), Some(3..4));
+
+        let full = StringMatch::from("日本").find("日本").unwrap();
+        assert_eq!((full.byte_range(), full.char_range()), (0..6, 0..2));
+
+        #[cfg(feature = "std")]
+        {
+            let re = Regex::new("[ö本]").unwrap();
+            let found: Vec<MatchSpan> = FindableNeedle::find_all(&re, haystack).collect();
+            assert_eq!(found.iter().map(|s| s.char_start).collect::<Vec<_>>(), vec![0, 4, 6]);
+            let haystack = "héllo 日本";
+            let span = FindableNeedle::find(&re, haystack).unwrap();
+            assert_eq!(span.as_str(haystack), "本");
+            assert_eq!(span.char_range(), 7..8);
+            let found: Vec<Range<usize>> =
+                FindableNeedle::find_all(&re, "本é本").map(|s| s.char_range()).collect();
+            assert_eq!(found, vec![0..1, 2..3]);
+        }
+
+        let empty: Vec<Range<usize>> =
+            StringMatch::from("").partial().find_all("aé").map(|s| s.char_range()).collect();
+        assert_eq!(empty, vec![0..0, 1..1, 2..2]);
+    }
+
+    #[test]
+    fn test_find_stringmatch() {
+        assert_eq!(StringMatch::from("Test").find("Test").map(Range::from), Some(0..4));
+        assert_eq!(StringMatch::from("Test").find("Tests"), None);
+        assert_eq!(
+            StringMatch::from("test").case_insensitive().find("TEST").map(Range::from),
+            Some(0..4)
+        );
+        assert_eq!(StringMatch::from("es").partial().find("Test").map(Range::from), Some(1..3));
+        assert_eq!(StringMatch::from("ES").partial().find("Test"), None);
+        assert_eq!(
+            StringMatch::from("ES").partial().case_insensitive().find("Test").map(Range::from),
+            Some(1..3)
+        );
+        assert_eq!(StringMatch::from("b").word().find("ab b c").map(Range::from), Some(3..4));
+        assert_eq!(StringMatch::from("b").word().find("ab bc"), None);
+        assert_eq!(
+            StringMatch::from("B C").word().case_insensitive().find("a b c").map(Range::from),
+            Some(2..5)
+        );
+        assert_eq!(StringMatch::from("Te").prefix().find("Test").map(Range::from), Some(0..2));
+        assert_eq!(StringMatch::from("st").prefix().find("Test"), None);
+        assert_eq!(StringMatch::from("st").suffix().find("Test").map(Range::from), Some(2..4));
+        assert_eq!(StringMatch::from("").partial().find("Test").map(Range::from), Some(0..0));
+        assert_eq!(StringMatch::from("").suffix().find("Test").map(Range::from), Some(4..4));
+    }
+
+    #[test]
+    fn test_find_all_stringmatch() {
+        // Overlapping occurrences are not reported twice.
+        assert_eq!(spans(&StringMatch::from("aa").partial(), "aaaaa"), vec![0..2, 2..4]);
+        assert_eq!(spans(&StringMatch::from("aba").partial(), "ababa"), vec![0..3]);
+        assert_eq!(spans(&StringMatch::from("a").partial(), "baba"), vec![1..2, 3..4]);
+        assert!(spans(&StringMatch::from("x").partial(), "baba").is_empty());
+        assert_eq!(spans(&StringMatch::from("").partial(), "ab"), vec![0..0, 1..1, 2..2]);
+        assert_eq!(spans(&StringMatch::from("a").word(), "a aa a"), vec![0..1, 5..6]);
+        assert_eq!(spans(&StringMatch::from("a a").word(), "a a a a"), vec![0..3, 4..7]);
+        assert_eq!(
+            spans(&StringMatch::from("a").word_with_separators(",;"), "a,aa;a"),
+            vec![0..1, 5..6]
+        );
+        assert_eq!(spans(&StringMatch::from("a").prefix(), "aaa"), vec![0..1]);
+        assert_eq!(spans(&StringMatch::from("a").suffix(), "aaa"), vec![2..3]);
+        assert_eq!(spans(&StringMatch::from("aaa"), "aaa"), vec![0..3]);
+        assert_eq!(
+            spans(&StringMatch::from("A").partial().case_insensitive(), "aAa"),
+            vec![0..1, 1..2, 2..3]
+        );
+    }
+
+    #[test]
+    fn test_find_non_ascii() {
+        let haystack = "café CAFÉ";
+        assert_eq!(spans(&StringMatch::from("é").partial(), haystack), vec![3..5]);
+        assert_eq!(
+            spans(&StringMatch::from("é").partial().case_insensitive(), haystack),
+            vec![3..5, 9..11]
+        );
+        for span in StringMatch::from("café").word().case_insensitive().find_all(haystack) {
+            assert_eq!(span.as_str(haystack).to_lowercase(), "café");
+        }
+
+        // Lowercasing 'İ' produces more bytes than the original character.
+        let haystack = "İxİx";
+        let needle = StringMatch::from("x").partial().case_insensitive();
+        assert_eq!(spans(&needle, haystack), vec![2..3, 5..6]);
+        let needle = StringMatch::from("İ").partial().case_insensitive();
+        assert_eq!(spans(&needle, haystack), vec![0..2, 3..5]);
+
+        assert_eq!(spans(&StringMatch::from("").partial(), "éé"), vec![0..0, 2..2, 4..4]);
+    }
+
+    #[test]
+    fn test_count() {
+        assert_eq!(StringMatch::from("a").count("a"), 1);
+        assert_eq!(StringMatch::from("a").count("aa"), 0);
+        assert_eq!(StringMatch::from("a").partial().count("banana"), 3);
+        assert_eq!(StringMatch::from("A").partial().count("banana"), 0);
+        assert_eq!(StringMatch::from("A").partial().case_insensitive().count("bAnana"), 3);
+        assert_eq!(StringMatch::from("ana").partial().count("banana"), 1);
+        assert_eq!(StringMatch::from("ana").partial().count_overlapping("banana"), 2);
+        assert_eq!(StringMatch::from("aa").partial().count("aaaa"), 2);
+        assert_eq!(StringMatch::from("aa").partial().count_overlapping("aaaa"), 3);
+        assert_eq!(StringMatch::from("éé").partial().count_overlapping("ééé"), 2);
+        assert_eq!(StringMatch::from("").partial().count("ab"), 3);
+        assert_eq!(StringMatch::from("").partial().count_overlapping("ab"), 3);
+        assert_eq!(StringMatch::from("a").word().count("a aa a b a"), 3);
+        assert_eq!(StringMatch::from("a a").word().count_overlapping("a a a"), 1);
+        assert_eq!(StringMatch::from("a").prefix().count("aaa"), 1);
+        assert_eq!(StringMatch::from("a").suffix().count_overlapping("aaa"), 1);
+    }
+
+    #[test]
+    fn test_all_spans() {
+        let needles = [
+            StringMatch::from("a"),
+            StringMatch::from("a").partial(),
+            StringMatch::from("aa").partial(),
+            StringMatch::from("").partial(),
+            StringMatch::from("A").partial().case_insensitive(),
+            StringMatch::from("é").partial(),
+            StringMatch::from("a").word(),
+            StringMatch::from("a b").word().case_insensitive(),
+            StringMatch::from("a").prefix(),
+            StringMatch::from("a").suffix(),
+        ];
+        let haystacks = ["", "a", "aa", "aaaa", "a b a", "A B a b", "bab", "aAaA", "éaéé"];
+        for needle in &needles {
+            for haystack in haystacks {
+                // Reassembling the gaps and spans in order gives back the haystack.
+                let mut rebuilt = String::new();
+                let mut last = 0;
+                for span in needle.all_spans(haystack) {
+                    assert!(span.byte_start >= last, "{:?} {:?}", needle, haystack);
+                    rebuilt.push_str(&haystack[last..span.byte_start]);
+                    rebuilt.push_str(span.as_str(haystack));
+                    last = span.byte_end;
+                }
+                rebuilt.push_str(&haystack[last..]);
+                assert_eq!(rebuilt, haystack);
+
+                assert_eq!(needle.all_spans(haystack).count(), needle.count(haystack));
+                let overlapping: Vec<_> = needle.all_spans_overlapping(haystack).collect();
+                assert_eq!(overlapping.len(), needle.count_overlapping(haystack));
+                assert!(overlapping.windows(2).all(|w| w[0].byte_start < w[1].byte_start));
+                for span in overlapping {
+                    assert_eq!(span, MatchSpan::new(haystack, span.byte_start, span.byte_end));
+                }
+            }
+        }
+
+        assert_eq!(spans(&StringMatch::from("ab"), "ab"), vec![0..2]);
+        let needle = StringMatch::from("A").partial();
+        assert_eq!(needle.all_spans("aAbA").map(Range::from).collect::<Vec<_>>(), vec![1..2, 3..4]);
+        let needle = StringMatch::from("aa").partial();
+        assert_eq!(needle.all_spans("aaa").map(Range::from).collect::<Vec<_>>(), vec![0..2]);
+        assert_eq!(
+            needle.all_spans_overlapping("aaa").map(Range::from).collect::<Vec<_>>(),
+            vec![0..2, 1..3]
+        );
+        let needle = StringMatch::from("a").word();
+        assert_eq!(
+            needle.all_spans("a ab a").map(Range::from).collect::<Vec<_>>(),
+            vec![0..1, 5..6]
+        );
+        let needle = StringMatch::from("éé").partial();
+        let chars: Vec<_> = needle.all_spans_overlapping("xééé").map(|s| s.char_range()).collect();
+        assert_eq!(chars, vec![1..3, 2..4]);
+    }
+
+    #[test]
+    fn test_count_agrees_with_replace_all() {
+        let needles = [
+            StringMatch::from("a"),
+            StringMatch::from("a").partial(),
+            StringMatch::from("aa").partial(),
+            StringMatch::from("A").partial().case_insensitive(),
+            StringMatch::from("a").word(),
+            StringMatch::from("a b").word().case_insensitive(),
+            StringMatch::from("a").prefix(),
+            StringMatch::from("a").suffix(),
+        ];
+        let haystacks = ["", "a", "aa", "aaa", "a b a", "A B a b", "bab", "aAaA"];
+        for needle in &needles {
+            for haystack in haystacks {
+                let replaced = needle.replace_all(haystack, "\0");
+                assert_eq!(
+                    needle.count(haystack),
+                    replaced.matches('\0').count(),
+                    "{:?} {:?}",
+                    needle,
+                    haystack
+                );
+                assert_eq!(needle.count(haystack) > 0, needle.is_match(haystack));
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_find_regex() {
+        let re = Regex::new(r"\d+").unwrap();
+        assert_eq!(FindableNeedle::find(&re, "ab12cd345").map(Range::from), Some(2..4));
+        assert_eq!(FindableNeedle::find(&re, "abcd"), None);
+        assert_eq!(spans(&re, "ab12cd345"), vec![2..4, 6..9]);
+        assert_eq!(spans(&Regex::new("^a").unwrap(), "aaa"), vec![0..1]);
+        assert_eq!(spans(&Regex::new("é+").unwrap(), "éaéé"), vec![0..2, 3..7]);
+    }
+
+    #[test]
+    fn test_find_str() {
+        // Call through the trait explicitly, since `str::find` would otherwise take precedence.
+        assert_eq!(FindableNeedle::find(&"Test", "Test").map(Range::from), Some(0..4));
+        assert_eq!(FindableNeedle::find(&"Test", "test"), None);
+        assert_eq!(spans(&"Test", "Test"), vec![0..4]);
+        assert_eq!(String::from("Test").find("Test").map(Range::from), Some(0..4));
+        assert_eq!(String::from("Test").find("Tests"), None);
+        assert_eq!(spans(&String::from("Tést"), "Tést"), vec![0..5]);
+    }
+}
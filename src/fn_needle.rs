@@ -0,0 +1,80 @@
+use crate::{Needle, NeedleIter};
+use core::fmt;
+
+/// A closure needle with a name, so it can be identified in debug output.
+///
+/// Plain closures are needles too, but they show up as `<closure>` when debugging.
+#[derive(Clone, Copy)]
+pub struct FnNeedle<F> {
+    name: &'static str,
+    f: F,
+}
+
+impl<F> FnNeedle<F>
+where
+    F: Fn(&str) -> bool,
+{
+    pub fn new(name: &'static str, f: F) -> Self {
+        Self {
+            name,
+            f,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Create a `FnNeedle` with the given name. This is the same as `FnNeedle::new()`.
+pub fn named_needle<F>(name: &'static str, f: F) -> FnNeedle<F>
+where
+    F: Fn(&str) -> bool,
+{
+    FnNeedle::new(name, f)
+}
+
+impl<F> fmt::Debug for FnNeedle<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnNeedle").field("name", &self.name).finish()
+    }
+}
+
+impl<F> Needle for FnNeedle<F>
+where
+    F: Fn(&str) -> bool,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        (self.f)(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.f.complexity_estimate()
+    }
+}
+
+impl<F> NeedleIter for FnNeedle<F> where F: Fn(&str) -> bool {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fn_needle() {
+        let needle = FnNeedle::new("is_numeric", |s: &str| s.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(needle.name(), "is_numeric");
+        assert!(needle.is_match("123"));
+        assert!(!needle.is_match("12a"));
+        assert_eq!(needle.complexity_estimate(), 20);
+        assert!(needle.is_match_in(&mut vec!["abc", "42"].into_iter()));
+        assert_eq!(format!("{:?}", needle), r#"FnNeedle { name: "is_numeric" }"#);
+
+        let needles: Vec<Box<dyn Needle>> = vec![
+            Box::new(named_needle("empty", |s: &str| s.is_empty())),
+            Box::new(named_needle("long", |s: &str| s.len() > 5)),
+        ];
+        assert!(needles[0].is_match(""));
+        assert!(!needles[0].is_match("x"));
+        assert!(needles[1].is_match("longer"));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{Needle, NeedleIter, ScoredNeedle};
+use std::borrow::Cow;
+
+/// Needle that matches any haystack within a maximum Levenshtein edit distance of the pattern.
+///
+/// The distance is measured in Unicode code points, not bytes.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct FuzzyNeedle {
+    pattern: String,
+    /// The maximum number of single-character edits allowed for a match.
+    max_distance: usize,
+    /// If true, use a case-sensitive match. Default is true.
+    case_sensitive: bool,
+}
+
+impl FuzzyNeedle {
+    pub fn new<S>(pattern: S, max_distance: usize) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            pattern: pattern.into(),
+            max_distance,
+            case_sensitive: true,
+        }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn max_distance(&self) -> usize {
+        self.max_distance
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_sensitive = false;
+        self
+    }
+
+    pub fn case_sensitive(mut self) -> Self {
+        self.case_sensitive = true;
+        self
+    }
+
+    /// Return the edit distance between the pattern and the haystack.
+    pub fn distance(&self, haystack: &str) -> usize {
+        let (pattern, haystack) = self.fold_case(haystack);
+        strsim::levenshtein(&pattern, &haystack)
+    }
+
+    /// Return the pattern and haystack, lowercased for a case-insensitive match.
+    fn fold_case<'a>(&'a self, haystack: &'a str) -> (Cow<'a, str>, Cow<'a, str>) {
+        match self.case_sensitive {
+            true => (Cow::Borrowed(&self.pattern), Cow::Borrowed(haystack)),
+            false => (Cow::Owned(self.pattern.to_lowercase()), Cow::Owned(haystack.to_lowercase())),
+        }
+    }
+}
+
+impl Needle for FuzzyNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        // The distance is at least the difference in length, so skip the full computation.
+        // Lowercasing can change the length, e.g. 'İ' becomes two characters, so compare the
+        // lowercased strings.
+        let (pattern, haystack) = self.fold_case(haystack);
+        let len_diff = pattern.chars().count().abs_diff(haystack.chars().count());
+        len_diff <= self.max_distance
+            && strsim::levenshtein(&pattern, &haystack) <= self.max_distance
+    }
+}
+
+impl NeedleIter for FuzzyNeedle {}
+
+/// Matching haystacks score their normalized Levenshtein similarity to the pattern, from
+/// just above 0.0 (every character differs) to 1.0 (identical).
+impl ScoredNeedle for FuzzyNeedle {
+    fn score(&self, haystack: &str) -> f64 {
+        if !self.is_match(haystack) {
+            return 0.0;
+        }
+        let (pattern, haystack) = self.fold_case(haystack);
+        strsim::normalized_levenshtein(&pattern, &haystack).max(f64::MIN_POSITIVE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy() {
+        let exact = FuzzyNeedle::new("kitten", 0);
+        assert!(exact.is_match("kitten"));
+        assert!(!exact.is_match("sitten"));
+        assert!(!exact.is_match("Kitten"));
+        assert!(exact.clone().case_insensitive().is_match("Kitten"));
+
+        let one = FuzzyNeedle::new("kitten", 1);
+        assert_eq!(one.pattern(), "kitten");
+        assert_eq!(one.max_distance(), 1);
+        assert!(one.is_match("kitten"));
+        assert!(one.is_match("sitten"));
+        assert!(one.is_match("kittens"));
+        assert!(one.is_match("kiten"));
+        assert!(!one.is_match("sittin"));
+        assert!(!one.is_match("KITTEN"));
+        assert!(one.clone().case_insensitive().is_match("KITTEN"));
+        assert!(one.clone().case_insensitive().is_match("SITTEN"));
+        assert!(!one.clone().case_insensitive().case_sensitive().is_match("KITTEN"));
+
+        // Lengths differ by more than the max distance.
+        let two = FuzzyNeedle::new("kitten", 2);
+        assert_eq!(two.distance("sitting"), 3);
+        assert!(!two.is_match("sitting"));
+        assert!(two.is_match("sittin"));
+        assert!(!two.is_match("kit"));
+        assert!(!two.is_match("kittenish"));
+        assert!(!two.is_match(""));
+
+        assert!(one.is_match_in(&mut vec!["dog", "mitten"].into_iter()));
+    }
+
+    #[test]
+    fn test_fuzzy_unicode() {
+        // Each of these is a single code point edit even though it is multiple bytes.
+        let needle = FuzzyNeedle::new("café", 1);
+        assert!(needle.is_match("cafe"));
+        assert!(needle.is_match("cafés"));
+        assert!(needle.is_match("caf"));
+        assert!(!needle.is_match("cofe"));
+        assert_eq!(FuzzyNeedle::new("日本語", 0).distance("日本人"), 1);
+        assert!(FuzzyNeedle::new("ÉTÉ", 0).case_insensitive().is_match("été"));
+
+        // 'İ' lowercases to two characters, so the lengths only match once lowercased.
+        let needle = FuzzyNeedle::new("i\u{307}x", 0).case_insensitive();
+        assert_eq!(needle.distance("İx"), 0);
+        assert!(needle.is_match("İx"));
+        assert!(!needle.clone().case_sensitive().is_match("İx"));
+        assert!(FuzzyNeedle::new("İİ", 1).case_insensitive().is_match("i\u{307}i"));
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        let needle = FuzzyNeedle::new("kitten", 2);
+        assert_eq!(needle.score("kitten"), 1.0);
+        assert_eq!(needle.score("sitting"), 0.0);
+        assert!(needle.score("sitten") > needle.score("sittin"));
+        assert_eq!(needle.clone().case_insensitive().score("KITTEN"), 1.0);
+        assert!(FuzzyNeedle::new("ab", 2).score("cd") > 0.0);
+        for haystack in ["", "k", "kitten", "kitte", "mitten", "KITTEN", "kittens!"] {
+            let score = needle.score(haystack);
+            assert!((0.0..=1.0).contains(&score));
+            assert_eq!(score > 0.0, needle.is_match(haystack));
+        }
+
+        let words = ["mitten", "kitchen", "sitting", "kitten"];
+        assert_eq!(needle.best_match(words.into_iter()), Some("kitten"));
+        assert_eq!(needle.best_match(words[..3].iter().copied()), Some("mitten"));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::find::{FindableNeedle, MatchSpan};
+use crate::{Needle, NeedleIter, StringMatch, StringMatchLength};
+use globset::{Glob, GlobBuilder};
+use regex::{Regex, RegexBuilder};
+
+/// The error returned when a glob pattern fails to compile.
+pub type GlobError = globset::Error;
+
+/// Needle that matches shell-style glob patterns such as `foo_*.txt`.
+///
+/// The pattern must match the whole haystack. Supported syntax includes `*`, `?`,
+/// character classes such as `[a-z]`, and alternation such as `{a,b}`.
+///
+/// `?` matches a single character, which may be more than one byte.
+#[derive(Debug, Clone)]
+pub struct GlobNeedle {
+    glob: Glob,
+    regex: Regex,
+    /// If true, use a case-sensitive match.
+    case_sensitive: bool,
+}
+
+impl GlobNeedle {
+    /// Compile the glob pattern, using the platform's default case sensitivity
+    /// (case-insensitive on Windows, case-sensitive elsewhere).
+    pub fn new(pattern: &str) -> Result<Self, GlobError> {
+        Self::new_with_case(pattern, !cfg!(windows))
+    }
+
+    /// Compile the glob pattern with explicit case sensitivity.
+    pub fn new_with_case(pattern: &str, case_sensitive: bool) -> Result<Self, GlobError> {
+        let glob = GlobBuilder::new(pattern).case_insensitive(!case_sensitive).build()?;
+        let regex = RegexBuilder::new(&unicode_regex(glob.regex()))
+            .dot_matches_new_line(true)
+            .build()
+            .expect("a glob regex is always valid");
+        Ok(Self {
+            glob,
+            regex,
+            case_sensitive,
+        })
+    }
+
+    pub fn pattern(&self) -> &str {
+        self.glob.glob()
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+}
+
+impl Needle for GlobNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.regex.is_match(haystack)
+    }
+}
+
+impl NeedleIter for GlobNeedle {}
+
+impl FindableNeedle for GlobNeedle {
+    fn find(&self, haystack: &str) -> Option<MatchSpan> {
+        self.is_match(haystack).then(|| MatchSpan::new(haystack, 0, haystack.len()))
+    }
+}
+
+impl StringMatch {
+    /// Convert to a glob pattern that matches the same haystacks, with any glob
+    /// metacharacters in the needle text escaped so they match literally. The `*` and `?`
+    /// of a `wildcard()` needle are kept.
+    ///
+    /// Returns `None` for `Word` mode, which has no glob equivalent. The case sensitivity is
+    /// not part of the pattern, so use `to_glob_needle()` to keep it. As with
+    /// `to_regex_pattern()`, the options that transform the haystack are ignored.
+    pub fn to_glob_pattern(&self) -> Option<String> {
+        let text = escape_glob(&self.text, self.wildcard);
+        match self.match_length {
+            StringMatchLength::Full => Some(text),
+            StringMatchLength::Partial => Some(format!("*{}*", text)),
+            StringMatchLength::Word => None,
+            StringMatchLength::Prefix => Some(format!("{}*", text)),
+            StringMatchLength::Suffix => Some(format!("*{}", text)),
+        }
+    }
+
+    /// Convert to a `GlobNeedle` with the same case sensitivity. See `to_glob_pattern()`.
+    pub fn to_glob_needle(&self) -> Option<GlobNeedle> {
+        let pattern = self.to_glob_pattern()?;
+        let needle = GlobNeedle::new_with_case(&pattern, self.case_sensitive)
+            .expect("an escaped glob pattern is always valid");
+        Some(needle)
+    }
+}
+
+/// Convert a regex generated by `globset`, which matches bytes, to one that matches
+/// characters, so that `?` and classes such as `[!a]` match a whole character.
+///
+/// `globset` writes each non-ASCII character as a sequence of `\xNN` byte escapes, which are
+/// decoded back into characters.
+fn unicode_regex(regex: &str) -> String {
+    let regex = regex.strip_prefix("(?-u)").unwrap_or(regex);
+    let mut converted = String::with_capacity(regex.len());
+    let mut bytes = Vec::new();
+    let mut chars = regex.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let next = chars.next();
+            if next == Some('x') {
+                let hex: String = chars.by_ref().take(2).collect();
+                if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                    bytes.push(byte);
+                    continue;
+                }
+                flush_bytes(&mut converted, &mut bytes);
+                converted.push_str("\\x");
+                converted.push_str(&hex);
+                continue;
+            }
+            flush_bytes(&mut converted, &mut bytes);
+            converted.push(c);
+            converted.extend(next);
+            continue;
+        }
+        flush_bytes(&mut converted, &mut bytes);
+        converted.push(c);
+    }
+    flush_bytes(&mut converted, &mut bytes);
+    converted
+}
+
+/// Append the escaped characters encoded by `bytes` to `regex`, and clear `bytes`.
+fn flush_bytes(regex: &mut String, bytes: &mut Vec<u8>) {
+    for c in String::from_utf8_lossy(bytes).chars() {
+        regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])));
+    }
+    bytes.clear();
+}
+
+/// Escape `s` so that every character matches literally in a glob pattern, except for `*` and
+/// `?` if `wildcard` is true.
+fn escape_glob(s: &str, wildcard: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '?' | '*' if wildcard => escaped.push(c),
+            // A backslash escapes the next character on platforms where it isn't a path
+            // separator, so it is escaped too.
+            '?' | '*' | '[' | ']' | '{' | '}' | '\\' => {
+                escaped.push('[');
+                escaped.push(c);
+                escaped.push(']');
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str) -> GlobNeedle {
+        GlobNeedle::new_with_case(pattern, true).unwrap()
+    }
+
+    #[test]
+    fn test_glob() {
+        assert!(glob("foo_*.txt").is_match("foo_bar.txt"));
+        assert!(glob("foo_*.txt").is_match("foo_.txt"));
+        assert!(!glob("foo_*.txt").is_match("foo_bar.txt.bak"));
+        assert!(!glob("foo_*.txt").is_match("xfoo_bar.txt"));
+        assert!(!glob("foo_*.txt").is_match("FOO_bar.txt"));
+
+        assert!(glob("a?c").is_match("abc"));
+        assert!(glob("a?c").is_match("aéc"));
+        assert!(!glob("a??c").is_match("aéc"));
+        assert!(glob("[é]").is_match("é"));
+        assert!(!glob("[!é]").is_match("é"));
+        assert!(glob("a*").is_match("a\nb"));
+        assert!(GlobNeedle::new_with_case("É*", false).unwrap().is_match("éa"));
+        assert!(!glob("a?c").is_match("ac"));
+        assert!(!glob("a?c").is_match("abbc"));
+
+        assert!(glob("[a-z]1").is_match("q1"));
+        assert!(!glob("[a-z]1").is_match("Q1"));
+        assert!(!glob("[!a-z]1").is_match("q1"));
+
+        // Anchored at both ends.
+        assert!(glob("test").is_match("test"));
+        assert!(!glob("test").is_match("tests"));
+        assert!(!glob("test").is_match("atest"));
+        assert!(glob("*test*").is_match("atests"));
+
+        assert!(GlobNeedle::new_with_case("FOO*", false).unwrap().is_match("foobar"));
+        assert!(!GlobNeedle::new_with_case("FOO*", false).unwrap().is_case_sensitive());
+        assert_eq!(glob("foo_*.txt").pattern(), "foo_*.txt");
+        assert!(glob("*.rs").is_match_in(&mut vec!["lib.rs", "Cargo.toml"].into_iter()));
+
+        assert!(GlobNeedle::new("[a-").is_err());
+    }
+
+    #[test]
+    fn test_glob_find() {
+        assert_eq!(glob("*.txt").find("a.txt"), Some(MatchSpan::new("a.txt", 0, 5)));
+        assert_eq!(glob("*.txt").find("a.txt2"), None);
+        assert_eq!(glob("*.txt").find_all("a.txt").count(), 1);
+    }
+
+    #[test]
+    fn test_to_glob_pattern() {
+        assert_eq!(StringMatch::from("a*b").to_glob_pattern().as_deref(), Some("a[*]b"));
+        assert_eq!(StringMatch::from("ab").partial().to_glob_pattern().as_deref(), Some("*ab*"));
+        assert_eq!(StringMatch::from("ab").prefix().to_glob_pattern().as_deref(), Some("ab*"));
+        assert_eq!(StringMatch::from("ab").suffix().to_glob_pattern().as_deref(), Some("*ab"));
+        assert_eq!(StringMatch::from("ab").word().to_glob_pattern(), None);
+        assert!(StringMatch::from("ab").word().to_glob_needle().is_none());
+
+        let texts = ["(test|foo)", "*.txt", "a?b", "[a-z]", "{a,b}", r"a\b", "!x", "]", "a/b", "é"];
+        let haystacks = ["", "test", "foo", "a.txt", "aXb", "q", "a", "b", "ab", r"a\b", "a/b"];
+        for text in texts {
+            for sm in [
+                StringMatch::from(text),
+                StringMatch::from(text).partial(),
+                StringMatch::from(text).prefix(),
+                StringMatch::from(text).suffix(),
+                StringMatch::from(text.to_ascii_uppercase().as_str()).case_insensitive(),
+            ] {
+                let glob = sm.to_glob_needle().unwrap();
+                assert_eq!(glob.is_case_sensitive(), sm.is_case_sensitive());
+                for haystack in haystacks.iter().copied().chain([text, &format!("x{}x", text)]) {
+                    assert_eq!(
+                        glob.is_match(haystack),
+                        sm.is_match(haystack),
+                        "{:?} {:?}",
+                        sm,
+                        haystack
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wildcard_to_glob_pattern() {
+        let sm = StringMatch::from_wildcard("a*b?[c]");
+        assert_eq!(sm.to_glob_pattern().as_deref(), Some("a*b?[[]c[]]"));
+        assert_eq!(sm.clone().partial().to_glob_pattern().as_deref(), Some("*a*b?[[]c[]]*"));
+
+        let glob = sm.to_glob_needle().unwrap();
+        for haystack in ["ab1[c]", "axxbc[c]", "ab[c]", "ab1c", "xab1[c]"] {
+            assert_eq!(glob.is_match(haystack), sm.is_match(haystack), "{:?}", haystack);
+        }
+    }
+}
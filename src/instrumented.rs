@@ -0,0 +1,168 @@
+use crate::{Needle, NeedleIter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Needle that counts how often the inner needle is called and how often it matches.
+///
+/// The counters are atomic, so an `InstrumentedNeedle` can be shared between threads if
+/// the inner needle can. Timing is off by default; enable it with `timed()`.
+#[derive(Debug)]
+pub struct InstrumentedNeedle<N> {
+    inner: N,
+    timed: bool,
+    calls: AtomicU64,
+    matches: AtomicU64,
+    nanos: AtomicU64,
+}
+
+impl<N> InstrumentedNeedle<N>
+where
+    N: Needle,
+{
+    pub fn new(inner: N) -> Self {
+        Self {
+            inner,
+            timed: false,
+            calls: AtomicU64::new(0),
+            matches: AtomicU64::new(0),
+            nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Also record the total time spent in the inner needle's `is_match()`.
+    pub fn timed(mut self) -> Self {
+        self.timed = true;
+        self
+    }
+
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> N {
+        self.inner
+    }
+
+    /// The number of calls to `is_match()`.
+    pub fn call_count(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    /// The number of calls to `is_match()` that returned true.
+    pub fn match_count(&self) -> u64 {
+        self.matches.load(Ordering::Relaxed)
+    }
+
+    /// The fraction of calls that matched, or 0.0 if there have been no calls.
+    pub fn match_ratio(&self) -> f64 {
+        match self.call_count() {
+            0 => 0.0,
+            calls => self.match_count() as f64 / calls as f64,
+        }
+    }
+
+    /// The total time spent matching. This is always zero unless `timed()` was used.
+    pub fn total_time(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+
+    /// Reset all counters to zero.
+    pub fn reset_stats(&self) {
+        self.calls.store(0, Ordering::Relaxed);
+        self.matches.store(0, Ordering::Relaxed);
+        self.nanos.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<N> Needle for InstrumentedNeedle<N>
+where
+    N: Needle,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        let start = self.timed.then(Instant::now);
+        let matched = self.inner.is_match(haystack);
+        if let Some(start) = start {
+            let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+            self.nanos.fetch_add(nanos, Ordering::Relaxed);
+        }
+
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        if matched {
+            self.matches.fetch_add(1, Ordering::Relaxed);
+        }
+        matched
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.inner.complexity_estimate()
+    }
+}
+
+impl<N> NeedleIter for InstrumentedNeedle<N> where N: Needle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StringMatch;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_instrumented_needle() {
+        let needle = InstrumentedNeedle::new(StringMatch::from("a"));
+        assert_eq!(needle.match_ratio(), 0.0);
+        assert_eq!(needle.complexity_estimate(), 1);
+        assert!(needle.is_match("a"));
+        assert!(!needle.is_match("b"));
+        assert!(!needle.is_match("c"));
+        assert!(needle.is_match("a"));
+        assert_eq!(needle.call_count(), 4);
+        assert_eq!(needle.match_count(), 2);
+        assert_eq!(needle.match_ratio(), 0.5);
+        assert_eq!(needle.total_time(), Duration::ZERO);
+
+        needle.reset_stats();
+        assert_eq!(needle.call_count(), 0);
+        assert_eq!(needle.match_count(), 0);
+        assert!(needle.is_match_in(&mut vec!["b", "a", "c"].into_iter()));
+        assert_eq!(needle.call_count(), 2);
+    }
+
+    #[test]
+    fn test_instrumented_needle_timed() {
+        let needle = InstrumentedNeedle::new(|_: &str| {
+            thread::sleep(Duration::from_millis(1));
+            true
+        })
+        .timed();
+        assert!(needle.is_match("x"));
+        assert!(needle.total_time() >= Duration::from_millis(1));
+        needle.reset_stats();
+        assert_eq!(needle.total_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_instrumented_needle_threads() {
+        let needle = Arc::new(InstrumentedNeedle::new(StringMatch::from("match")));
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let needle = Arc::clone(&needle);
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        needle.is_match(if i % 4 == 0 {
+                            "match"
+                        } else {
+                            "miss"
+                        });
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(needle.call_count(), 8000);
+        assert_eq!(needle.match_count(), 2000);
+        assert_eq!(needle.match_ratio(), 0.25);
+    }
+}
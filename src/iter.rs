@@ -0,0 +1,134 @@
+use crate::Needle;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Lazy iterator adaptor yielding only the items that match (or don't match) a needle.
+///
+/// Created by `NeedleIter::filter_matching()` and `NeedleIter::filter_not_matching()`.
+pub struct FilterMatching<'n, N: ?Sized, I> {
+    needle: &'n N,
+    haystacks: I,
+    /// If true, yield matching items, otherwise yield non-matching items.
+    keep_matching: bool,
+}
+
+impl<'n, N, I> FilterMatching<'n, N, I>
+where
+    N: ?Sized,
+{
+    pub(crate) fn new(needle: &'n N, haystacks: I, keep_matching: bool) -> Self {
+        Self {
+            needle,
+            haystacks,
+            keep_matching,
+        }
+    }
+}
+
+impl<'n, 'a, N, I, S> Iterator for FilterMatching<'n, N, I>
+where
+    N: Needle + ?Sized,
+    I: Iterator<Item = S>,
+    S: Into<&'a str> + Clone,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let keep_matching = self.keep_matching;
+        let needle = self.needle;
+        self.haystacks.find(|h| needle.is_match(h.clone().into()) == keep_matching)
+    }
+}
+
+/// Lazy iterator adaptor yielding the index and value of each item that matches a needle.
+///
+/// Created by `NeedleIter::enumerate_matches()`.
+pub struct EnumerateMatches<'n, N: ?Sized, I> {
+    needle: &'n N,
+    haystacks: core::iter::Enumerate<I>,
+}
+
+impl<'n, N, I> EnumerateMatches<'n, N, I>
+where
+    N: ?Sized,
+    I: Iterator,
+{
+    pub(crate) fn new(needle: &'n N, haystacks: I) -> Self {
+        Self {
+            needle,
+            haystacks: haystacks.enumerate(),
+        }
+    }
+}
+
+impl<'n, 'a, N, I, S> Iterator for EnumerateMatches<'n, N, I>
+where
+    N: Needle + ?Sized,
+    I: Iterator<Item = S>,
+    S: Into<&'a str> + Clone,
+{
+    type Item = (usize, S);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let needle = self.needle;
+        self.haystacks.find(|(_, h)| needle.is_match(h.clone().into()))
+    }
+}
+
+/// Lazy iterator adaptor yielding each window of consecutive items that matches a needle.
+///
+/// Created by `NeedleIter::windows_matching()`.
+pub struct WindowsMatching<'n, N: ?Sized, I, S> {
+    needle: &'n N,
+    haystacks: I,
+    window: VecDeque<S>,
+    window_size: usize,
+}
+
+impl<'n, N, I, S> WindowsMatching<'n, N, I, S>
+where
+    N: ?Sized,
+{
+    pub(crate) fn new(needle: &'n N, haystacks: I, window_size: usize) -> Self {
+        assert!(window_size > 0, "window size must be non-zero");
+        Self {
+            needle,
+            haystacks,
+            window: VecDeque::with_capacity(window_size),
+            window_size,
+        }
+    }
+}
+
+impl<'n, 'a, N, I, S> Iterator for WindowsMatching<'n, N, I, S>
+where
+    N: Needle + ?Sized,
+    I: Iterator<Item = S>,
+    S: Into<&'a str> + Clone,
+{
+    type Item = Vec<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.window.len() == self.window_size {
+                self.window.pop_front();
+            }
+            self.window.push_back(self.haystacks.next()?);
+            if self.window.len() < self.window_size {
+                continue;
+            }
+
+            let mut haystack = String::new();
+            for (i, item) in self.window.iter().enumerate() {
+                if i > 0 {
+                    haystack.push('\n');
+                }
+                haystack.push_str(item.clone().into());
+            }
+            if self.needle.is_match(&haystack) {
+                return Some(self.window.iter().cloned().collect());
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{Needle, NeedleIter};
+use core::ops::{Range, RangeInclusive};
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Serialize};
+
+/// Needle that matches haystacks whose length in characters is within a range.
+///
+/// Both bounds are inclusive. If the minimum is greater than the maximum, nothing matches.
+/// The default has no bounds and matches every haystack.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct LengthNeedle {
+    min: Option<usize>,
+    max: Option<usize>,
+}
+
+impl LengthNeedle {
+    /// Match haystacks at least `n` characters long.
+    pub fn at_least(n: usize) -> Self {
+        Self {
+            min: Some(n),
+            max: None,
+        }
+    }
+
+    /// Match haystacks at most `n` characters long.
+    pub fn at_most(n: usize) -> Self {
+        Self {
+            min: None,
+            max: Some(n),
+        }
+    }
+
+    /// Match haystacks from `min` to `max` characters long, inclusive.
+    pub fn between(min: usize, max: usize) -> Self {
+        Self {
+            min: Some(min),
+            max: Some(max),
+        }
+    }
+
+    /// Match haystacks exactly `n` characters long.
+    pub fn exact(n: usize) -> Self {
+        Self::between(n, n)
+    }
+
+    pub fn min(&self) -> Option<usize> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<usize> {
+        self.max
+    }
+}
+
+impl Needle for LengthNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        let len = haystack.chars().count();
+        self.min.is_none_or(|min| len >= min) && self.max.is_none_or(|max| len <= max)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        1
+    }
+}
+
+impl NeedleIter for LengthNeedle {}
+
+/// A range matches haystacks whose length in characters is within the range.
+impl Needle for RangeInclusive<usize> {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.contains(&haystack.chars().count())
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        1
+    }
+}
+
+impl NeedleIter for RangeInclusive<usize> {}
+
+/// A range matches haystacks whose length in characters is within the range. The upper
+/// bound is exclusive.
+impl Needle for Range<usize> {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.contains(&haystack.chars().count())
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        1
+    }
+}
+
+impl NeedleIter for Range<usize> {}
+
+/// A `usize` needle matches haystacks that are exactly that many characters long.
+impl Needle for usize {
+    fn is_match(&self, haystack: &str) -> bool {
+        haystack.chars().count() == *self
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        1
+    }
+}
+
+impl NeedleIter for usize {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NeedleExt, StringMatch};
+
+    #[test]
+    fn test_length_needle() {
+        assert!(LengthNeedle::default().is_match(""));
+        assert!(LengthNeedle::default().is_match("anything"));
+
+        assert!(LengthNeedle::at_least(0).is_match(""));
+        assert!(!LengthNeedle::at_least(1).is_match(""));
+        assert!(LengthNeedle::at_least(1).is_match("a"));
+        assert!(LengthNeedle::at_most(0).is_match(""));
+        assert!(!LengthNeedle::at_most(0).is_match("a"));
+        assert!(LengthNeedle::at_most(1).is_match("a"));
+        assert!(!LengthNeedle::at_most(1).is_match("ab"));
+
+        let needle = LengthNeedle::between(2, 3);
+        assert_eq!((needle.min(), needle.max()), (Some(2), Some(3)));
+        assert!(!needle.is_match("a"));
+        assert!(needle.is_match("ab"));
+        assert!(needle.is_match("abc"));
+        assert!(!needle.is_match("abcd"));
+        assert!(!LengthNeedle::between(3, 2).is_match("ab"));
+
+        assert!(LengthNeedle::exact(1).is_match("a"));
+        assert!(!LengthNeedle::exact(1).is_match(""));
+        assert!(!LengthNeedle::exact(1).is_match("ab"));
+        assert!(LengthNeedle::exact(0).is_match_in(&mut vec!["a", ""].into_iter()));
+    }
+
+    #[test]
+    fn test_unicode_length() {
+        // Lengths are in characters, not bytes.
+        assert!(LengthNeedle::exact(1).is_match("é"));
+        assert!(LengthNeedle::exact(1).is_match("日"));
+        assert!(LengthNeedle::exact(1).is_match("🦀"));
+        assert!(LengthNeedle::exact(3).is_match("日本語"));
+        // A decomposed character is two characters.
+        assert!(LengthNeedle::exact(2).is_match("e\u{301}"));
+    }
+
+    #[test]
+    fn test_compose() {
+        let needle = StringMatch::from("ab").partial().and(LengthNeedle::at_most(4));
+        assert!(needle.is_match("abcd"));
+        assert!(!needle.is_match("abcde"));
+        assert!(!needle.is_match("xyz"));
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert!((3..=10).is_match("hello"));
+        assert!(!(3..=10).is_match("hi"));
+        assert!((3..=5).is_match("hello"));
+        assert!(!(3..=4).is_match("hello"));
+        assert!((3..6).is_match("hello"));
+        assert!(!(3..5).is_match("hello"));
+        assert!((0..1).is_match(""));
+        assert!(!(0..0).is_match(""));
+        assert!((2..=2).is_match("日本"));
+        assert!((1..3).is_match("🦀🦀"));
+
+        assert!(5.is_match("hello"));
+        assert!(!4.is_match("hello"));
+        assert!(0.is_match(""));
+        assert!(1.is_match("é"));
+        assert!(3.is_match_in(&mut vec!["a", "abc"].into_iter()));
+
+        let needle = (3..=10).and(StringMatch::from("pre").prefix());
+        assert!(needle.is_match("prefix"));
+        assert!(!needle.is_match("pr"));
+        assert!(!needle.is_match("prefix is too long"));
+    }
+
+    #[cfg(feature = "serde_derive")]
+    #[test]
+    fn test_serde() {
+        let orig = LengthNeedle::between(1, 5);
+        let serialized = serde_json::to_string(&orig).unwrap();
+        assert_eq!(serde_json::from_str::<LengthNeedle>(&serialized).unwrap(), orig);
+    }
+}
@@ -1,9 +1,157 @@
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::Range;
+#[cfg(feature = "std")]
 use regex::Regex;
 #[cfg(feature = "serde_derive")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "aho-corasick")]
+mod aho;
+#[cfg(feature = "tokio")]
+mod async_needle;
+#[cfg(feature = "std")]
+mod cached;
+#[cfg(feature = "std")]
+mod captures;
+#[cfg(feature = "cbor")]
+mod cbor;
+mod collections;
+mod combinators;
+mod compiled;
+mod context;
+#[cfg(feature = "std")]
+mod env;
+mod error;
+mod explain;
+mod find;
+mod fn_needle;
+#[cfg(feature = "fuzzy")]
+mod fuzzy;
+#[cfg(feature = "glob")]
+mod glob;
+#[cfg(feature = "std")]
+mod instrumented;
+mod iter;
+mod length;
+mod macros;
+mod maybe;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod named;
+mod needle_ext;
+#[cfg(feature = "std")]
+mod os_str;
+#[cfg(feature = "rayon")]
+mod par;
+mod parse;
+#[cfg(feature = "std")]
+mod path;
+mod pipeline;
+mod preprocess;
+#[cfg(feature = "std")]
+mod regex_pattern;
+#[cfg(feature = "std")]
+mod regex_set;
+#[cfg(feature = "registry")]
+mod registry;
+mod replace;
+#[cfg(feature = "ron")]
+mod ron;
+mod scored;
+#[cfg(feature = "std")]
+mod serializable_needle;
+#[cfg(feature = "std")]
+mod serializable_regex;
+mod split;
+mod stateful;
+mod streaming;
+mod text_key;
+#[cfg(feature = "std")]
+mod timed;
+#[cfg(feature = "toml")]
+mod toml;
+mod trie;
+#[cfg(feature = "unicode")]
+mod unicode;
+#[cfg(feature = "std")]
+mod vocabulary;
+mod wildcard;
+
+#[cfg(feature = "aho-corasick")]
+pub use aho::AhoCorasickNeedle;
+#[cfg(feature = "tokio")]
+pub use async_needle::{AsyncNeedle, AsyncNeedleIter};
+#[cfg(feature = "std")]
+pub use cached::CachedNeedle;
+#[cfg(feature = "std")]
+pub use captures::{CaptureNeedle, Captures};
+pub use collections::{AllNeedle, AnyNeedle, MultiNeedle};
+pub use combinators::{AlwaysNeedle, AndNeedle, NeverNeedle, NotNeedle, OrNeedle};
+pub use compiled::CompiledStringMatch;
+pub use context::{ContextNeedle, WithContext};
+pub use error::StringMatchError;
+pub use find::{FindableNeedle, MatchSpan};
+pub use fn_needle::{named_needle, FnNeedle};
+#[cfg(feature = "fuzzy")]
+pub use fuzzy::FuzzyNeedle;
+#[cfg(feature = "glob")]
+pub use glob::{GlobError, GlobNeedle};
+#[cfg(feature = "std")]
+pub use instrumented::InstrumentedNeedle;
+pub use iter::{EnumerateMatches, FilterMatching, WindowsMatching};
+pub use length::LengthNeedle;
+pub use maybe::MaybeNeedle;
+pub use named::NamedStringMatch;
+pub use needle_ext::{ArcNeedle, BoxedNeedle, NeedleExt};
+#[cfg(feature = "rayon")]
+pub use par::ParNeedleIter;
+pub use parse::StringMatchParseError;
+#[cfg(feature = "std")]
+pub use path::PathNeedle;
+pub use pipeline::NeedlePipeline;
+pub use preprocess::PreprocessedMatch;
+#[cfg(feature = "std")]
+pub use regex_set::RegexSetNeedle;
+#[cfg(feature = "registry")]
+pub use registry::PatternRegistry;
+pub use scored::{RankedMatches, ScoredNeedle};
+#[cfg(feature = "std")]
+pub use serializable_needle::{RegexNeedle, SerializableNeedle};
+#[cfg(feature = "std")]
+pub use serializable_regex::SerializableRegex;
+pub use stateful::{SequenceNeedle, StatefulNeedle};
+pub use streaming::{StreamingMatch, StreamingNeedle};
+pub use text_key::TextKey;
+#[cfg(feature = "std")]
+pub use timed::{TimedNeedle, TimeoutError};
+pub use trie::TrieNeedle;
+#[cfg(feature = "unicode")]
+pub use unicode::NormalizeForm;
+#[cfg(feature = "std")]
+pub use vocabulary::VocabularyNeedle;
+pub use wildcard::WildcardNeedle;
+
 pub trait Needle {
     fn is_match(&self, haystack: &str) -> bool;
+
+    /// A rough estimate of the cost of `is_match()`, where higher values are more expensive.
+    ///
+    /// This can be used to order needles so that cheap checks run first. Only the relative
+    /// order of the estimates is meaningful. Literal strings are 1, `StringMatch` is 1 to
+    /// about 10 depending on its options, `Regex` is 10, and other needles such as closures
+    /// default to 20.
+    fn complexity_estimate(&self) -> u32 {
+        20
+    }
 }
 
 pub trait NeedleIter: Needle {
@@ -14,21 +162,185 @@ pub trait NeedleIter: Needle {
     {
         haystacks.any(|h| self.is_match(h.into()))
     }
+
+    /// Return true if every item matches. An empty iterator returns true.
+    fn all_match<'a, I, S>(&self, mut haystacks: I) -> bool
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str>,
+    {
+        haystacks.all(|h| self.is_match(h.into()))
+    }
+
+    /// Return true if no item matches. An empty iterator returns true.
+    fn none_match<'a, I, S>(&self, mut haystacks: I) -> bool
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str>,
+    {
+        !haystacks.any(|h| self.is_match(h.into()))
+    }
+
+    /// Return the zero-based index of the first item that matches, advancing the iterator
+    /// past it.
+    fn position<'a, I, S>(&self, haystacks: &mut I) -> Option<usize>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str>,
+    {
+        haystacks.position(|h| self.is_match(h.into()))
+    }
+
+    /// Return the zero-based indices of all items that match.
+    fn positions<'a, I, S>(&self, haystacks: I) -> Vec<usize>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str>,
+    {
+        haystacks
+            .map(|h| self.is_match(h.into()))
+            .enumerate()
+            .filter_map(|(i, m)| m.then_some(i))
+            .collect()
+    }
+
+    /// Return the first item that matches, advancing the iterator past it.
+    fn first_match<'a, I, S>(&self, haystacks: &mut I) -> Option<S>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        haystacks.find(|h| self.is_match(h.clone().into()))
+    }
+
+    /// Return the last item that matches.
+    fn last_match<'a, I, S>(&self, haystacks: I) -> Option<S>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        haystacks.filter(|h| self.is_match(h.clone().into())).last()
+    }
+
+    /// Return the nth (zero-based) item that matches, advancing the iterator past it.
+    fn nth_match<'a, I, S>(&self, haystacks: &mut I, n: usize) -> Option<S>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        haystacks.filter(|h| self.is_match(h.clone().into())).nth(n)
+    }
+
+    /// Return the number of items that match.
+    fn count_matches<'a, I, S>(&self, haystacks: I) -> usize
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str>,
+    {
+        haystacks.map(|h| self.is_match(h.into())).filter(|&m| m).count()
+    }
+
+    /// Split the items into those that match and those that don't, preserving their order.
+    ///
+    /// Unlike the other methods this accepts any `S: AsRef<str>`, so it works with owned
+    /// `String` items as well as `&str`.
+    fn partition_matching<I, S>(&self, haystacks: I) -> (Vec<S>, Vec<S>)
+    where
+        I: Iterator<Item = S>,
+        S: AsRef<str>,
+    {
+        haystacks.partition(|h| self.is_match(h.as_ref()))
+    }
+
+    /// Return a lazy iterator over the items that match.
+    ///
+    /// The iterator borrows the needle, so this works for needles that are not `Clone`.
+    fn filter_matching<'a, I, S>(&self, haystacks: I) -> FilterMatching<'_, Self, I>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        FilterMatching::new(self, haystacks, true)
+    }
+
+    /// Return a lazy iterator over the items that don't match.
+    fn filter_not_matching<'a, I, S>(&self, haystacks: I) -> FilterMatching<'_, Self, I>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        FilterMatching::new(self, haystacks, false)
+    }
+
+    /// Return a lazy iterator over the items that match, along with their zero-based index
+    /// in the original iterator.
+    fn enumerate_matches<'a, I, S>(&self, haystacks: I) -> EnumerateMatches<'_, Self, I>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        EnumerateMatches::new(self, haystacks)
+    }
+
+    /// Return true if any `window_size` consecutive items match when joined with newlines.
+    ///
+    /// A `Partial` needle can match text that spans items, and `line_by_line()` matches each
+    /// item separately. If there are fewer than `window_size` items there are no windows and
+    /// this returns false.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is 0.
+    fn window_match<'a, I, S>(&self, haystacks: I, window_size: usize) -> bool
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        self.windows_matching(haystacks, window_size).next().is_some()
+    }
+
+    /// Return a lazy iterator over all windows of `window_size` consecutive items that match
+    /// when joined with newlines, in order. The windows overlap. See `window_match()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is 0.
+    fn windows_matching<'a, I, S>(
+        &self,
+        haystacks: I,
+        window_size: usize,
+    ) -> WindowsMatching<'_, Self, I, S>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        WindowsMatching::new(self, haystacks, window_size)
+    }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// The ordering of variants (`Full < Partial < Word < Prefix < Suffix`) is arbitrary and not
+/// semantically meaningful, but it is stable across versions.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
 pub enum StringMatchLength {
-    /// Needle string must match the whole haystack string.
+    /// Needle string must match the whole haystack string. This is the default.
+    #[default]
     Full,
     /// Needle string can be any substring within the haystack string.
     Partial,
     /// Needle string will only match strings within the haystack surrounded by spaces or
     /// a string boundary.
     Word,
+    /// Needle string must match the start of the haystack string.
+    Prefix,
+    /// Needle string must match the end of the haystack string.
+    Suffix,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// `StringMatch` is ordered by text, then match length, then case sensitivity, then the
+/// remaining options. This ordering is arbitrary and not semantically meaningful, but it is
+/// stable across versions, so it can be used for sorting and as a `BTreeMap` key.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
 pub struct StringMatch {
     text: String,
@@ -37,6 +349,59 @@ pub struct StringMatch {
     match_length: StringMatchLength,
     /// If true, use a case-sensitive match. Default is true.
     case_sensitive: bool,
+    /// The characters that separate words in `Word` mode. Default is None, which means
+    /// words are separated by spaces.
+    #[cfg_attr(feature = "serde_derive", serde(default, skip_serializing_if = "Option::is_none"))]
+    separator_chars: Option<String>,
+    /// If true, collapse each run of whitespace in the needle and haystack into a single
+    /// space before matching. Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    normalize_whitespace: bool,
+    /// If true, strip leading and trailing whitespace from the haystack before matching.
+    /// Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    trim: bool,
+    /// If true, strip trailing whitespace from the haystack before matching.
+    /// Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    trim_end: bool,
+    /// If true, remove diacritics from the needle and haystack before matching.
+    /// Default is false.
+    #[cfg(feature = "unicode")]
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    strip_diacritics: bool,
+    /// The Unicode normalization form to apply to the needle and haystack before matching.
+    /// Default is NormalizeForm::None.
+    #[cfg(feature = "unicode")]
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    normalize_unicode: NormalizeForm,
+    /// If true, match each line of the haystack separately. Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    multiline: bool,
+    /// The minimum length of the haystack in characters. Default is None, for no minimum.
+    #[cfg_attr(feature = "serde_derive", serde(default, skip_serializing_if = "Option::is_none"))]
+    min_length: Option<usize>,
+    /// The maximum length of the haystack in characters. Default is None, for no maximum.
+    #[cfg_attr(feature = "serde_derive", serde(default, skip_serializing_if = "Option::is_none"))]
+    max_length: Option<usize>,
+    /// If true, never match haystacks that contain non-ASCII characters. Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    ascii_only: bool,
+    /// If true, remove punctuation from the needle and haystack before matching.
+    /// Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    ignore_punctuation: bool,
+    /// If true, remove digits from the needle and haystack before matching. Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    ignore_numbers: bool,
+    /// If true, `*` in the needle matches any sequence of characters and `?` matches any
+    /// single character. Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    wildcard: bool,
+    /// If true, collapse each run of whitespace in the needle and haystack into a single
+    /// space and strip leading and trailing whitespace before matching. Default is false.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
+    collapse_whitespace: bool,
 }
 
 impl<S> From<S> for StringMatch
@@ -48,10 +413,34 @@ where
             text: text.into(),
             match_length: StringMatchLength::Full,
             case_sensitive: true,
+            separator_chars: None,
+            normalize_whitespace: false,
+            trim: false,
+            trim_end: false,
+            #[cfg(feature = "unicode")]
+            strip_diacritics: false,
+            #[cfg(feature = "unicode")]
+            normalize_unicode: NormalizeForm::None,
+            multiline: false,
+            min_length: None,
+            max_length: None,
+            ascii_only: false,
+            ignore_punctuation: false,
+            ignore_numbers: false,
+            wildcard: false,
+            collapse_whitespace: false,
         }
     }
 }
 
+/// The default `StringMatch` is an empty, full, case-sensitive needle, which only matches
+/// the empty string.
+impl Default for StringMatch {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
 impl StringMatch {
     pub fn new<S>(text: S) -> Self
     where
@@ -60,6 +449,93 @@ impl StringMatch {
         Self::from(text)
     }
 
+    /// Create a needle that matches `text` literally. This is the same as `new()`, or
+    /// `StringMatch::new(text).literal()`.
+    ///
+    /// Characters such as `*` and `|` only match themselves, and `to_regex_pattern()` and
+    /// `to_glob_pattern()` escape them. This holds as long as `wildcard()` is not set
+    /// afterwards.
+    pub fn from_literal<S>(text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new(text).literal()
+    }
+
+    /// Create a needle from a wildcard pattern, where `*` matches any sequence of characters
+    /// (including none) and `?` matches exactly one character. This is the same as
+    /// `StringMatch::new(pattern).wildcard()`.
+    ///
+    /// The pattern must match the whole haystack, as with `WildcardNeedle`.
+    pub fn from_wildcard(pattern: &str) -> Self {
+        Self::new(pattern).wildcard()
+    }
+
+    /// Fallible version of `new()`.
+    ///
+    /// Plain string patterns are always valid, so this currently never fails.
+    pub fn try_new<S>(text: S) -> Result<Self, StringMatchError>
+    where
+        S: Into<String>,
+    {
+        let sm = Self::new(text);
+        sm.validate()?;
+        Ok(sm)
+    }
+
+    /// Check that this `StringMatch` is in a consistent state.
+    ///
+    /// This fails if `min_length()` is greater than `max_length()`, since nothing can match,
+    /// or if a `Word` match was given an empty list of separators by `word_with_separators()`.
+    pub fn validate(&self) -> Result<(), StringMatchError> {
+        if let (Some(min), Some(max)) = (self.min_length, self.max_length) {
+            if min > max {
+                return Err(StringMatchError::InvalidLengthBounds {
+                    min,
+                    max,
+                });
+            }
+        }
+        if self.is_word_match() && self.separators() == Some("") {
+            return Err(StringMatchError::EmptySeparators);
+        }
+        Ok(())
+    }
+
+    /// Return true if `validate()` succeeds.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Create a full, case-sensitive `StringMatch` for the single character `c`.
+    pub fn from_char(c: char) -> Self {
+        Self::from(c.to_string())
+    }
+
+    /// Return a needle that matches every haystack.
+    pub fn always() -> AlwaysNeedle {
+        AlwaysNeedle
+    }
+
+    /// Return a needle that never matches any haystack.
+    pub fn never() -> NeverNeedle {
+        NeverNeedle
+    }
+
+    /// The needle text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Return true if the needle text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn match_length(&self) -> &StringMatchLength {
+        &self.match_length
+    }
+
     pub fn is_full_match(&self) -> bool {
         matches!(self.match_length, StringMatchLength::Full)
     }
@@ -72,10 +548,82 @@ impl StringMatch {
         matches!(self.match_length, StringMatchLength::Word)
     }
 
+    pub fn is_prefix_match(&self) -> bool {
+        matches!(self.match_length, StringMatchLength::Prefix)
+    }
+
+    pub fn is_suffix_match(&self) -> bool {
+        matches!(self.match_length, StringMatchLength::Suffix)
+    }
+
     pub fn is_case_sensitive(&self) -> bool {
         self.case_sensitive
     }
 
+    /// The custom word separators, if set by `word_with_separators()`.
+    pub fn separators(&self) -> Option<&str> {
+        self.separator_chars.as_deref()
+    }
+
+    pub fn is_whitespace_normalized(&self) -> bool {
+        self.normalize_whitespace
+    }
+
+    pub fn is_whitespace_collapsed(&self) -> bool {
+        self.collapse_whitespace
+    }
+
+    pub fn is_trimmed(&self) -> bool {
+        self.trim
+    }
+
+    pub fn is_end_trimmed(&self) -> bool {
+        self.trim_end
+    }
+
+    pub fn is_ascii_only(&self) -> bool {
+        self.ascii_only
+    }
+
+    pub fn is_line_by_line(&self) -> bool {
+        self.multiline
+    }
+
+    pub fn is_punctuation_ignored(&self) -> bool {
+        self.ignore_punctuation
+    }
+
+    pub fn is_numbers_ignored(&self) -> bool {
+        self.ignore_numbers
+    }
+
+    pub fn is_wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    /// Return true if diacritics are removed before matching. This is always false without
+    /// the `unicode` feature.
+    pub fn is_diacritic_insensitive(&self) -> bool {
+        #[cfg(feature = "unicode")]
+        return self.strip_diacritics;
+        #[cfg(not(feature = "unicode"))]
+        return false;
+    }
+
+    /// Return true if a Unicode normalization form is applied before matching. This is
+    /// always false without the `unicode` feature.
+    pub fn is_unicode_normalized(&self) -> bool {
+        #[cfg(feature = "unicode")]
+        return self.normalize_unicode != NormalizeForm::None;
+        #[cfg(not(feature = "unicode"))]
+        return false;
+    }
+
+    #[cfg(feature = "unicode")]
+    pub fn normalize_form(&self) -> NormalizeForm {
+        self.normalize_unicode
+    }
+
     pub fn partial(mut self) -> Self {
         self.match_length = StringMatchLength::Partial;
         self
@@ -91,6 +639,24 @@ impl StringMatch {
         self
     }
 
+    /// Match whole words, where a word boundary is any of the characters in `separators`
+    /// (or the start or end of the haystack), instead of a space.
+    pub fn word_with_separators(mut self, separators: &str) -> Self {
+        self.match_length = StringMatchLength::Word;
+        self.separator_chars = Some(separators.to_string());
+        self
+    }
+
+    pub fn prefix(mut self) -> Self {
+        self.match_length = StringMatchLength::Prefix;
+        self
+    }
+
+    pub fn suffix(mut self) -> Self {
+        self.match_length = StringMatchLength::Suffix;
+        self
+    }
+
     pub fn case_insensitive(mut self) -> Self {
         self.case_sensitive = false;
         self
@@ -100,120 +666,847 @@ impl StringMatch {
         self.case_sensitive = true;
         self
     }
-}
 
-fn needle_in_haystack(needle: &str, haystack: &str, match_length: &StringMatchLength) -> bool {
-    match match_length {
-        StringMatchLength::Full => haystack == needle,
-        StringMatchLength::Partial => haystack.contains(needle),
-        StringMatchLength::Word => format!(" {} ", haystack).contains(&format!(" {} ", needle)),
+    /// Replace the needle text, keeping the other options.
+    pub fn set_text<S>(&mut self, text: S)
+    where
+        S: Into<String>,
+    {
+        self.text = text.into();
     }
-}
 
-impl Needle for StringMatch {
-    fn is_match(&self, haystack: &str) -> bool {
-        match self.case_sensitive {
-            true => needle_in_haystack(&self.text, haystack, &self.match_length),
-            false => {
-                let hs = haystack.to_lowercase();
-                let needle = self.text.to_lowercase();
-                needle_in_haystack(&needle, &hs, &self.match_length)
-            }
-        }
+    pub fn set_match_length(&mut self, match_length: StringMatchLength) {
+        self.match_length = match_length;
     }
-}
 
-impl Needle for Regex {
-    fn is_match(&self, haystack: &str) -> bool {
-        self.is_match(haystack)
+    pub fn set_case_sensitive(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
     }
-}
 
-impl Needle for &str {
-    fn is_match(&self, haystack: &str) -> bool {
-        self == &haystack
+    pub fn set_ascii_only(&mut self, ascii_only: bool) {
+        self.ascii_only = ascii_only;
     }
-}
 
-impl Needle for String {
-    fn is_match(&self, haystack: &str) -> bool {
-        self == haystack
+    /// Return a copy of this `StringMatch` with different text.
+    pub fn with_text<S>(&self, text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut sm = self.clone();
+        sm.set_text(text);
+        sm
     }
-}
 
-impl<F> Needle for F
-where
-    F: Fn(&str) -> bool,
-{
-    fn is_match(&self, haystack: &str) -> bool {
-        self(haystack)
+    /// Return a copy of this `StringMatch` with `f` applied to the text, keeping the other
+    /// options.
+    pub fn map_text<F>(&self, f: F) -> Self
+    where
+        F: Fn(&str) -> String,
+    {
+        self.with_text(f(&self.text))
     }
-}
 
-pub trait StringMatchable: Into<StringMatch> {
-    fn match_case_sensitive(self) -> StringMatch {
-        self.into().case_sensitive()
+    /// Apply `f` to the text in place, keeping the other options.
+    pub fn map_text_in_place<F>(&mut self, f: F)
+    where
+        F: Fn(&str) -> String,
+    {
+        self.text = f(&self.text);
     }
 
-    fn match_case_insensitive(self) -> StringMatch {
-        self.into().case_insensitive()
+    /// Return a copy of this `StringMatch` with a different match length.
+    pub fn with_match_length(&self, match_length: StringMatchLength) -> Self {
+        let mut sm = self.clone();
+        sm.set_match_length(match_length);
+        sm
     }
 
-    fn match_full(self) -> StringMatch {
-        self.into().full()
+    /// Return a copy of this `StringMatch` with different case sensitivity.
+    pub fn with_case_sensitive(&self, case_sensitive: bool) -> Self {
+        let mut sm = self.clone();
+        sm.set_case_sensitive(case_sensitive);
+        sm
     }
 
-    fn match_word(self) -> StringMatch {
-        self.into().word()
+    /// Collapse each run of whitespace into a single space before matching.
+    ///
+    /// This applies to both the needle and the haystack, so `"hello  world"` and
+    /// `"hello\tworld"` are both treated as `"hello world"`.
+    pub fn normalize_whitespace(mut self) -> Self {
+        self.normalize_whitespace = true;
+        self
     }
 
-    fn match_partial(self) -> StringMatch {
-        self.into().partial()
+    /// Collapse each run of whitespace into a single space, and strip leading and trailing
+    /// whitespace, before matching.
+    ///
+    /// Unlike `normalize_whitespace()` with `trim()`, this also strips the needle, so
+    /// `"  hello   world  "` and `"hello world"` are the same whether they are the needle or
+    /// the haystack. This is useful when matching text extracted from HTML.
+    pub fn collapse_whitespace(mut self) -> Self {
+        self.collapse_whitespace = true;
+        self
     }
-}
-
-impl StringMatchable for String {}
-impl StringMatchable for &str {}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Match each line of the haystack separately, matching if any line matches.
+    ///
+    /// The haystack is split on `'\n'`, and a trailing `'\r'` is removed from each line. For
+    /// example, in `Full` mode a line must equal the needle, but the whole haystack need not.
+    /// The other options, such as `trim()`, apply to each line.
+    pub fn line_by_line(mut self) -> Self {
+        self.multiline = true;
+        self
+    }
 
-    #[test]
-    fn test_stringmatch() {
-        assert!(StringMatch::from("a").is_full_match());
-        assert!(!StringMatch::from("a").is_partial_match());
-        assert!(StringMatch::from("a").is_case_sensitive());
-        assert!(StringMatch::from("a").is_match("a"));
-        assert!(!StringMatch::from("a").is_match(""));
-        assert!(!StringMatch::from("a").is_match("b"));
-        assert!(!StringMatch::from("a").is_match("A"));
+    /// Only match haystacks that are at least `n` characters long.
+    ///
+    /// The length of the whole haystack is checked before any other options are applied, so
+    /// it includes any whitespace removed by `trim()` and all lines for `line_by_line()`. The
+    /// exception is characters removed by `ignore_punctuation()` and `ignore_numbers()`,
+    /// which are not counted.
+    pub fn min_length(mut self, n: usize) -> Self {
+        self.min_length = Some(n);
+        self
+    }
 
-        assert!(StringMatch::from("a").partial().is_partial_match());
-        assert!(!StringMatch::from("a").partial().is_full_match());
-        assert!(!StringMatch::from("a").partial().is_word_match());
-        assert!(StringMatch::from("a").partial().is_case_sensitive());
-        assert!(StringMatch::from("a").partial().is_match("a"));
-        assert!(StringMatch::from("a").partial().is_match("aa"));
-        assert!(StringMatch::from("a").partial().is_match("dad"));
-        assert!(StringMatch::from("a").partial().is_match("ba"));
-        assert!(!StringMatch::from("A").partial().is_match("a"));
-        assert!(!StringMatch::from("a").partial().is_match("A"));
+    /// Only match haystacks that are at most `n` characters long. See `min_length()`.
+    pub fn max_length(mut self, n: usize) -> Self {
+        self.max_length = Some(n);
+        self
+    }
 
-        assert!(!StringMatch::from("a").case_insensitive().is_case_sensitive());
-        assert!(!StringMatch::from("a").case_insensitive().is_partial_match());
-        assert!(StringMatch::from("a").case_insensitive().is_match("a"));
-        assert!(StringMatch::from("a").case_insensitive().is_match("A"));
-        assert!(!StringMatch::from("a").case_insensitive().is_match("aa"));
+    /// The minimum and maximum haystack lengths in characters, if set.
+    pub fn length_bounds(&self) -> (Option<usize>, Option<usize>) {
+        (self.min_length, self.max_length)
+    }
 
-        assert!(StringMatch::from("a").partial().case_insensitive().is_partial_match());
-        assert!(!StringMatch::from("a").partial().case_insensitive().is_case_sensitive());
-        assert!(StringMatch::from("a").partial().case_insensitive().is_match("a"));
-        assert!(StringMatch::from("a").partial().case_insensitive().is_match("aa"));
-        assert!(StringMatch::from("a").partial().case_insensitive().is_match("A"));
-        assert!(StringMatch::from("a").partial().case_insensitive().is_match("AA"));
-        assert!(StringMatch::from("aA").partial().case_insensitive().is_match("Aa"));
-        assert!(StringMatch::from("aA").partial().case_insensitive().is_match("Aaa"));
+    /// Never match haystacks that contain non-ASCII characters.
+    ///
+    /// Like `min_length()`, this checks the whole haystack before any other options are
+    /// applied.
+    pub fn ascii_only(mut self) -> Self {
+        self.ascii_only = true;
+        self
+    }
+
+    /// Clear the `ascii_only()` option.
+    pub fn not_ascii_only(mut self) -> Self {
+        self.ascii_only = false;
+        self
+    }
+
+    /// Remove punctuation from the needle and haystack before matching, so that `"ISBN-10"`
+    /// matches `"ISBN10"`.
+    ///
+    /// With the `unicode` feature punctuation is any character in the Unicode punctuation
+    /// category (`General_Category=P`). Otherwise it is ASCII punctuation, as defined by
+    /// `char::is_ascii_punctuation()`, which also includes ASCII symbols such as `'$'` and
+    /// `'+'`.
+    ///
+    /// In the needle, punctuation between two other characters also allows whitespace, so
+    /// `"ISBN-10"` matches `"ISBN 10"` and `"ISBN - 10"` as well. Any whitespace next to the
+    /// punctuation in the needle is ignored too. Elsewhere, punctuation is removed before
+    /// whitespace is collapsed by `normalize_whitespace()`, so a haystack of `"a - b"`
+    /// becomes `"a b"`. In `Word` mode, word boundaries are found in the text after
+    /// punctuation is removed.
+    pub fn ignore_punctuation(mut self) -> Self {
+        self.ignore_punctuation = true;
+        self
+    }
+
+    /// Remove digits from the needle and haystack before matching, so that `"Python3"`
+    /// matches `"Python"`.
+    ///
+    /// With the `unicode` feature a digit is any Unicode decimal digit
+    /// (`General_Category=Nd`), such as `'٣'`. Otherwise only the ASCII digits `'0'` to `'9'`
+    /// are removed. As with `ignore_punctuation()`, whitespace is collapsed and word
+    /// boundaries are found after the digits are removed.
+    pub fn ignore_numbers(mut self) -> Self {
+        self.ignore_numbers = true;
+        self
+    }
+
+    /// Treat `*` in the needle as matching any sequence of characters (including none) and
+    /// `?` as matching exactly one character. There is no way to match a literal `*` or `?`.
+    ///
+    /// The match length and other options apply as usual, so a `Partial` wildcard needle
+    /// matches if the pattern matches any substring of the haystack. In `Word` mode the
+    /// pattern must match whole words, which are always found using the separator
+    /// characters (spaces by default), even with the `unicode` feature. `ignore_punctuation()`
+    /// does not remove the wildcards from the needle.
+    pub fn wildcard(mut self) -> Self {
+        self.wildcard = true;
+        self
+    }
+
+    /// Treat every character in the needle literally, clearing the `wildcard()` option.
+    ///
+    /// This is the default. The needle text is not changed: `to_regex_pattern()` and
+    /// `to_glob_pattern()` escape any special characters when converting a literal needle.
+    pub fn literal(mut self) -> Self {
+        self.wildcard = false;
+        self
+    }
+
+    /// Treat every character in the needle literally. This is the same as `literal()`.
+    pub fn escape(self) -> Self {
+        self.literal()
+    }
+
+    /// Return true if the haystack passes the `ascii_only()`, `min_length()` and
+    /// `max_length()` checks.
+    pub(crate) fn is_haystack_allowed(&self, haystack: &str) -> bool {
+        if self.ascii_only && !haystack.is_ascii() {
+            return false;
+        }
+        if self.min_length.is_none() && self.max_length.is_none() {
+            return true;
+        }
+        let len = match self.has_ignored_chars() {
+            true => haystack.chars().filter(|&c| !self.is_ignored_char(c)).count(),
+            false => haystack.chars().count(),
+        };
+        self.is_length_allowed(len)
+    }
+
+    /// Return true if a haystack of `len` characters passes the `min_length()` and
+    /// `max_length()` checks.
+    pub(crate) fn is_length_allowed(&self, len: usize) -> bool {
+        self.min_length.is_none_or(|min| len >= min) && self.max_length.is_none_or(|max| len <= max)
+    }
+
+    /// Strip leading and trailing whitespace from the haystack before matching.
+    pub fn trim(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    /// Strip leading and trailing whitespace from the haystack before matching. This is the
+    /// same as `trim()`.
+    pub fn trim_before_match(self) -> Self {
+        self.trim()
+    }
+
+    /// Strip trailing whitespace from the haystack before matching.
+    pub fn trim_end_before_match(mut self) -> Self {
+        self.trim_end = true;
+        self
+    }
+
+    /// Remove diacritics from the needle and haystack before matching, so that `"cafe"`
+    /// matches `"café"`.
+    ///
+    /// Text is decomposed (NFD) and nonspacing marks are dropped. Letters that do not
+    /// decompose, such as `'ø'` or `'đ'`, are unaffected.
+    #[cfg(feature = "unicode")]
+    pub fn strip_diacritics(mut self) -> Self {
+        self.strip_diacritics = true;
+        self
+    }
+
+    /// Normalize the needle and haystack to the given Unicode normalization form before
+    /// matching, so that characters with different encodings compare equal.
+    #[cfg(feature = "unicode")]
+    pub fn normalize_unicode(mut self, form: NormalizeForm) -> Self {
+        self.normalize_unicode = form;
+        self
+    }
+
+    /// Normalize to NFC before matching, so that `"é"` matches `"e\u{301}"`. This is the
+    /// same as `normalize_unicode(NormalizeForm::Nfc)`.
+    #[cfg(feature = "unicode")]
+    pub fn nfc(self) -> Self {
+        self.normalize_unicode(NormalizeForm::Nfc)
+    }
+
+    /// Normalize to NFD before matching. This is the same as
+    /// `normalize_unicode(NormalizeForm::Nfd)`.
+    #[cfg(feature = "unicode")]
+    pub fn nfd(self) -> Self {
+        self.normalize_unicode(NormalizeForm::Nfd)
+    }
+
+    /// Return true if every haystack that matches `self` is guaranteed to also match `other`,
+    /// i.e. `self` accepts a subset of the haystacks that `other` accepts.
+    ///
+    /// `Full` is stricter than every other match length, and `Word`, `Prefix` and `Suffix`
+    /// are stricter than `Partial`. The exception is `Word` with Unicode word boundaries, as
+    /// a needle with no words in it matches any haystack with no words. A case-sensitive
+    /// match is stricter than a case-insensitive one. A needle is stricter than itself. This
+    /// returns false if the needles have different text or options, since no general
+    /// ordering is possible.
+    pub fn is_stricter_than(&self, other: &StringMatch) -> bool {
+        use StringMatchLength::*;
+
+        let same_options = StringMatch {
+            match_length: self.match_length.clone(),
+            case_sensitive: self.case_sensitive,
+            ..other.clone()
+        };
+        if *self != same_options || (!self.case_sensitive && other.case_sensitive) {
+            return false;
+        }
+        match (&self.match_length, &other.match_length) {
+            (a, b) if a == b => true,
+            (Full, _) => true,
+            (Prefix | Suffix, Partial) => true,
+            (Word, Partial) => self.separator_chars.is_some() || !cfg!(feature = "unicode"),
+            _ => false,
+        }
+    }
+
+    /// Return true if every haystack that matches `other` is guaranteed to also match `self`.
+    /// This is the same as `other.is_stricter_than(self)`.
+    pub fn subsumes(&self, other: &StringMatch) -> bool {
+        other.is_stricter_than(self)
+    }
+
+    /// Return true if the needles match the same haystacks.
+    ///
+    /// This is an approximation: the needles must have the same match length and options, and
+    /// the same text once the options are applied, so `"Hello"` and `"hello"` are equivalent
+    /// when both are case-insensitive. Needles with different settings that happen to match
+    /// the same haystacks, such as a case-insensitive `"123"` and a case-sensitive `"123"`,
+    /// are not considered equivalent.
+    pub fn is_equivalent_to(&self, other: &StringMatch) -> bool {
+        let same_options = StringMatch {
+            text: self.text.clone(),
+            ..other.clone()
+        };
+        *self == same_options && self.compiled_text() == other.compiled_text()
+    }
+
+    /// The needle text in the form that is compared against haystacks: lowercased for a
+    /// case-insensitive match, with whitespace collapsed if `normalize_whitespace()` or
+    /// `collapse_whitespace()` is set and diacritics removed if `strip_diacritics()` is set.
+    ///
+    /// With `ignore_punctuation()`, each run of punctuation between two other characters is
+    /// written as a single `-`, which matches any whitespace or none, so `"ISBN - 10"`
+    /// becomes `"ISBN-10"`.
+    pub fn canonical_text(&self) -> String {
+        self.compiled_text().into_owned()
+    }
+
+    /// Return a copy with `canonical_text()` as its text.
+    ///
+    /// The copy matches the same haystacks, and needles for which `is_equivalent_to()` is true
+    /// have equal canonical forms, so this is useful for deduplication.
+    ///
+    /// Note that the copy keeps the case sensitivity of `self`. It is not made case-sensitive,
+    /// because the lowercased text of a case-insensitive needle would then stop matching
+    /// haystacks such as `"HELLO"`, and the copy would no longer be equivalent. Call
+    /// `case_sensitive()` on the result if a case-sensitive lowercase needle is wanted.
+    pub fn canonical_form(&self) -> StringMatch {
+        self.with_text(self.canonical_text())
+    }
+
+    /// Return a needle that matches the text of `self` immediately followed by the text of
+    /// `other`.
+    ///
+    /// The result is anchored at the start if `self` is (`Full` or `Prefix`), and at the end
+    /// if `other` is (`Full` or `Suffix`), so two `Full` needles give a `Full` needle, two
+    /// `Partial` needles give a `Partial` needle, and a `Full` needle followed by a `Partial`
+    /// one gives a `Prefix` needle. If either needle is `Word`, so is the result. The result
+    /// is case-sensitive only if both needles are. All other options are taken from `self`.
+    pub fn concat(mut self, other: StringMatch) -> Self {
+        use StringMatchLength::*;
+
+        let start = matches!(self.match_length, Full | Prefix);
+        let end = matches!(other.match_length, Full | Suffix);
+        self.match_length = match (&self.match_length, &other.match_length) {
+            (Word, _) | (_, Word) => Word,
+            _ if start && end => Full,
+            _ if start => Prefix,
+            _ if end => Suffix,
+            _ => Partial,
+        };
+        self.case_sensitive &= other.case_sensitive;
+        self.text.push_str(&other.text);
+        self
+    }
+
+    /// Apply the trim, ignored character, whitespace and diacritic options to the haystack.
+    fn prepare_haystack<'h>(&self, haystack: &'h str) -> Cow<'h, str> {
+        let haystack = self.trim_haystack(haystack);
+        let haystack = match self.has_ignored_chars() {
+            true => Cow::Owned(haystack.chars().filter(|&c| !self.is_ignored_char(c)).collect()),
+            false => Cow::Borrowed(haystack),
+        };
+        self.prepare_text(haystack)
+    }
+
+    /// Apply the trim options to the haystack.
+    pub(crate) fn trim_haystack<'h>(&self, haystack: &'h str) -> &'h str {
+        match (self.trim, self.trim_end) {
+            (true, _) => haystack.trim(),
+            (false, true) => haystack.trim_end(),
+            (false, false) => haystack,
+        }
+    }
+
+    /// Apply the ignored character, whitespace and diacritic options to the needle text.
+    fn prepare_needle(&self) -> Cow<'_, str> {
+        let text = match self.has_ignored_chars() {
+            true => Cow::Owned(self.remove_ignored_needle_chars()),
+            false => Cow::Borrowed(self.text.as_str()),
+        };
+        self.prepare_text(text)
+    }
+
+    /// Remove the ignored characters from the needle text, keeping any wildcards.
+    ///
+    /// Each run of punctuation between two other characters is replaced, along with any
+    /// whitespace around it, by a single `PUNCTUATION_GAP`. Punctuation at the start or end
+    /// of the needle is just removed.
+    fn remove_ignored_needle_chars(&self) -> String {
+        let mut needle = String::with_capacity(self.text.len());
+        // The whitespace since the last character that was kept, and whether any punctuation
+        // was removed since then.
+        let mut whitespace = String::new();
+        let mut gap = false;
+        for c in self.text.chars() {
+            let is_wildcard = self.wildcard && (c == '*' || c == '?');
+            if self.is_ignored_char(c) && !is_wildcard {
+                gap |= self.ignore_punctuation && is_punctuation(c);
+            } else if c.is_whitespace() {
+                whitespace.push(c);
+            } else {
+                match gap && !needle.is_empty() {
+                    true => needle.push(PUNCTUATION_GAP),
+                    false => needle.push_str(&whitespace),
+                }
+                whitespace.clear();
+                gap = false;
+                needle.push(c);
+            }
+        }
+        needle.push_str(&whitespace);
+        needle
+    }
+
+    /// Return true if the prepared needle must be matched as a pattern, because it contains
+    /// wildcards or `PUNCTUATION_GAP`s, rather than compared literally.
+    pub(crate) fn is_pattern(&self, needle: &str) -> bool {
+        self.wildcard || (self.ignore_punctuation && needle.contains(PUNCTUATION_GAP))
+    }
+
+    /// The needle text with all options applied, ready to compare against a prepared haystack.
+    pub(crate) fn compiled_text(&self) -> Cow<'_, str> {
+        let needle = self.prepare_needle();
+        match self.case_sensitive {
+            true => needle,
+            false => Cow::Owned(needle.to_lowercase()),
+        }
+    }
+
+    /// Match the haystack against `needle`, which must come from `compiled_text()`.
+    pub(crate) fn is_match_compiled(&self, needle: &str, haystack: &str) -> bool {
+        self.is_haystack_allowed(haystack)
+            && lines(haystack, self.multiline).any(|(_, line)| self.is_line_match(needle, line))
+    }
+
+    /// Match a single line, ignoring the `line_by_line()` option.
+    fn is_line_match(&self, needle: &str, haystack: &str) -> bool {
+        let haystack = self.prepare_haystack(haystack);
+        let haystack = match self.case_sensitive {
+            true => haystack,
+            false => Cow::Owned(haystack.to_lowercase()),
+        };
+        match self.is_pattern(needle) {
+            true => wildcard::pattern_in_haystack(self, needle, &haystack),
+            false => needle_in_haystack(needle, &haystack, &self.match_length, self.separators()),
+        }
+    }
+
+    /// Return true if `ignore_punctuation()` or `ignore_numbers()` is set.
+    pub(crate) fn has_ignored_chars(&self) -> bool {
+        self.ignore_punctuation || self.ignore_numbers
+    }
+
+    /// Return true if `c` is removed from the needle and haystack before matching.
+    pub(crate) fn is_ignored_char(&self, c: char) -> bool {
+        (self.ignore_punctuation && is_punctuation(c)) || (self.ignore_numbers && is_digit(c))
+    }
+
+    /// Apply the whitespace, normalization and diacritic options.
+    fn prepare_text<'t>(&self, text: Cow<'t, str>) -> Cow<'t, str> {
+        let text = match (self.normalize_whitespace, self.collapse_whitespace) {
+            (_, true) => Cow::Owned(String::from(collapse_whitespace(&text).trim_matches(' '))),
+            (true, false) => Cow::Owned(collapse_whitespace(&text)),
+            (false, false) => text,
+        };
+        #[cfg(feature = "unicode")]
+        let text = match self.normalize_unicode {
+            NormalizeForm::None => text,
+            form => Cow::Owned(unicode::normalize(&text, form)),
+        };
+        #[cfg(feature = "unicode")]
+        if self.strip_diacritics {
+            return Cow::Owned(unicode::strip_diacritics(&text));
+        }
+        text
+    }
+}
+
+/// Marks where punctuation was removed from a needle by `StringMatch::ignore_punctuation()`.
+/// It matches any run of whitespace in the haystack, including none.
+///
+/// The hyphen can't otherwise appear in the prepared needle or haystack, since it is
+/// punctuation and all punctuation is removed.
+pub(crate) const PUNCTUATION_GAP: char = '-';
+
+/// Return true if `c` is punctuation for `StringMatch::ignore_punctuation()`.
+fn is_punctuation(c: char) -> bool {
+    #[cfg(feature = "unicode")]
+    return unicode::is_punctuation(c);
+    #[cfg(not(feature = "unicode"))]
+    return c.is_ascii_punctuation();
+}
+
+/// Return true if `c` is a digit for `StringMatch::ignore_numbers()`.
+fn is_digit(c: char) -> bool {
+    #[cfg(feature = "unicode")]
+    return unicode::is_decimal_digit(c);
+    #[cfg(not(feature = "unicode"))]
+    return c.is_ascii_digit();
+}
+
+/// Replace each run of whitespace characters with a single ASCII space.
+fn collapse_whitespace(s: &str) -> String {
+    let mut collapsed = String::with_capacity(s.len());
+    let mut in_whitespace = false;
+    for c in s.chars() {
+        match c.is_whitespace() {
+            true if in_whitespace => {}
+            true => collapsed.push(' '),
+            false => collapsed.push(c),
+        }
+        in_whitespace = c.is_whitespace();
+    }
+    collapsed
+}
+
+/// Comparing a `StringMatch` with a `&str` or `String`, in either order, compares the needle
+/// text only. The match length, case sensitivity and other options are ignored, so this can
+/// be true even when comparing the two `StringMatch` values is not.
+impl PartialEq<&str> for StringMatch {
+    fn eq(&self, other: &&str) -> bool {
+        self.text == *other
+    }
+}
+
+impl PartialEq<String> for StringMatch {
+    fn eq(&self, other: &String) -> bool {
+        &self.text == other
+    }
+}
+
+impl PartialEq<StringMatch> for &str {
+    fn eq(&self, other: &StringMatch) -> bool {
+        *self == other.text
+    }
+}
+
+impl PartialEq<StringMatch> for String {
+    fn eq(&self, other: &StringMatch) -> bool {
+        *self == other.text
+    }
+}
+
+impl AsRef<str> for StringMatch {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+/// With both `AsRef` impls in scope, a bare `as_ref()` call needs the target type to be
+/// known, e.g. `AsRef::<str>::as_ref(&sm)`. Use `text().as_bytes()` to avoid this.
+impl AsRef<[u8]> for StringMatch {
+    fn as_ref(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+}
+
+/// Return each line of the haystack along with its byte offset, or just the whole haystack
+/// if `multiline` is false. Lines are split on `'\n'`, with any trailing `'\r'` removed.
+pub(crate) fn lines(haystack: &str, multiline: bool) -> impl Iterator<Item = (usize, &str)> {
+    haystack.split(move |c| multiline && c == '\n').map(move |line| {
+        let offset = line.as_ptr() as usize - haystack.as_ptr() as usize;
+        match multiline {
+            true => (offset, line.strip_suffix('\r').unwrap_or(line)),
+            false => (offset, line),
+        }
+    })
+}
+
+/// Return the byte range of the first occurrence of `needle` in `haystack` that is preceded
+/// and followed by a word boundary.
+///
+/// If `separators` is set, a word boundary is any of those characters or the start or end of
+/// the haystack. Otherwise words are separated by spaces, or, with the `unicode` feature, by
+/// Unicode word boundaries.
+pub(crate) fn find_word(
+    needle: &str,
+    haystack: &str,
+    separators: Option<&str>,
+) -> Option<Range<usize>> {
+    #[cfg(feature = "unicode")]
+    if separators.is_none() {
+        return unicode::find_unicode_word(needle, haystack);
+    }
+    find_separated_word(needle, haystack, separators, true, true)
+}
+
+/// Find `needle` in `haystack` surrounded by separators, which are spaces if `separators` is
+/// `None`.
+///
+/// The start and end of the haystack only count as separators if `start_is_separator` and
+/// `end_is_separator` are true. They are false when the haystack is part of a longer text, so
+/// that a word at that edge might continue beyond it.
+pub(crate) fn find_separated_word(
+    needle: &str,
+    haystack: &str,
+    separators: Option<&str>,
+    start_is_separator: bool,
+    end_is_separator: bool,
+) -> Option<Range<usize>> {
+    let is_separator = |c: char| match separators {
+        Some(seps) => seps.contains(c),
+        None => c == ' ',
+    };
+
+    let mut pos = 0;
+    while let Some(offset) = str::find(&haystack[pos..], needle) {
+        let start = pos + offset;
+        let end = start + needle.len();
+        let before = haystack[..start].chars().next_back().map_or(start_is_separator, is_separator);
+        let after = haystack[end..].chars().next().map_or(end_is_separator, is_separator);
+        if before && after {
+            return Some(start..end);
+        }
+        pos = start + haystack[start..].chars().next()?.len_utf8();
+    }
+    None
+}
+
+fn needle_in_haystack(
+    needle: &str,
+    haystack: &str,
+    match_length: &StringMatchLength,
+    separators: Option<&str>,
+) -> bool {
+    match match_length {
+        StringMatchLength::Full => haystack == needle,
+        StringMatchLength::Partial => haystack.contains(needle),
+        StringMatchLength::Word => find_word(needle, haystack, separators).is_some(),
+        StringMatchLength::Prefix => haystack.starts_with(needle),
+        StringMatchLength::Suffix => haystack.ends_with(needle),
+    }
+}
+
+impl Needle for StringMatch {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.is_match_compiled(&self.compiled_text(), haystack)
+    }
+
+    /// `Full`, `Prefix` and `Suffix` are 1, `Partial` is 3 and `Word` is 5, plus 1 for a
+    /// case-insensitive match and 1 for each of the other options that transform the
+    /// haystack.
+    fn complexity_estimate(&self) -> u32 {
+        let base = match self.match_length {
+            StringMatchLength::Full | StringMatchLength::Prefix | StringMatchLength::Suffix => 1,
+            StringMatchLength::Partial => 3,
+            StringMatchLength::Word => 5,
+        };
+        let options = [
+            !self.case_sensitive,
+            self.normalize_whitespace || self.collapse_whitespace,
+            self.trim || self.trim_end,
+            self.is_diacritic_insensitive(),
+            self.is_unicode_normalized(),
+            self.multiline,
+            self.ignore_punctuation,
+            self.ignore_numbers,
+            self.wildcard,
+        ];
+        base + options.iter().filter(|&&o| o).count() as u32
+    }
+}
+
+impl NeedleIter for StringMatch {}
+
+#[cfg(feature = "std")]
+impl Needle for Regex {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.is_match(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        10
+    }
+}
+
+#[cfg(feature = "std")]
+impl NeedleIter for Regex {}
+
+impl Needle for &str {
+    fn is_match(&self, haystack: &str) -> bool {
+        self == &haystack
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        1
+    }
+}
+
+impl NeedleIter for &str {}
+
+impl Needle for String {
+    fn is_match(&self, haystack: &str) -> bool {
+        self == haystack
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        1
+    }
+}
+
+impl NeedleIter for String {}
+
+/// A `char` needle matches a haystack consisting of exactly that one character.
+///
+/// The comparison is by Unicode scalar value, so the haystack `"é"` (U+00E9) matches `'é'`,
+/// while the decomposed form `"e\u{301}"` is two characters and matches neither `'e'` nor
+/// `'é'`, even though it renders as a single grapheme.
+impl Needle for char {
+    fn is_match(&self, haystack: &str) -> bool {
+        let mut chars = haystack.chars();
+        chars.next() == Some(*self) && chars.next().is_none()
+    }
+}
+
+impl Needle for Cow<'_, str> {
+    fn is_match(&self, haystack: &str) -> bool {
+        self == haystack
+    }
+}
+
+impl NeedleIter for Cow<'_, str> {}
+
+impl Needle for Arc<str> {
+    fn is_match(&self, haystack: &str) -> bool {
+        &**self == haystack
+    }
+}
+
+impl NeedleIter for Arc<str> {}
+
+impl Needle for Rc<str> {
+    fn is_match(&self, haystack: &str) -> bool {
+        &**self == haystack
+    }
+}
+
+impl NeedleIter for Rc<str> {}
+
+impl Needle for Box<str> {
+    fn is_match(&self, haystack: &str) -> bool {
+        &**self == haystack
+    }
+}
+
+impl NeedleIter for Box<str> {}
+
+impl<F> Needle for F
+where
+    F: Fn(&str) -> bool,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        self(haystack)
+    }
+}
+
+impl<F> NeedleIter for F where F: Fn(&str) -> bool {}
+
+/// Shorthand for building a `StringMatch` from a string-like value, e.g. `"a".match_partial()`.
+///
+/// This is implemented for `String`, `&str`, `Box<str>` and `Cow<'static, str>`. `Arc<str>`
+/// is not supported, because `From<Arc<str>>` for `StringMatch` would overlap with the
+/// existing `From<S: Into<String>>` impl; use `StringMatch::new(arc.to_string())` instead.
+pub trait StringMatchable: Into<StringMatch> {
+    fn match_case_sensitive(self) -> StringMatch {
+        self.into().case_sensitive()
+    }
+
+    fn match_case_insensitive(self) -> StringMatch {
+        self.into().case_insensitive()
+    }
+
+    fn match_full(self) -> StringMatch {
+        self.into().full()
+    }
+
+    fn match_word(self) -> StringMatch {
+        self.into().word()
+    }
+
+    fn match_partial(self) -> StringMatch {
+        self.into().partial()
+    }
+
+    fn match_prefix(self) -> StringMatch {
+        self.into().prefix()
+    }
+
+    fn match_suffix(self) -> StringMatch {
+        self.into().suffix()
+    }
+}
+
+impl StringMatchable for String {}
+impl StringMatchable for &str {}
+impl StringMatchable for Box<str> {}
+impl StringMatchable for Cow<'static, str> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stringmatch() {
+        assert!(StringMatch::from("a").is_full_match());
+        assert!(!StringMatch::from("a").is_partial_match());
+        assert!(StringMatch::from("a").is_case_sensitive());
+        assert!(StringMatch::from("a").is_match("a"));
+        assert!(!StringMatch::from("a").is_match(""));
+        assert!(!StringMatch::from("a").is_match("b"));
+        assert!(!StringMatch::from("a").is_match("A"));
+
+        assert!(StringMatch::from("a").partial().is_partial_match());
+        assert!(!StringMatch::from("a").partial().is_full_match());
+        assert!(!StringMatch::from("a").partial().is_word_match());
+        assert!(StringMatch::from("a").partial().is_case_sensitive());
+        assert!(StringMatch::from("a").partial().is_match("a"));
+        assert!(StringMatch::from("a").partial().is_match("aa"));
+        assert!(StringMatch::from("a").partial().is_match("dad"));
+        assert!(StringMatch::from("a").partial().is_match("ba"));
+        assert!(!StringMatch::from("A").partial().is_match("a"));
+        assert!(!StringMatch::from("a").partial().is_match("A"));
+
+        assert!(!StringMatch::from("a").case_insensitive().is_case_sensitive());
+        assert!(!StringMatch::from("a").case_insensitive().is_partial_match());
+        assert!(StringMatch::from("a").case_insensitive().is_match("a"));
+        assert!(StringMatch::from("a").case_insensitive().is_match("A"));
+        assert!(!StringMatch::from("a").case_insensitive().is_match("aa"));
+
+        assert!(StringMatch::from("a").partial().case_insensitive().is_partial_match());
+        assert!(!StringMatch::from("a").partial().case_insensitive().is_case_sensitive());
+        assert!(StringMatch::from("a").partial().case_insensitive().is_match("a"));
+        assert!(StringMatch::from("a").partial().case_insensitive().is_match("aa"));
+        assert!(StringMatch::from("a").partial().case_insensitive().is_match("A"));
+        assert!(StringMatch::from("a").partial().case_insensitive().is_match("AA"));
+        assert!(StringMatch::from("aA").partial().case_insensitive().is_match("Aa"));
+        assert!(StringMatch::from("aA").partial().case_insensitive().is_match("Aaa"));
 
         assert!(StringMatch::from("a").word().is_word_match());
         assert!(!StringMatch::from("a").word().is_partial_match());
@@ -234,22 +1527,826 @@ mod tests {
         assert!(StringMatch::from("aaa aa").word().case_insensitive().is_match("aa aaa aa"));
         assert!(!StringMatch::from("aaa aa").word().case_insensitive().is_match("aa aaa aaa"));
 
-        assert!(StringMatch::from("a").word().case_insensitive().is_word_match());
-        assert!(!StringMatch::from("a").word().case_insensitive().is_case_sensitive());
-        assert!(StringMatch::from("a").word().case_insensitive().is_match("a"));
-        assert!(!StringMatch::from("a").word().case_insensitive().is_match("aa"));
-        assert!(StringMatch::from("a").word().case_insensitive().is_match("A"));
-        assert!(StringMatch::from("a").word().case_insensitive().is_match("AA A AA"));
-        assert!(StringMatch::from("aA").word().case_insensitive().is_match("Aa"));
-        assert!(StringMatch::from("aA").word().case_insensitive().is_match("aa"));
-        assert!(StringMatch::from("A").word().case_insensitive().is_match("aa a aa"));
-        assert!(StringMatch::from("A").word().case_insensitive().is_match("a aa"));
-        assert!(StringMatch::from("A").word().case_insensitive().is_match("aa a"));
-        assert!(StringMatch::from("AAA AA").word().case_insensitive().is_match("aaa aa"));
-        assert!(StringMatch::from("AAA AA").word().case_insensitive().is_match("aa aaa aa aaa"));
-        assert!(StringMatch::from("AAA AA").word().case_insensitive().is_match("aaa aa aaa"));
-        assert!(StringMatch::from("AAA AA").word().case_insensitive().is_match("aa aaa aa"));
-        assert!(!StringMatch::from("AAA AA").word().case_insensitive().is_match("aa aaa aaa"));
+        assert!(StringMatch::from("a").word().case_insensitive().is_word_match());
+        assert!(!StringMatch::from("a").word().case_insensitive().is_case_sensitive());
+        assert!(StringMatch::from("a").word().case_insensitive().is_match("a"));
+        assert!(!StringMatch::from("a").word().case_insensitive().is_match("aa"));
+        assert!(StringMatch::from("a").word().case_insensitive().is_match("A"));
+        assert!(StringMatch::from("a").word().case_insensitive().is_match("AA A AA"));
+        assert!(StringMatch::from("aA").word().case_insensitive().is_match("Aa"));
+        assert!(StringMatch::from("aA").word().case_insensitive().is_match("aa"));
+        assert!(StringMatch::from("A").word().case_insensitive().is_match("aa a aa"));
+        assert!(StringMatch::from("A").word().case_insensitive().is_match("a aa"));
+        assert!(StringMatch::from("A").word().case_insensitive().is_match("aa a"));
+        assert!(StringMatch::from("AAA AA").word().case_insensitive().is_match("aaa aa"));
+        assert!(StringMatch::from("AAA AA").word().case_insensitive().is_match("aa aaa aa aaa"));
+        assert!(StringMatch::from("AAA AA").word().case_insensitive().is_match("aaa aa aaa"));
+        assert!(StringMatch::from("AAA AA").word().case_insensitive().is_match("aa aaa aa"));
+        assert!(!StringMatch::from("AAA AA").word().case_insensitive().is_match("aa aaa aaa"));
+    }
+
+    #[test]
+    fn test_word_with_separators() {
+        let sm = StringMatch::from("b").word_with_separators("\t");
+        assert!(sm.is_word_match());
+        assert_eq!(sm.separators(), Some("\t"));
+        assert_eq!(StringMatch::from("b").word().separators(), None);
+        assert!(sm.is_match("a\tb\tc"));
+        assert!(sm.is_match("b"));
+        assert!(sm.is_match("b\t"));
+        assert!(!sm.is_match("a b c")); // Spaces are no longer separators.
+        assert!(!sm.is_match("a\tbb\tc"));
+
+        let sm = StringMatch::from("b c").word_with_separators(",");
+        assert!(sm.is_match("a,b c,d"));
+        assert!(!sm.is_match("a,b,c,d"));
+
+        let sm = StringMatch::from("Bar").word_with_separators(",;\t ").case_insensitive();
+        assert!(sm.is_match("foo,bar"));
+        assert!(sm.is_match("foo;BAR;baz"));
+        assert!(sm.is_match("foo\tbar baz"));
+        assert!(sm.is_match("foo,barbar;bar"));
+        assert!(!sm.is_match("foo,barbaz"));
+        assert!(!sm.is_match("foo-bar"));
+
+        // Check multi-byte separators and needles.
+        let sm = StringMatch::from("é").word_with_separators("·");
+        assert!(sm.is_match("a·é·b"));
+        assert!(!sm.is_match("a·éé·b"));
+    }
+
+    #[test]
+    fn test_whitespace_options() {
+        let sm = StringMatch::from("hello world").normalize_whitespace();
+        assert!(sm.is_whitespace_normalized());
+        assert!(!sm.is_trimmed());
+        assert!(sm.is_match("hello world"));
+        assert!(sm.is_match("hello   world"));
+        assert!(sm.is_match("hello\t\n world"));
+        assert!(!sm.is_match(" hello world"));
+        assert!(!StringMatch::from("hello world").is_match("hello  world"));
+        // The needle is normalized too.
+        assert!(StringMatch::from("hello \t world").normalize_whitespace().is_match("hello world"));
+
+        let sm = StringMatch::from("hello").trim();
+        assert!(sm.is_trimmed());
+        assert!(sm.is_match("  hello\n"));
+        assert!(!sm.is_match("  hello there"));
+        // Only the haystack is trimmed.
+        assert!(!StringMatch::from(" hello").trim().is_match(" hello"));
+
+        let sm = StringMatch::from("hello").trim_before_match();
+        assert!(sm.is_trimmed());
+        assert!(sm.is_match("  hello  "));
+        assert!(!StringMatch::from("hello").is_match("  hello  "));
+
+        let sm = StringMatch::from("hello").trim_end_before_match();
+        assert!(sm.is_end_trimmed());
+        assert!(!sm.is_trimmed());
+        assert!(sm.is_match("hello \r\n"));
+        assert!(!sm.is_match("  hello  "));
+        assert!(sm.clone().suffix().is_match("say hello\t"));
+        assert!(sm.normalize_whitespace().is_match("hello\t \n"));
+
+        // Trimming happens before looking for word boundaries.
+        let sm = StringMatch::from("end").word_with_separators(",").trim_end_before_match();
+        assert!(sm.is_match("start,end  "));
+        assert!(!StringMatch::from("end").word_with_separators(",").is_match("start,end  "));
+
+        // Partial mode with leading spaces in the needle.
+        let sm = StringMatch::from(" world").partial();
+        assert!(sm.is_match("hello world"));
+        assert!(!sm.clone().trim().is_match(" world"));
+        assert!(sm.clone().normalize_whitespace().is_match("hello\t\tworld"));
+
+        // Word mode relies on spaces, so normalizing other whitespace to spaces helps.
+        let sm = StringMatch::from("world").word().normalize_whitespace();
+        assert!(sm.is_match("hello\tworld"));
+        assert!(sm.is_match("hello\u{a0}world"));
+        #[cfg(not(feature = "unicode"))]
+        assert!(!StringMatch::from("world").word().is_match("hello\tworld"));
+
+        // Both options together, combined with case and match length.
+        let sm = StringMatch::from("Hello World").trim().normalize_whitespace();
+        assert!(sm.is_match("\t Hello \n World  "));
+        assert!(!sm.is_match("\t hello \n world  "));
+        assert!(sm.clone().case_insensitive().is_match("\t hello \n world  "));
+        assert!(sm.clone().prefix().is_match("  Hello  World  and more"));
+        assert!(sm.clone().suffix().is_match("And then,  Hello\tWorld\n"));
+        assert!(!sm.clone().full().is_match("And then,  Hello\tWorld\n"));
+
+        let debug = format!("{:?}", sm);
+        assert!(debug.contains("normalize_whitespace: true"));
+        assert!(debug.contains("trim: true"));
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        let sm = StringMatch::from("hello world").collapse_whitespace();
+        assert!(sm.is_whitespace_collapsed());
+        assert!(!sm.is_whitespace_normalized());
+        assert!(sm.is_match("  hello   world  "));
+        assert!(sm.is_match("\n\thello\n\n world\r\n"));
+        assert!(!sm.is_match("helloworld"));
+        assert!(!StringMatch::from("hello world").normalize_whitespace().is_match(" hello world"));
+
+        // The needle is collapsed and stripped too.
+        let sm = StringMatch::from("  hello \t world\n").collapse_whitespace();
+        assert_eq!(sm.canonical_text(), "hello world");
+        assert!(sm.is_match("hello world"));
+        assert!(sm.is_match(" hello  world "));
+        assert!(sm.clone().prefix().is_match(" hello  world and more"));
+        assert!(sm.clone().suffix().is_match("say  hello world\n"));
+        assert!(sm.clone().partial().is_match("<p>say hello\n   world</p>"));
+
+        // Word boundaries are found after collapsing.
+        let sm = StringMatch::from("world").word().collapse_whitespace();
+        assert!(sm.is_match("hello\t\tworld  "));
+        assert!(sm.is_match("  world"));
+        assert!(!sm.is_match("hello worlds"));
+        let sm = StringMatch::from(" big  world ").word().collapse_whitespace();
+        assert!(sm.is_match("hello   big\nworld"));
+        assert!(!sm.is_match("hello bigworld"));
+
+        // Collapsing is idempotent.
+        for text in ["", " ", "  a  ", "a \t\n b", "\u{a0}a\u{2003} b "] {
+            let once = StringMatch::from(text).collapse_whitespace().canonical_text();
+            let twice = StringMatch::from(once.as_str()).collapse_whitespace().canonical_text();
+            assert_eq!(once, twice, "{:?}", text);
+            assert!(StringMatch::from(once.as_str()).is_match(&once));
+            assert!(StringMatch::from(text).collapse_whitespace().is_match(&once));
+            assert!(StringMatch::from(once.as_str()).collapse_whitespace().is_match(text));
+        }
+
+        // Matches are found in the original haystack.
+        let sm = StringMatch::from("hello world").partial().collapse_whitespace();
+        let span = sm.find("  say hello \n world  ").unwrap();
+        assert_eq!(span.byte_range(), 6..19);
+        assert_eq!(sm.replace_all(" hello  world ", "hi"), " hi ");
+        let sm = StringMatch::from("").suffix().collapse_whitespace();
+        assert_eq!(sm.find("ab  ").unwrap().byte_range(), 2..2);
+    }
+
+    #[test]
+    fn test_line_by_line() {
+        let sm = StringMatch::from("hello").line_by_line();
+        assert!(sm.is_line_by_line());
+        assert!(!StringMatch::from("hello").is_line_by_line());
+        assert!(sm.is_match("hello"));
+        assert!(sm.is_match("first\nsecond\nhello\nfourth"));
+        assert!(!StringMatch::from("hello").is_match("first\nsecond\nhello"));
+        assert!(!sm.is_match("first\nhello there"));
+        assert!(sm.is_match("a\r\nhello\r\nb"));
+        assert!(sm.is_match("hello\r\n"));
+        assert!(!sm.is_match(""));
+
+        // Empty lines.
+        let empty = StringMatch::from("").line_by_line();
+        assert!(empty.is_match(""));
+        assert!(empty.is_match("a\n\nb"));
+        assert!(empty.is_match("a\r\n\r\nb"));
+        assert!(!empty.is_match("a\nb"));
+
+        // Other modes apply to each line.
+        assert!(StringMatch::from("ell").partial().line_by_line().is_match("x\nhello"));
+        assert!(StringMatch::from("b").word().line_by_line().is_match("a\nb c"));
+        assert!(StringMatch::from("b").prefix().line_by_line().is_match("ab\nba"));
+        assert!(!StringMatch::from("b").prefix().line_by_line().is_match("ab\nab"));
+        assert!(StringMatch::from("a").suffix().line_by_line().is_match("ba\nbb"));
+        assert!(!StringMatch::from("a").suffix().is_match("ba\nbb"));
+
+        let ci = StringMatch::from("HELLO").line_by_line().case_insensitive();
+        assert!(ci.is_match("one\ntwo\nHello"));
+        assert!(!ci.clone().case_sensitive().is_match("one\ntwo\nHello"));
+        assert!(StringMatch::from("hello").trim().line_by_line().is_match("x\n  hello  \ny"));
+        assert!(StringMatch::from("hello").line_by_line().compile().is_match("a\nhello"));
+    }
+
+    #[test]
+    fn test_stringmatch_prefix_suffix() {
+        assert!(StringMatch::from("ab").prefix().is_prefix_match());
+        assert!(!StringMatch::from("ab").prefix().is_suffix_match());
+        assert!(!StringMatch::from("ab").prefix().is_full_match());
+        assert!(StringMatch::from("ab").prefix().is_case_sensitive());
+        assert!(StringMatch::from("ab").prefix().is_match("ab"));
+        assert!(StringMatch::from("ab").prefix().is_match("abc"));
+        assert!(!StringMatch::from("ab").prefix().is_match("cab"));
+        assert!(!StringMatch::from("ab").prefix().is_match("a"));
+        assert!(!StringMatch::from("ab").prefix().is_match(""));
+        assert!(!StringMatch::from("ab").prefix().is_match("ABc"));
+        assert!(StringMatch::from("").prefix().is_match(""));
+        assert!(StringMatch::from("").prefix().is_match("abc"));
+        assert!(StringMatch::from("ab").prefix().case_insensitive().is_match("ABc"));
+        assert!(StringMatch::from("AB").prefix().case_insensitive().is_match("abc"));
+        assert!(!StringMatch::from("AB").prefix().case_insensitive().is_match("cab"));
+
+        assert!(StringMatch::from("bc").suffix().is_suffix_match());
+        assert!(!StringMatch::from("bc").suffix().is_prefix_match());
+        assert!(!StringMatch::from("bc").suffix().is_partial_match());
+        assert!(StringMatch::from("bc").suffix().is_case_sensitive());
+        assert!(StringMatch::from("bc").suffix().is_match("bc"));
+        assert!(StringMatch::from("bc").suffix().is_match("abc"));
+        assert!(!StringMatch::from("bc").suffix().is_match("bca"));
+        assert!(!StringMatch::from("bc").suffix().is_match("c"));
+        assert!(!StringMatch::from("bc").suffix().is_match(""));
+        assert!(!StringMatch::from("bc").suffix().is_match("aBC"));
+        assert!(StringMatch::from("").suffix().is_match(""));
+        assert!(StringMatch::from("").suffix().is_match("abc"));
+        assert!(StringMatch::from("bc").suffix().case_insensitive().is_match("aBC"));
+        assert!(StringMatch::from("BC").suffix().case_insensitive().is_match("abc"));
+        assert!(!StringMatch::from("BC").suffix().case_insensitive().is_match("bca"));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let sm = StringMatch::new("Test");
+        assert_eq!(sm.text(), "Test");
+        assert_eq!(sm.match_length(), &StringMatchLength::Full);
+        assert!(sm.is_case_sensitive());
+        assert_eq!(AsRef::<str>::as_ref(&sm), "Test");
+
+        let sm = StringMatch::new("Test").word().case_insensitive();
+        assert_eq!(sm.text(), "Test");
+        assert_eq!(sm.match_length(), &StringMatchLength::Word);
+        assert!(!sm.is_case_sensitive());
+        assert_eq!(StringMatch::new(sm.text()).word().case_insensitive(), sm);
+
+        // Comparisons against strings ignore the mode flags.
+        assert_eq!(StringMatch::new("Test"), "Test");
+        assert_eq!(StringMatch::new("Test").partial().case_insensitive(), "Test");
+        assert_eq!(StringMatch::new("Test").suffix(), String::from("Test"));
+        assert_ne!(StringMatch::new("Test"), "test");
+        assert_ne!(StringMatch::new("Test").case_insensitive(), String::from("test"));
+
+        // And in the other direction.
+        assert_eq!("Test", StringMatch::new("Test").word());
+        assert_eq!(String::from("Test"), StringMatch::new("Test").prefix());
+        assert_ne!("test", StringMatch::new("Test"));
+        assert_ne!(String::from("test"), StringMatch::new("Test").case_insensitive());
+
+        // Comparing two needles also compares the mode flags.
+        let full = StringMatch::new("Test");
+        let partial = StringMatch::new("Test").partial();
+        assert_eq!(full, partial.text());
+        assert_eq!(partial.text(), full);
+        assert_ne!(full, partial);
+    }
+
+    #[test]
+    fn test_as_ref() {
+        let sm = StringMatch::new("dir");
+        assert_eq!(
+            std::path::Path::new("").join(AsRef::<str>::as_ref(&sm)),
+            std::path::Path::new("dir")
+        );
+        let bytes: &[u8] = sm.as_ref();
+        assert_eq!(bytes, b"dir");
+    }
+
+    #[test]
+    fn test_complexity_estimate() {
+        let sm = StringMatch::from("test");
+        let ordered: [&dyn Needle; 8] = [
+            &"test",
+            &sm,
+            &sm.clone().case_insensitive(),
+            &sm.clone().partial(),
+            &sm.clone().partial().case_insensitive(),
+            &sm.clone().word(),
+            &sm.clone().word().case_insensitive(),
+            &|s: &str| s.is_empty(),
+        ];
+        for pair in ordered.windows(2) {
+            assert!(pair[0].complexity_estimate() <= pair[1].complexity_estimate());
+        }
+        assert!(sm.complexity_estimate() < sm.clone().partial().complexity_estimate());
+        assert!(
+            sm.clone().partial().complexity_estimate() < sm.clone().word().complexity_estimate()
+        );
+        assert!(sm.complexity_estimate() < sm.clone().case_insensitive().complexity_estimate());
+        assert!(sm.complexity_estimate() < sm.clone().trim().complexity_estimate());
+        assert_eq!(sm.complexity_estimate(), sm.clone().compile().complexity_estimate());
+        assert_eq!(sm.complexity_estimate(), StringMatch::from("other").complexity_estimate());
+
+        #[cfg(feature = "std")]
+        {
+            let re = Regex::new("^t").unwrap();
+            assert!(sm.clone().word().complexity_estimate() < re.complexity_estimate());
+            assert!(re.complexity_estimate() < (|_: &str| true).complexity_estimate());
+        }
+
+        let combined = "a".or(sm.clone().partial()).and(sm.clone().not());
+        assert!(combined.complexity_estimate() > sm.clone().partial().complexity_estimate());
+        assert!(AlwaysNeedle.complexity_estimate() < "a".complexity_estimate());
+
+        let mut needles: Vec<BoxedNeedle> = vec![
+            Box::new(|s: &str| s.len() > 3),
+            Box::new(sm.clone().word()),
+            Box::new(String::from("test")),
+        ];
+        needles.sort_by_key(|n| n.complexity_estimate());
+        assert_eq!(needles[0].complexity_estimate(), 1);
+        assert!(needles[2].is_match("long"));
+    }
+
+    #[test]
+    fn test_is_stricter_than() {
+        use StringMatchLength::*;
+
+        let lengths = [Full, Partial, Word, Prefix, Suffix];
+        let haystacks = ["foo", "Foo", "foo bar", "a foo", "a foo b", "xfoo", "foox", "", "bar"];
+        for a in &lengths {
+            for b in &lengths {
+                let expected = match (a, b) {
+                    (a, b) if a == b => true,
+                    (Full, _) => true,
+                    (Prefix | Suffix, Partial) => true,
+                    (Word, Partial) => !cfg!(feature = "unicode"),
+                    _ => false,
+                };
+                let sm_a = StringMatch::from("foo").with_match_length(a.clone());
+                let sm_b = StringMatch::from("foo").with_match_length(b.clone());
+                assert_eq!(sm_a.is_stricter_than(&sm_b), expected, "{a:?} {b:?}");
+                assert_eq!(sm_b.subsumes(&sm_a), expected, "{a:?} {b:?}");
+
+                // Case sensitive is stricter than case insensitive, but not the reverse.
+                let ci_b = sm_b.clone().case_insensitive();
+                assert_eq!(sm_a.is_stricter_than(&ci_b), expected, "{a:?} {b:?}");
+                assert!(!ci_b.is_stricter_than(&sm_a) || a != b);
+                assert!(!ci_b.is_stricter_than(&sm_b));
+                assert!(ci_b.is_stricter_than(&ci_b));
+
+                if expected {
+                    for haystack in haystacks {
+                        assert!(!sm_a.is_match(haystack) || ci_b.is_match(haystack));
+                    }
+                }
+            }
+        }
+
+        let sm = StringMatch::from("foo").word_with_separators(",");
+        assert!(sm.is_stricter_than(&sm.clone().partial()));
+        assert!(!sm.is_stricter_than(&StringMatch::from("foo").partial()));
+        assert!(!StringMatch::from("foo").is_stricter_than(&StringMatch::from("bar").partial()));
+        assert!(!StringMatch::from("foo").is_stricter_than(&StringMatch::from("fo").partial()));
+        assert!(!StringMatch::from("foo").trim().is_stricter_than(&StringMatch::from("foo")));
+
+        #[cfg(feature = "unicode")]
+        {
+            let sm = StringMatch::from("!").word();
+            assert!(sm.is_match("?") && !sm.clone().partial().is_match("?"));
+            assert!(!sm.is_stricter_than(&sm.clone().partial()));
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let base = StringMatch::from("Hello World");
+        let mut needles = vec![
+            StringMatch::default(),
+            base.clone(),
+            base.clone().partial(),
+            base.clone().word(),
+            base.clone().word_with_separators(",;"),
+            base.clone().word_with_separators("").partial(),
+            base.clone().prefix(),
+            base.clone().suffix(),
+            base.clone().case_insensitive(),
+            base.clone().normalize_whitespace(),
+            base.clone().trim(),
+            base.clone().trim_end_before_match(),
+            base.clone().line_by_line(),
+            base.clone().min_length(3),
+            base.clone().max_length(3),
+            base.clone().min_length(3).max_length(3),
+            base.clone().ascii_only(),
+            base.clone().ignore_punctuation().ignore_numbers(),
+            base.clone().wildcard().collapse_whitespace(),
+            base.map_text(|_| String::new()),
+        ];
+        #[cfg(feature = "unicode")]
+        needles.push(StringMatch::from("café").strip_diacritics());
+        needles.push(StringMatch::try_new("x").unwrap().partial().case_insensitive());
+        for sm in &needles {
+            assert!(sm.validate().is_ok(), "{sm:?}");
+            assert!(sm.is_valid(), "{sm:?}");
+        }
+
+        let sm = StringMatch::from("a").min_length(5).max_length(2);
+        assert!(!sm.is_valid());
+        assert!(matches!(
+            sm.validate(),
+            Err(StringMatchError::InvalidLengthBounds {
+                min: 5,
+                max: 2
+            })
+        ));
+        assert_eq!(
+            sm.validate().unwrap_err().to_string(),
+            "minimum length 5 is greater than maximum length 2"
+        );
+        let sm = StringMatch::from("a").word_with_separators("");
+        assert!(!sm.is_valid());
+        assert!(matches!(sm.validate(), Err(StringMatchError::EmptySeparators)));
+    }
+
+    #[test]
+    fn test_is_equivalent_to() {
+        let sm = StringMatch::from("Hello").case_insensitive();
+        assert!(sm.is_equivalent_to(&StringMatch::from("hello").case_insensitive()));
+        assert!(sm.is_equivalent_to(&StringMatch::from("HELLO").case_insensitive()));
+        assert!(sm.is_equivalent_to(&sm));
+        assert!(!sm.is_equivalent_to(&StringMatch::from("hello")));
+        assert!(!StringMatch::from("Hello").is_equivalent_to(&StringMatch::from("hello")));
+        assert!(!sm.is_equivalent_to(&StringMatch::from("hello").case_insensitive().partial()));
+        assert!(!StringMatch::from("a").is_equivalent_to(&StringMatch::from("a").word()));
+        assert!(!StringMatch::from("a")
+            .partial()
+            .is_equivalent_to(&StringMatch::from("b").partial()));
+
+        let sm = StringMatch::from("a  b").normalize_whitespace();
+        assert!(sm.is_equivalent_to(&StringMatch::from("a\tb").normalize_whitespace()));
+        assert!(!sm.is_equivalent_to(&StringMatch::from("a b")));
+    }
+
+    #[test]
+    fn test_canonical_form() {
+        assert_eq!(StringMatch::from("Hello").canonical_text(), "Hello");
+        assert_eq!(StringMatch::from("Hello").case_insensitive().canonical_text(), "hello");
+        assert_eq!(StringMatch::from("a \t b").normalize_whitespace().canonical_text(), "a b");
+        assert_eq!(
+            StringMatch::from("Hello").case_insensitive().canonical_form(),
+            StringMatch::from("hello").case_insensitive()
+        );
+        let forced =
+            StringMatch::from("Hello").case_insensitive().canonical_form().case_sensitive();
+        assert!(forced.is_match("hello") && !forced.is_match("HELLO"));
+
+        let haystacks = ["", "hello", "Hello", "HELLO", "hello world", "say HELLO", "a  b", "a b"];
+        let needles = [
+            StringMatch::from("Hello"),
+            StringMatch::from("HeLLo").case_insensitive(),
+            StringMatch::from("HeLLo").case_insensitive().partial(),
+            StringMatch::from("HeLLo").case_insensitive().word(),
+            StringMatch::from("A  B").case_insensitive().normalize_whitespace(),
+        ];
+        for sm in &needles {
+            let canonical = sm.canonical_form();
+            assert!(canonical.is_equivalent_to(sm));
+            assert_eq!(canonical.canonical_form(), canonical);
+            for haystack in haystacks {
+                assert_eq!(
+                    canonical.is_match(haystack),
+                    sm.is_match(haystack),
+                    "{sm:?} {haystack:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_concat() {
+        let sm = StringMatch::new("foo").partial().concat(StringMatch::new("bar").partial());
+        assert_eq!(sm, StringMatch::new("foobar").partial());
+        assert!(sm.is_match("a foobar b"));
+        assert!(!sm.is_match("foo bar"));
+        assert!(!sm.is_match("barfoo"));
+
+        let sm = StringMatch::new("foo").concat(StringMatch::new("bar"));
+        assert_eq!(sm, StringMatch::new("foobar"));
+        assert!(sm.is_match("foobar"));
+        assert!(!sm.is_match("foobar!"));
+
+        // Mixed modes keep the anchoring of the outer ends.
+        let full = || StringMatch::new("foo");
+        let partial = || StringMatch::new("bar").partial();
+        assert_eq!(full().concat(partial()).match_length(), &StringMatchLength::Prefix);
+        assert!(full().concat(partial()).is_match("foobar and more"));
+        assert!(!full().concat(partial()).is_match("a foobar"));
+        assert_eq!(partial().concat(full()).match_length(), &StringMatchLength::Suffix);
+        assert!(partial().concat(full()).is_match("a barfoo"));
+        assert!(!partial().concat(full()).is_match("barfoo b"));
+        let sm = StringMatch::new("foo").prefix().concat(StringMatch::new("bar").suffix());
+        assert_eq!(sm.match_length(), &StringMatchLength::Full);
+        let sm = StringMatch::new("foo").suffix().concat(StringMatch::new("bar").prefix());
+        assert_eq!(sm.match_length(), &StringMatchLength::Partial);
+        let sm = StringMatch::new("foo").word().concat(full());
+        assert_eq!(sm, StringMatch::new("foofoo").word());
+
+        // The result is case-insensitive if either needle is.
+        let ci = StringMatch::new("FOO").partial().case_insensitive();
+        let sm = ci.clone().concat(partial());
+        assert!(!sm.is_case_sensitive());
+        assert!(sm.is_match("xfooBARx"));
+        let sm = partial().concat(ci.clone());
+        assert!(!sm.is_case_sensitive());
+        assert!(sm.is_match("BARfoo"));
+        assert!(partial().concat(partial()).is_case_sensitive());
+        assert!(!ci.clone().concat(ci).is_match("foo"));
+
+        // Other options come from the first needle.
+        let sm = StringMatch::new("a ").partial().normalize_whitespace().concat(partial());
+        assert!(sm.is_match("a \t bar"));
+    }
+
+    #[test]
+    fn test_length_constraints() {
+        let sm = StringMatch::from("ab").partial().min_length(3).max_length(5);
+        assert_eq!(sm.length_bounds(), (Some(3), Some(5)));
+        assert_eq!(StringMatch::from("ab").length_bounds(), (None, None));
+        assert!(!sm.is_match("ab"));
+        assert!(sm.is_match("abc"));
+        assert!(sm.is_match("xxabx"));
+        assert!(!sm.is_match("xxxabx"));
+        assert!(!sm.is_match("xyz"));
+
+        // Lengths are in characters, not bytes.
+        let sm = StringMatch::from("é").partial().max_length(2);
+        assert!(sm.is_match("éé"));
+        assert!(!sm.is_match("ééé"));
+        assert!(StringMatch::from("é").min_length(1).max_length(1).is_match("é"));
+
+        let full = StringMatch::from("abc").min_length(3).max_length(3);
+        assert!(full.is_match("abc"));
+        assert!(!StringMatch::from("abc").min_length(4).is_match("abc"));
+        assert!(!StringMatch::from("abc").max_length(2).is_match("abc"));
+
+        let word = StringMatch::from("cat").word().min_length(4);
+        assert!(!word.is_match("cat"));
+        assert!(word.is_match("a cat"));
+        assert!(!word.clone().max_length(6).is_match("the cat"));
+        assert!(word.max_length(7).is_match("the cat"));
+
+        // The whole haystack is measured, before trimming.
+        assert!(!StringMatch::from("ab").trim().max_length(3).is_match(" ab "));
+        assert!(StringMatch::from("ab").trim().min_length(4).is_match(" ab "));
+        assert!(!StringMatch::from("ab").line_by_line().max_length(4).is_match("ab\ncd"));
+
+        assert_eq!(sm.clone().compile().is_match("ééé"), sm.is_match("ééé"));
+        assert_eq!(StringMatch::from("a").partial().max_length(2).find("aaa"), None);
+        assert_eq!(StringMatch::from("a").partial().max_length(2).count("aa"), 2);
+        assert_eq!(StringMatch::from("a").partial().min_length(3).count("aa"), 0);
+        assert_eq!(StringMatch::from("aa").partial().min_length(3).count_overlapping("aaa"), 2);
+    }
+
+    #[test]
+    fn test_ascii_only() {
+        let sm = StringMatch::from("llo").partial().ascii_only();
+        assert!(sm.is_ascii_only());
+        assert!(!StringMatch::from("llo").is_ascii_only());
+        assert!(sm.is_match("hello"));
+        assert!(!sm.is_match("héllo"));
+        assert!(StringMatch::from("llo").partial().is_match("héllo"));
+        assert_eq!(sm.find("héllo"), None);
+        assert_eq!(sm.count("llo llo"), 2);
+
+        let sm = StringMatch::from("HELLO").case_insensitive().word().trim().ascii_only();
+        assert!(sm.is_match(" say hello "));
+        assert!(!sm.is_match(" say hello\u{a0}"));
+        assert!(sm.clone().compile().is_match(" hello "));
+        assert!(!sm.clone().compile().is_match("hello ✓"));
+
+        let cleared = sm.clone().not_ascii_only();
+        assert!(!cleared.is_ascii_only());
+        assert!(cleared.is_match("hello ✓"));
+        let mut sm = sm;
+        sm.set_ascii_only(false);
+        assert_eq!(sm, cleared);
+    }
+
+    #[test]
+    fn test_ignore_punctuation() {
+        let sm = StringMatch::from("ISBN-10").ignore_punctuation();
+        assert!(sm.is_punctuation_ignored());
+        assert!(!StringMatch::from("ISBN-10").is_punctuation_ignored());
+        assert!(sm.is_match("ISBN10"));
+        assert!(sm.is_match("ISBN 10"));
+        assert!(sm.is_match("ISBN.10"));
+        assert!(sm.is_match("ISBN -\t10"));
+        assert!(sm.is_match("I.S.B.N. 10!"));
+        assert!(sm.is_match("I.S.B.N.10!"));
+        assert!(!sm.is_match("IS BN10"));
+        assert!(!sm.is_match("ISBN x10"));
+        assert!(!StringMatch::from("ISBN-10").is_match("ISBN10"));
+        assert!(!StringMatch::from("ISBN10").ignore_punctuation().is_match("ISBN 10"));
+        assert_eq!(sm.canonical_text(), "ISBN-10");
+        assert_eq!(StringMatch::from("ISBN - 10").ignore_punctuation().canonical_text(), "ISBN-10");
+        assert_eq!(StringMatch::from(". ISBN, ").ignore_punctuation().canonical_text(), " ISBN ");
+        let sm = sm.partial().case_insensitive();
+        assert_eq!(sm.find("see isbn 10 here").map(Range::from), Some(4..11));
+        assert_eq!(sm.find("see isbn10").map(Range::from), Some(4..10));
+        assert_eq!(sm.count("ISBN10, ISBN 10, ISBN-10"), 3);
+        assert!(sm.clone().compile().is_match("an ISBN\n10"));
+        assert!(sm.clone().prefix().is_match("ISBN 10 is"));
+        assert!(!sm.clone().suffix().is_match("ISBN 10 is"));
+
+        // Whitespace is collapsed after punctuation is removed.
+        let sm = StringMatch::from("ISBN 10").ignore_punctuation().normalize_whitespace();
+        assert!(sm.is_match("ISBN - 10"));
+        assert!(sm.is_match("ISBN, 10"));
+        assert!(!sm.is_match("ISBN10"));
+
+        let sm = StringMatch::from("e.g.").partial().case_insensitive().ignore_punctuation();
+        assert!(sm.is_match("For example (EG), this"));
+        assert_eq!(sm.find("an e.g. here").map(Range::from), Some(3..7));
+        assert_eq!(sm.find("an (E)G here").map(Range::from), Some(4..7));
+        assert_eq!(sm.count("eg e-g e.g"), 3);
+        assert!(sm.clone().compile().is_match("e,g"));
+
+        // Word boundaries are found after punctuation is removed.
+        assert_eq!(StringMatch::from("foo").word().is_match("foo-bar"), cfg!(feature = "unicode"));
+        assert!(!StringMatch::from("foo").word().ignore_punctuation().is_match("foo-bar"));
+        assert!(StringMatch::from("foobar").word().ignore_punctuation().is_match("a foo-bar b"));
+        assert!(StringMatch::from("foo").word().ignore_punctuation().is_match("a foo, b"));
+        let sm = StringMatch::from("foo-bar").word().ignore_punctuation();
+        assert!(sm.is_match("a foo bar b"));
+        assert!(sm.is_match("a foobar b"));
+        assert!(!sm.is_match("a foo barb"));
+
+        // ASCII symbols are punctuation for char::is_ascii_punctuation() but not in Unicode.
+        assert_eq!(
+            StringMatch::from("a1").ignore_punctuation().is_match("a$1"),
+            !cfg!(feature = "unicode")
+        );
+        assert_eq!(
+            StringMatch::from("a1").ignore_punctuation().is_match("a\u{ab}1\u{bb}"),
+            cfg!(feature = "unicode")
+        );
+
+        // Removing punctuation is idempotent.
+        for text in ["ISBN-10", "a.b,c", "...", "", "«quoted»", "e.g. (this)"] {
+            let once = StringMatch::from(text).ignore_punctuation().canonical_text();
+            let twice = StringMatch::from(once.as_str()).ignore_punctuation().canonical_text();
+            assert_eq!(once, twice);
+            assert!(StringMatch::from(text).ignore_punctuation().is_match(&once));
+        }
+    }
+
+    #[test]
+    fn test_ignore_numbers() {
+        let sm = StringMatch::from("Python").partial().ignore_numbers();
+        assert!(sm.is_numbers_ignored());
+        assert!(!StringMatch::from("Python").is_numbers_ignored());
+        assert!(sm.is_match("Python3"));
+        assert!(sm.is_match("Pyth0on"));
+        assert!(!StringMatch::from("Python").partial().is_match("Pyth0on"));
+
+        let sm = StringMatch::from("Python3").ignore_numbers();
+        assert!(sm.is_match("Python"));
+        assert!(sm.is_match("Python2"));
+        assert!(!sm.is_match("Python 3"));
+        assert_eq!(sm.canonical_text(), "Python");
+
+        // An all-digit haystack is empty once the digits are removed.
+        let sm = StringMatch::from("").ignore_numbers();
+        assert!(sm.is_match("12345"));
+        assert!(sm.is_match(""));
+        assert!(!sm.is_match("1a"));
+        assert!(StringMatch::from("123").ignore_numbers().is_match("4"));
+        assert_eq!(
+            StringMatch::from("١٢٣").ignore_numbers().is_match(""),
+            cfg!(feature = "unicode")
+        );
+
+        // Length constraints apply to the haystack once the digits are removed.
+        let sm = StringMatch::from("Python").ignore_numbers().max_length(6);
+        assert!(sm.is_match("Python3"));
+        assert!(sm.is_match("Python312"));
+        let sm = StringMatch::from("Py").prefix().ignore_numbers().min_length(3);
+        assert!(!sm.is_match("Py123"));
+        assert!(sm.is_match("Py1t"));
+        let sm = StringMatch::from("a").partial().ignore_punctuation().max_length(2);
+        assert!(sm.is_match("a...b"));
+        assert!(!sm.is_match("a.b.c"));
+
+        let sm = StringMatch::from("v.").partial().ignore_numbers().ignore_punctuation();
+        assert_eq!(sm.find("release v1.2.3 here").map(Range::from), Some(8..14));
+        assert_eq!(sm.canonical_text(), "v");
+
+        let sm = StringMatch::from("model x").word().ignore_numbers().normalize_whitespace();
+        assert!(sm.is_match("the model 3 x"));
+        assert!(!sm.is_match("the model3x"));
+    }
+
+    #[test]
+    fn test_setters() {
+        let mut needles = [StringMatch::new("a"), StringMatch::new("b").partial()];
+        for (i, sm) in needles.iter_mut().enumerate() {
+            sm.set_text(format!("{}{}", sm.text(), i));
+            sm.set_match_length(StringMatchLength::Prefix);
+            sm.set_case_sensitive(false);
+        }
+        assert_eq!(needles[0], StringMatch::new("a0").prefix().case_insensitive());
+        assert_eq!(needles[1], StringMatch::new("b1").prefix().case_insensitive());
+
+        let orig = StringMatch::new("a").word().trim();
+        assert_eq!(orig.with_text("b"), StringMatch::new("b").word().trim());
+        assert_eq!(orig.with_text(String::from("c")).text(), "c");
+        assert_eq!(
+            orig.with_match_length(StringMatchLength::Suffix),
+            StringMatch::new("a").suffix().trim()
+        );
+        assert_eq!(
+            orig.with_case_sensitive(false),
+            StringMatch::new("a").word().trim().case_insensitive()
+        );
+        assert!(orig.with_case_sensitive(true).is_case_sensitive());
+        // The original is unchanged.
+        assert_eq!(orig, StringMatch::new("a").word().trim());
+    }
+
+    #[test]
+    fn test_map_text() {
+        let orig = StringMatch::new("Hello").partial().case_insensitive();
+        let upper = orig.map_text(|t| t.to_uppercase());
+        assert_eq!(upper.text(), "HELLO");
+        assert_eq!(orig.text(), "Hello");
+        assert_eq!(upper.match_length(), orig.match_length());
+        assert!(!upper.is_case_sensitive());
+        for haystack in ["hello", "say HELLO", "HeLLo world", "help", ""] {
+            assert_eq!(upper.is_match(haystack), orig.is_match(haystack));
+        }
+
+        let prefixed = StringMatch::new("log").prefix().map_text(|t| format!("app.{}", t));
+        assert_eq!(prefixed, StringMatch::new("app.log").prefix());
+
+        let mut sm = StringMatch::new("abc").word();
+        sm.map_text_in_place(|t| t.chars().rev().collect());
+        assert_eq!(sm, StringMatch::new("cba").word());
+    }
+
+    #[test]
+    fn test_default() {
+        let sm = StringMatch::default();
+        assert_eq!(sm, StringMatch::new(""));
+        assert!(sm.is_empty());
+        assert!(sm.is_full_match());
+        assert!(sm.is_case_sensitive());
+        assert!(sm.is_match(""));
+        assert!(!sm.is_match("x"));
+        assert!(!StringMatch::new("x").is_empty());
+        assert_eq!(StringMatchLength::default(), StringMatchLength::Full);
+    }
+
+    #[test]
+    fn test_ord() {
+        assert!(StringMatchLength::Full < StringMatchLength::Partial);
+        assert!(StringMatchLength::Partial < StringMatchLength::Word);
+        assert!(StringMatchLength::Prefix < StringMatchLength::Suffix);
+
+        let mut needles = vec![
+            StringMatch::new("b"),
+            StringMatch::new("a").word(),
+            StringMatch::new("a").partial(),
+            StringMatch::new("a"),
+            StringMatch::new("a").case_insensitive(),
+            StringMatch::new("B"),
+        ];
+        needles.sort();
+        assert_eq!(
+            needles,
+            vec![
+                StringMatch::new("B"),
+                StringMatch::new("a").case_insensitive(),
+                StringMatch::new("a"),
+                StringMatch::new("a").partial(),
+                StringMatch::new("a").word(),
+                StringMatch::new("b"),
+            ]
+        );
+
+        let set: std::collections::BTreeSet<StringMatch> = [
+            StringMatch::new("a"),
+            StringMatch::new("a").partial(),
+            StringMatch::new("a"),
+            StringMatch::new("a").partial(),
+            StringMatch::new("a").partial().trim(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(StringMatch::try_new("ok").unwrap(), StringMatch::new("ok"));
+        assert!(StringMatch::new("ok").validate().is_ok());
+        assert!(StringMatch::new("ok").partial().case_insensitive().validate().is_ok());
+
+        #[cfg(feature = "std")]
+        {
+            let pattern = String::from("(unclosed");
+            let err = StringMatchError::from(Regex::new(&pattern).unwrap_err());
+            assert!(matches!(err, StringMatchError::InvalidRegex(_)));
+            assert!(err.to_string().starts_with("invalid regex"));
+            assert!(std::error::Error::source(&err).is_some());
+        }
+
+        #[cfg(feature = "glob")]
+        {
+            let err = StringMatchError::from(GlobNeedle::new("[a-").unwrap_err());
+            assert!(matches!(err, StringMatchError::InvalidGlob(_)));
+        }
     }
 
     #[test]
@@ -257,12 +2354,16 @@ mod tests {
         assert_eq!("a".match_full(), StringMatch::new("a").full());
         assert_eq!("a".match_partial(), StringMatch::new("a").partial());
         assert_eq!("a".match_word(), StringMatch::new("a").word());
+        assert_eq!("a".match_prefix(), StringMatch::new("a").prefix());
+        assert_eq!("a".match_suffix(), StringMatch::new("a").suffix());
         assert_eq!("a".match_case_insensitive(), StringMatch::new("a").case_insensitive());
         assert_eq!("a".match_case_sensitive(), StringMatch::new("a").case_sensitive());
 
         assert_eq!(String::from("a").match_full(), StringMatch::new("a").full());
         assert_eq!(String::from("a").match_partial(), StringMatch::new("a").partial());
         assert_eq!(String::from("a").match_word(), StringMatch::new("a").word());
+        assert_eq!(String::from("a").match_prefix(), StringMatch::new("a").prefix());
+        assert_eq!(String::from("a").match_suffix(), StringMatch::new("a").suffix());
         assert_eq!(
             String::from("a").match_case_insensitive(),
             StringMatch::new("a").case_insensitive()
@@ -273,6 +2374,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stringmatchable_types() {
+        fn check<T: StringMatchable>(make: impl Fn() -> T, text: &str) {
+            let expected = StringMatch::new(text.to_string());
+            assert_eq!(make().match_full(), expected.clone().full());
+            assert_eq!(make().match_partial(), expected.clone().partial());
+            assert_eq!(make().match_word(), expected.clone().word());
+            assert_eq!(make().match_prefix(), expected.clone().prefix());
+            assert_eq!(make().match_suffix(), expected.clone().suffix());
+            assert_eq!(make().match_case_insensitive(), expected.clone().case_insensitive());
+            assert_eq!(make().match_case_sensitive(), expected.case_sensitive());
+        }
+
+        check(|| Box::<str>::from("a"), "a");
+        check(|| Cow::<'static, str>::Borrowed("b"), "b");
+        check(|| Cow::<'static, str>::Owned(String::from("c")), "c");
+    }
+
     fn needle_is_match<N>(needle: N) -> bool
     where
         N: Needle,
@@ -290,6 +2409,28 @@ mod tests {
         assert!(!needle_is_match(String::from("test"))); // Strings are case-sensitive.
         assert!(!needle_is_match(String::from("Te"))); // Strings always match whole haystack.
 
+        assert!(needle_is_match(Cow::Borrowed("Test")));
+        assert!(needle_is_match(Cow::<str>::Owned(String::from("Test"))));
+        assert!(!needle_is_match(Cow::Borrowed("test")));
+        assert!(!needle_is_match(Cow::Borrowed("Te")));
+
+        assert!(needle_is_match(Arc::<str>::from("Test")));
+        assert!(!needle_is_match(Arc::<str>::from("test")));
+        assert!(!needle_is_match(Arc::<str>::from("Te")));
+
+        assert!(needle_is_match(Rc::<str>::from("Test")));
+        assert!(!needle_is_match(Rc::<str>::from("test")));
+        assert!(!needle_is_match(Rc::<str>::from("Te")));
+
+        assert!(needle_is_match(Box::<str>::from("Test")));
+        assert!(!needle_is_match(Box::<str>::from("test")));
+        assert!(!needle_is_match(Box::<str>::from("Te")));
+
+        assert!(Arc::<str>::from("a").is_match_in(&mut vec!["b", "a"].into_iter()));
+        assert!(!Rc::<str>::from("a").is_match_in(&mut vec!["b", "c"].into_iter()));
+        assert!(Box::<str>::from("a").is_match_in(&mut vec!["a"].into_iter()));
+        assert!(Cow::Borrowed("a").is_match_in(&mut vec!["a"].into_iter()));
+
         assert!(needle_is_match(StringMatch::from("Test")));
         assert!(!needle_is_match(StringMatch::from("test")));
         assert!(needle_is_match(StringMatch::from("test").case_insensitive()));
@@ -297,16 +2438,275 @@ mod tests {
         assert!(!needle_is_match(StringMatch::from("te").partial()));
         assert!(needle_is_match(StringMatch::from("te").partial().case_insensitive()));
 
-        assert!(needle_is_match(Regex::new("Test").unwrap()));
-        assert!(needle_is_match(Regex::new("Te").unwrap())); // Regex is partial by default unless ^$ specified.
-        assert!(!needle_is_match(Regex::new("te").unwrap())); // Regex is case-sensitive by default.
-        assert!(needle_is_match(Regex::new(r"(?i)te").unwrap())); // Case insensitive.
-        assert!(needle_is_match(Regex::new(r"\w+").unwrap()));
-        assert!(needle_is_match(Regex::new(r"\w").unwrap()));
-        assert!(!needle_is_match(Regex::new(r"^T$").unwrap()));
-        assert!(!needle_is_match(Regex::new(r"^est").unwrap()));
-        assert!(!needle_is_match(Regex::new(r"Te$").unwrap()));
-        assert!(needle_is_match(Regex::new(r"^T.+t$").unwrap()));
+        #[cfg(feature = "std")]
+        {
+            assert!(needle_is_match(Regex::new("Test").unwrap()));
+            assert!(needle_is_match(Regex::new("Te").unwrap())); // Regex is partial by default unless ^$ specified.
+            assert!(!needle_is_match(Regex::new("te").unwrap())); // Regex is case-sensitive by default.
+            assert!(needle_is_match(Regex::new(r"(?i)te").unwrap())); // Case insensitive.
+            assert!(needle_is_match(Regex::new(r"\w+").unwrap()));
+            assert!(needle_is_match(Regex::new(r"\w").unwrap()));
+            assert!(!needle_is_match(Regex::new(r"^T$").unwrap()));
+            assert!(!needle_is_match(Regex::new(r"^est").unwrap()));
+            assert!(!needle_is_match(Regex::new(r"Te$").unwrap()));
+            assert!(needle_is_match(Regex::new(r"^T.+t$").unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_char_needle() {
+        assert!('a'.is_match("a"));
+        assert!(!'a'.is_match("A"));
+        assert!(!'a'.is_match(""));
+        assert!(!'a'.is_match("aa"));
+        assert!(!'a'.is_match("ab"));
+        assert!('é'.is_match("é"));
+        assert!(!'é'.is_match("éé"));
+        assert!(!'é'.is_match("e"));
+        // Decomposed form is a single grapheme cluster but two chars.
+        assert!(!'é'.is_match("e\u{301}"));
+        assert!(!'e'.is_match("e\u{301}"));
+        assert!('🦀'.is_match("🦀"));
+        assert!(!'🦀'.is_match("🦀🦀"));
+
+        assert_eq!(StringMatch::from_char('a'), StringMatch::new("a"));
+        assert!(StringMatch::from_char('é').is_match("é"));
+        assert!(StringMatch::from_char('É').case_insensitive().is_match("é"));
+        assert!(StringMatch::from_char('é').partial().is_match("café"));
+    }
+
+    #[test]
+    fn test_needle_iter() {
+        let haystacks = vec!["apple", "Banana", "cherry", "banana", "date"];
+
+        let needle = StringMatch::from("an").partial();
+        assert!(needle.is_match_in(&mut haystacks.clone().into_iter()));
+        assert_eq!(needle.first_match(&mut haystacks.clone().into_iter()), Some("Banana"));
+        assert_eq!(needle.last_match(haystacks.clone().into_iter()), Some("banana"));
+        assert_eq!(needle.nth_match(&mut haystacks.clone().into_iter(), 1), Some("banana"));
+        assert_eq!(needle.nth_match(&mut haystacks.clone().into_iter(), 2), None);
+        assert_eq!(needle.count_matches(haystacks.clone().into_iter()), 2);
+
+        let needle = StringMatch::from("BANANA").case_insensitive();
+        assert_eq!(needle.count_matches(haystacks.clone().into_iter()), 2);
+        assert_eq!(StringMatch::from("fig").first_match(&mut haystacks.clone().into_iter()), None);
+        assert_eq!(StringMatch::from("fig").last_match(haystacks.clone().into_iter()), None);
+
+        #[cfg(feature = "std")]
+        {
+            let needle = Regex::new("^[a-c]").unwrap();
+            assert_eq!(needle.first_match(&mut haystacks.clone().into_iter()), Some("apple"));
+            assert_eq!(needle.last_match(haystacks.clone().into_iter()), Some("banana"));
+            assert_eq!(needle.nth_match(&mut haystacks.clone().into_iter(), 1), Some("cherry"));
+            assert_eq!(needle.count_matches(haystacks.clone().into_iter()), 3);
+        }
+
+        let needle = |s: &str| s.len() == 5;
+        assert_eq!(needle.first_match(&mut haystacks.clone().into_iter()), Some("apple"));
+        assert_eq!(needle.last_match(haystacks.clone().into_iter()), Some("apple"));
+        assert_eq!(needle.nth_match(&mut haystacks.clone().into_iter(), 0), Some("apple"));
+        assert_eq!(needle.count_matches(haystacks.clone().into_iter()), 1);
+
+        // The iterator is only advanced as far as needed.
+        let mut iter = haystacks.clone().into_iter();
+        assert_eq!(StringMatch::from("an").partial().first_match(&mut iter), Some("Banana"));
+        assert_eq!(iter.next(), Some("cherry"));
+    }
+
+    #[test]
+    fn test_partition_matching() {
+        let haystacks = vec!["a", "ab", "b a", "ba", "c", "A"];
+
+        let (matching, rest) =
+            StringMatch::from("a").partition_matching(haystacks.clone().into_iter());
+        assert_eq!(matching, vec!["a"]);
+        assert_eq!(rest, vec!["ab", "b a", "ba", "c", "A"]);
+
+        let (matching, rest) =
+            StringMatch::from("a").partial().partition_matching(haystacks.clone().into_iter());
+        assert_eq!(matching, vec!["a", "ab", "b a", "ba"]);
+        assert_eq!(rest, vec!["c", "A"]);
+
+        let (matching, rest) = StringMatch::from("a")
+            .word()
+            .case_insensitive()
+            .partition_matching(haystacks.clone().into_iter());
+        assert_eq!(matching, vec!["a", "b a", "A"]);
+        assert_eq!(rest, vec!["ab", "ba", "c"]);
+
+        // Owned strings work too, and the partitions reassemble into the original sequence.
+        #[cfg(feature = "std")]
+        {
+            let owned: Vec<String> = haystacks.iter().map(|s| s.to_string()).collect();
+            let (matching, rest) =
+                Regex::new("b").unwrap().partition_matching(owned.clone().into_iter());
+            assert_eq!(matching, vec!["ab", "b a", "ba"]);
+            let mut combined: Vec<String> = matching.into_iter().chain(rest).collect();
+            combined.sort_by_key(|s| owned.iter().position(|o| o == s).unwrap());
+            assert_eq!(combined, owned);
+        }
+
+        let (matching, rest) = "x".partition_matching(Vec::<String>::new().into_iter());
+        assert!(matching.is_empty() && rest.is_empty());
+    }
+
+    #[test]
+    fn test_filter_matching() {
+        let haystacks = vec!["apple", "Banana", "cherry", "banana", "date"];
+
+        #[cfg(feature = "std")]
+        {
+            let needle = Regex::new("an").unwrap();
+            let upper: Vec<String> = needle
+                .filter_matching(haystacks.clone().into_iter())
+                .map(str::to_uppercase)
+                .collect();
+            assert_eq!(upper, vec!["BANANA", "BANANA"]);
+            let rest: Vec<&str> =
+                needle.filter_not_matching(haystacks.clone().into_iter()).collect();
+            assert_eq!(rest, vec!["apple", "cherry", "date"]);
+        }
+
+        let needle = StringMatch::from("banana").case_insensitive();
+        assert_eq!(needle.filter_matching(haystacks.clone().into_iter()).count(), 2);
+        assert_eq!(needle.filter_not_matching(haystacks.clone().into_iter()).count(), 3);
+
+        // Items are evaluated lazily, and only once each.
+        let calls = std::cell::Cell::new(0);
+        let needle = |s: &str| {
+            calls.set(calls.get() + 1);
+            s.contains('e')
+        };
+        let mut filtered = needle.filter_matching(haystacks.clone().into_iter());
+        assert_eq!(calls.get(), 0);
+        assert_eq!(filtered.next(), Some("apple"));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(filtered.next(), Some("cherry"));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(filtered.collect::<Vec<_>>(), vec!["date"]);
+        assert_eq!(calls.get(), haystacks.len());
+    }
+
+    #[test]
+    fn test_all_none_match() {
+        let needle = StringMatch::from("a").partial();
+        assert!(needle.all_match(Vec::<&str>::new().into_iter()));
+        assert!(needle.none_match(Vec::<&str>::new().into_iter()));
+        assert!(needle.all_match(vec!["a", "ba", "cab"].into_iter()));
+        assert!(!needle.all_match(vec!["a", "b", "cab"].into_iter()));
+        assert!(!needle.none_match(vec!["b", "c", "cab"].into_iter()));
+        assert!(needle.none_match(vec!["b", "c", "d"].into_iter()));
+        #[cfg(feature = "std")]
+        assert!(Regex::new(r"^\d+$").unwrap().all_match(vec!["1", "23"].into_iter()));
+
+        // Items after the deciding one are never evaluated.
+        let panic_after = |items: Vec<&'static str>| {
+            items.into_iter().chain(std::iter::from_fn(|| panic!("item should not be evaluated")))
+        };
+        assert!(!needle.all_match(panic_after(vec!["a", "b"])));
+        assert!(!needle.none_match(panic_after(vec!["b", "a"])));
+    }
+
+    #[test]
+    fn test_positions() {
+        let haystacks = ["apple", "Banana", "cherry", "banana", "date"];
+
+        let needle = StringMatch::from("an").partial();
+        assert_eq!(needle.positions(haystacks.into_iter()), vec![1, 3]);
+        assert_eq!(needle.position(&mut haystacks.into_iter()), Some(1));
+        #[cfg(feature = "std")]
+        assert_eq!(Regex::new("e").unwrap().positions(haystacks.into_iter()), vec![0, 2, 4]);
+        assert!(StringMatch::from("fig").positions(haystacks.into_iter()).is_empty());
+        assert_eq!(StringMatch::from("fig").position(&mut haystacks.into_iter()), None);
+
+        // Indices are tracked for any iterator, not just collections.
+        assert_eq!(needle.positions("a an b ban".split(' ')), vec![1, 3]);
+        assert_eq!(needle.position(&mut "a an b ban".split(' ')), Some(1));
+
+        // `position` stops at the first match.
+        let mut iter = haystacks.into_iter();
+        assert_eq!(needle.position(&mut iter), Some(1));
+        assert_eq!(iter.next(), Some("cherry"));
+    }
+
+    #[test]
+    fn test_enumerate_matches() {
+        let haystacks: Vec<&str> = "apple Banana cherry banana date".split(' ').collect();
+
+        let needle = StringMatch::from("an").partial();
+        let matches: Vec<(usize, &str)> =
+            needle.enumerate_matches(haystacks.iter().copied()).collect();
+        assert_eq!(matches, vec![(1, "Banana"), (3, "banana")]);
+        for (i, item) in &matches {
+            assert!(core::ptr::eq(*item, haystacks[*i]));
+        }
+        assert_eq!(
+            matches.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            needle.positions(haystacks.iter().copied())
+        );
+
+        let needle = StringMatch::from("BANANA").case_insensitive();
+        assert_eq!(
+            needle.enumerate_matches(haystacks.iter().copied()).collect::<Vec<_>>(),
+            vec![(1, "Banana"), (3, "banana")]
+        );
+        assert_eq!(
+            StringMatch::from("fig").enumerate_matches(haystacks.iter().copied()).count(),
+            0
+        );
+
+        // Any iterator works, and indices count every item, not just the matches.
+        let needle = StringMatch::from("b").prefix();
+        let mut iter = needle.enumerate_matches("a b c ba bb".split(' '));
+        assert_eq!(iter.next(), Some((1, "b")));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(3, "ba"), (4, "bb")]);
+    }
+
+    #[test]
+    fn test_window_match() {
+        let lines = vec!["connecting", "timeout", "retrying", "connected", "idle", "idle"];
+
+        let needle = StringMatch::from("timeout\nretrying").partial();
+        assert!(needle.window_match(lines.clone().into_iter(), 2));
+        assert!(needle.window_match(lines.clone().into_iter(), 3));
+        assert!(!needle.window_match(lines.clone().into_iter(), 1));
+        assert_eq!(
+            needle.windows_matching(lines.clone().into_iter(), 3).collect::<Vec<_>>(),
+            vec![
+                vec!["connecting", "timeout", "retrying"],
+                vec!["timeout", "retrying", "connected"]
+            ]
+        );
+
+        // Windows where any item contains the needle.
+        let needle = StringMatch::from("connect").partial();
+        let windows: Vec<Vec<&str>> =
+            needle.windows_matching(lines.clone().into_iter(), 2).collect();
+        assert_eq!(
+            windows,
+            vec![
+                vec!["connecting", "timeout"],
+                vec!["retrying", "connected"],
+                vec!["connected", "idle"]
+            ]
+        );
+        assert!(!windows.contains(&vec!["idle", "idle"]));
+        assert!(!windows.contains(&vec!["timeout", "retrying"]));
+
+        // A closure can inspect the window as a whole.
+        let needle = |s: &str| s.lines().filter(|l| *l == "idle").count() == 2;
+        assert!(needle.window_match(lines.clone().into_iter(), 2));
+        assert!(!needle.window_match(lines[..5].iter().copied(), 3));
+
+        // Too few items for a single window.
+        assert!(!StringMatch::from("").partial().window_match(lines.clone().into_iter(), 7));
+        assert!(StringMatch::from("").partial().window_match(lines.clone().into_iter(), 6));
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn test_window_match_zero() {
+        StringMatch::from("a").window_match(vec!["a"].into_iter(), 0);
     }
 
     fn dynamic_dispatched_needle(needle: &dyn Needle) -> bool {
@@ -330,16 +2730,19 @@ mod tests {
         assert!(!dynamic_dispatched_needle(&StringMatch::from("te").partial()));
         assert!(dynamic_dispatched_needle(&StringMatch::from("te").partial().case_insensitive()));
 
-        assert!(dynamic_dispatched_needle(&Regex::new("Test").unwrap()));
-        assert!(dynamic_dispatched_needle(&Regex::new("Te").unwrap())); // Regex is partial by default unless ^$ specified.
-        assert!(!dynamic_dispatched_needle(&Regex::new("te").unwrap())); // Regex is case-sensitive by default.
-        assert!(dynamic_dispatched_needle(&Regex::new(r"(?i)te").unwrap())); // Case insensitive.
-        assert!(dynamic_dispatched_needle(&Regex::new(r"\w+").unwrap()));
-        assert!(dynamic_dispatched_needle(&Regex::new(r"\w").unwrap()));
-        assert!(!dynamic_dispatched_needle(&Regex::new(r"^T$").unwrap()));
-        assert!(!dynamic_dispatched_needle(&Regex::new(r"^est").unwrap()));
-        assert!(!dynamic_dispatched_needle(&Regex::new(r"Te$").unwrap()));
-        assert!(dynamic_dispatched_needle(&Regex::new(r"^T.+t$").unwrap()));
+        #[cfg(feature = "std")]
+        {
+            assert!(dynamic_dispatched_needle(&Regex::new("Test").unwrap()));
+            assert!(dynamic_dispatched_needle(&Regex::new("Te").unwrap())); // Regex is partial by default unless ^$ specified.
+            assert!(!dynamic_dispatched_needle(&Regex::new("te").unwrap())); // Regex is case-sensitive by default.
+            assert!(dynamic_dispatched_needle(&Regex::new(r"(?i)te").unwrap())); // Case insensitive.
+            assert!(dynamic_dispatched_needle(&Regex::new(r"\w+").unwrap()));
+            assert!(dynamic_dispatched_needle(&Regex::new(r"\w").unwrap()));
+            assert!(!dynamic_dispatched_needle(&Regex::new(r"^T$").unwrap()));
+            assert!(!dynamic_dispatched_needle(&Regex::new(r"^est").unwrap()));
+            assert!(!dynamic_dispatched_needle(&Regex::new(r"Te$").unwrap()));
+            assert!(dynamic_dispatched_needle(&Regex::new(r"^T.+t$").unwrap()));
+        }
 
         assert!(dynamic_dispatched_needle(&|s: &str| s == "Test"));
         assert!(!dynamic_dispatched_needle(&|s: &str| s == "test"));
@@ -353,5 +2756,52 @@ mod tests {
         let serialized: String = serde_json::to_string(&orig).unwrap();
         let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, orig);
+
+        let orig = StringMatch::new("a").word_with_separators(",\t");
+        let serialized: String = serde_json::to_string(&orig).unwrap();
+        assert!(serialized.contains("separator_chars"));
+        let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+
+        let orig = StringMatch::new("a")
+            .trim()
+            .trim_end_before_match()
+            .normalize_whitespace()
+            .collapse_whitespace();
+        let serialized: String = serde_json::to_string(&orig).unwrap();
+        assert!(serialized.contains(r#""normalize_whitespace":true"#));
+        assert!(serialized.contains(r#""trim":true"#));
+        assert!(serialized.contains(r#""trim_end":true"#));
+        assert!(serialized.contains(r#""collapse_whitespace":true"#));
+        let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+        assert!(deserialized.is_whitespace_collapsed());
+
+        let orig = StringMatch::new("a").line_by_line();
+        let serialized: String = serde_json::to_string(&orig).unwrap();
+        assert!(serialized.contains(r#""multiline":true"#));
+        assert!(!serialized.contains("min_length"));
+        let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+
+        let orig = StringMatch::new("a").min_length(1).max_length(10).ascii_only();
+        let serialized: String = serde_json::to_string(&orig).unwrap();
+        assert!(serialized.contains(r#""ascii_only":true"#));
+        assert!(serialized.contains(r#""min_length":1"#));
+        assert!(serialized.contains(r#""max_length":10"#));
+        let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+
+        let orig = StringMatch::new("a-b").ignore_punctuation().ignore_numbers();
+        let serialized: String = serde_json::to_string(&orig).unwrap();
+        assert!(serialized.contains(r#""ignore_punctuation":true"#));
+        assert!(serialized.contains(r#""ignore_numbers":true"#));
+        let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+
+        // Fields added after the initial release are optional.
+        let json = r#"{"text":"a","match_length":"Partial","case_sensitive":false}"#;
+        let deserialized: StringMatch = serde_json::from_str(json).unwrap();
+        assert_eq!(deserialized, StringMatch::new("a").partial().case_insensitive());
     }
 }
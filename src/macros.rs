@@ -0,0 +1,172 @@
+/// Create a `StringMatch` from the text and optional flags.
+///
+/// The flags are `full`, `partial`, `word`, `prefix` and `suffix` for the match length, and
+/// `ci` (case-insensitive) and `cs` (case-sensitive). At most one flag of each kind may be
+/// given. The macro expands to the equivalent builder calls.
+///
+/// ```
+/// use stringmatch::{needle, StringMatch};
+///
+/// assert_eq!(needle!("foo", partial, ci), StringMatch::new("foo").partial().case_insensitive());
+/// ```
+///
+/// Conflicting flags are a compile error:
+///
+/// ```compile_fail
+/// let sm = stringmatch::needle!("foo", full, partial);
+/// ```
+///
+/// ```compile_fail
+/// let sm = stringmatch::needle!("foo", insensitive);
+/// ```
+#[macro_export]
+macro_rules! needle {
+    ($text:expr $(, $flag:ident)* $(,)?) => {{
+        $crate::__needle_flags!(@check [] [] $($flag)*);
+        $crate::__needle_flags!(@build $crate::StringMatch::new($text), $($flag)*)
+    }};
+}
+
+/// Create an `AnyNeedle` from the needle expressions.
+///
+/// ```
+/// use stringmatch::{any_needle, needle, Needle};
+///
+/// let any = any_needle!("foo", needle!("bar", partial));
+/// assert!(any.is_match("foo"));
+/// assert!(any.is_match("a bar"));
+/// ```
+#[macro_export]
+macro_rules! any_needle {
+    ($($needle:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut any = $crate::AnyNeedle::new();
+        $(any.push($needle);)*
+        any
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __needle_flags {
+    (@check [$($length:ident)?] [$($case:ident)?]) => {};
+    (@check [] [$($case:ident)?] full $($rest:ident)*) => {
+        $crate::__needle_flags!(@check [full] [$($case)?] $($rest)*)
+    };
+    (@check [] [$($case:ident)?] partial $($rest:ident)*) => {
+        $crate::__needle_flags!(@check [partial] [$($case)?] $($rest)*)
+    };
+    (@check [] [$($case:ident)?] word $($rest:ident)*) => {
+        $crate::__needle_flags!(@check [word] [$($case)?] $($rest)*)
+    };
+    (@check [] [$($case:ident)?] prefix $($rest:ident)*) => {
+        $crate::__needle_flags!(@check [prefix] [$($case)?] $($rest)*)
+    };
+    (@check [] [$($case:ident)?] suffix $($rest:ident)*) => {
+        $crate::__needle_flags!(@check [suffix] [$($case)?] $($rest)*)
+    };
+    (@check [$($length:ident)?] [] ci $($rest:ident)*) => {
+        $crate::__needle_flags!(@check [$($length)?] [ci] $($rest)*)
+    };
+    (@check [$($length:ident)?] [] cs $($rest:ident)*) => {
+        $crate::__needle_flags!(@check [$($length)?] [cs] $($rest)*)
+    };
+    (@check [$length:ident] [$($case:ident)?] full $($rest:ident)*) => {
+        compile_error!(concat!("needle!: conflicting flags `", stringify!($length), "` and `full`"));
+    };
+    (@check [$length:ident] [$($case:ident)?] partial $($rest:ident)*) => {
+        compile_error!(concat!("needle!: conflicting flags `", stringify!($length), "` and `partial`"));
+    };
+    (@check [$length:ident] [$($case:ident)?] word $($rest:ident)*) => {
+        compile_error!(concat!("needle!: conflicting flags `", stringify!($length), "` and `word`"));
+    };
+    (@check [$length:ident] [$($case:ident)?] prefix $($rest:ident)*) => {
+        compile_error!(concat!("needle!: conflicting flags `", stringify!($length), "` and `prefix`"));
+    };
+    (@check [$length:ident] [$($case:ident)?] suffix $($rest:ident)*) => {
+        compile_error!(concat!("needle!: conflicting flags `", stringify!($length), "` and `suffix`"));
+    };
+    (@check [$($length:ident)?] [$case:ident] ci $($rest:ident)*) => {
+        compile_error!(concat!("needle!: conflicting flags `", stringify!($case), "` and `ci`"));
+    };
+    (@check [$($length:ident)?] [$case:ident] cs $($rest:ident)*) => {
+        compile_error!(concat!("needle!: conflicting flags `", stringify!($case), "` and `cs`"));
+    };
+    (@check [$($length:ident)?] [$($case:ident)?] $flag:ident $($rest:ident)*) => {
+        compile_error!(concat!("needle!: unknown flag `", stringify!($flag), "`"));
+    };
+
+    (@build $sm:expr,) => {
+        $sm
+    };
+    (@build $sm:expr, full $($rest:ident)*) => {
+        $crate::__needle_flags!(@build $sm.full(), $($rest)*)
+    };
+    (@build $sm:expr, partial $($rest:ident)*) => {
+        $crate::__needle_flags!(@build $sm.partial(), $($rest)*)
+    };
+    (@build $sm:expr, word $($rest:ident)*) => {
+        $crate::__needle_flags!(@build $sm.word(), $($rest)*)
+    };
+    (@build $sm:expr, prefix $($rest:ident)*) => {
+        $crate::__needle_flags!(@build $sm.prefix(), $($rest)*)
+    };
+    (@build $sm:expr, suffix $($rest:ident)*) => {
+        $crate::__needle_flags!(@build $sm.suffix(), $($rest)*)
+    };
+    (@build $sm:expr, ci $($rest:ident)*) => {
+        $crate::__needle_flags!(@build $sm.case_insensitive(), $($rest)*)
+    };
+    (@build $sm:expr, cs $($rest:ident)*) => {
+        $crate::__needle_flags!(@build $sm.case_sensitive(), $($rest)*)
+    };
+    // Unknown flags are reported by `@check`.
+    (@build $sm:expr, $flag:ident $($rest:ident)*) => {
+        $sm
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnyNeedle, Needle, StringMatch};
+
+    #[test]
+    fn test_needle_macro() {
+        let sm: StringMatch = needle!("foo");
+        assert_eq!(sm, StringMatch::new("foo"));
+        assert_eq!(needle!("foo", partial), StringMatch::new("foo").partial());
+        assert_eq!(needle!("foo", word, ci), StringMatch::new("foo").word().case_insensitive());
+        assert_eq!(
+            needle!("foo", ci, prefix,),
+            StringMatch::new("foo").prefix().case_insensitive()
+        );
+        assert_eq!(needle!("foo", suffix, cs), StringMatch::new("foo").suffix());
+        assert_eq!(needle!("foo", full), StringMatch::new("foo"));
+
+        let text = String::from("Bar");
+        let sm = needle!(text, partial, ci);
+        assert!(sm.is_match("a bar"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_needle_macro_static() {
+        use std::sync::LazyLock;
+
+        static NEEDLE: LazyLock<StringMatch> = LazyLock::new(|| needle!("foo", partial, ci));
+        assert!(NEEDLE.is_match("FOOD"));
+    }
+
+    #[test]
+    fn test_any_needle_macro() {
+        let any: AnyNeedle = any_needle!();
+        assert!(any.is_empty());
+
+        let any = any_needle!("foo", needle!("bar", partial), |s: &str| s.len() > 10,);
+        assert_eq!(any.len(), 3);
+        assert!(any.is_match("foo"));
+        assert!(any.is_match("a bar"));
+        assert!(any.is_match("a long haystack"));
+        assert!(!any.is_match("baz"));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{Needle, NeedleIter};
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Serialize};
+
+/// An optional needle, e.g. an optional filter: `None` matches every haystack and `Some(n)`
+/// matches if `n` does.
+///
+/// Use `MaybeNeedle` if `None` should match nothing instead.
+impl<N> Needle for Option<N>
+where
+    N: Needle,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        match self {
+            Some(needle) => needle.is_match(haystack),
+            None => true,
+        }
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.as_ref().map_or(0, N::complexity_estimate)
+    }
+}
+
+impl<N> NeedleIter for Option<N> where N: Needle {}
+
+/// An optional needle that matches nothing when it is `None`, and otherwise matches if the
+/// inner needle does.
+///
+/// This is the opposite of the `Needle` impl for `Option`, where `None` matches everything.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct MaybeNeedle<N>(Option<N>);
+
+impl<N> MaybeNeedle<N>
+where
+    N: Needle,
+{
+    pub fn new(needle: Option<N>) -> Self {
+        Self(needle)
+    }
+
+    pub fn inner(&self) -> Option<&N> {
+        self.0.as_ref()
+    }
+
+    pub fn into_inner(self) -> Option<N> {
+        self.0
+    }
+}
+
+impl<N> From<Option<N>> for MaybeNeedle<N>
+where
+    N: Needle,
+{
+    fn from(needle: Option<N>) -> Self {
+        Self::new(needle)
+    }
+}
+
+impl<N> Needle for MaybeNeedle<N>
+where
+    N: Needle,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        self.0.as_ref().is_some_and(|needle| needle.is_match(haystack))
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.0.as_ref().map_or(0, N::complexity_estimate)
+    }
+}
+
+impl<N> NeedleIter for MaybeNeedle<N> where N: Needle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StringMatch, StringMatchLength};
+    use alloc::vec;
+
+    #[test]
+    fn test_option_needle() {
+        let none: Option<StringMatch> = None;
+        assert!(none.is_match(""));
+        assert!(none.is_match("anything"));
+        assert!(none.all_match(vec!["a", "b"].into_iter()));
+        assert_eq!(none.complexity_estimate(), 0);
+
+        let lengths = [
+            StringMatchLength::Full,
+            StringMatchLength::Partial,
+            StringMatchLength::Word,
+            StringMatchLength::Prefix,
+            StringMatchLength::Suffix,
+        ];
+        let haystacks = ["", "ab", "ab cd", "xabx", "x ab", "cd ab", "AB"];
+        for length in lengths {
+            let sm = StringMatch::from("ab").with_match_length(length);
+            let some = Some(sm.clone());
+            let maybe = MaybeNeedle::new(Some(sm.clone()));
+            for haystack in haystacks {
+                assert_eq!(some.is_match(haystack), sm.is_match(haystack), "{sm:?} {haystack:?}");
+                assert_eq!(maybe.is_match(haystack), sm.is_match(haystack), "{sm:?} {haystack:?}");
+            }
+            assert_eq!(some.complexity_estimate(), sm.complexity_estimate());
+            assert_eq!(maybe.complexity_estimate(), sm.complexity_estimate());
+        }
+
+        // Works with any needle type, e.g. for an optional filter.
+        let filter: Option<&str> = Some("b");
+        assert_eq!(filter.positions(vec!["a", "b", "c"].into_iter()), vec![1]);
+        let filter: Option<&str> = None;
+        assert_eq!(filter.positions(vec!["a", "b", "c"].into_iter()), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_maybe_needle() {
+        let none: MaybeNeedle<StringMatch> = MaybeNeedle::new(None);
+        assert!(!none.is_match(""));
+        assert!(!none.is_match("anything"));
+        assert!(none.none_match(vec!["a", "b"].into_iter()));
+        assert_eq!(none, MaybeNeedle::default());
+        assert_eq!(none.inner(), None);
+
+        let maybe: MaybeNeedle<_> = Some(StringMatch::from("a").prefix()).into();
+        assert!(maybe.is_match("abc"));
+        assert!(!maybe.is_match("cba"));
+        assert!(maybe.is_match_in(&mut vec!["x", "ax"].into_iter()));
+        assert_eq!(maybe.inner(), Some(&StringMatch::from("a").prefix()));
+        assert_eq!(maybe.into_inner(), Some(StringMatch::from("a").prefix()));
+    }
+}
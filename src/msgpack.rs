@@ -0,0 +1,51 @@
+use crate::StringMatch;
+
+impl StringMatch {
+    /// Serialize to the MessagePack binary format.
+    ///
+    /// Fields are written by name rather than by position, so the bytes can still be read
+    /// after new options are added to `StringMatch`.
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
+
+    /// Deserialize from the MessagePack binary format, as written by `to_msgpack()`.
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Needle;
+
+    #[test]
+    fn test_msgpack_roundtrip() {
+        let needles = [
+            StringMatch::from("test"),
+            StringMatch::from("Test").case_insensitive(),
+            StringMatch::from("es").partial(),
+            StringMatch::from("te").prefix().case_insensitive(),
+            StringMatch::from("st").suffix(),
+            StringMatch::from("a b").word_with_separators(",\t "),
+            StringMatch::from(" a  b ").trim().normalize_whitespace(),
+            StringMatch::from("b").line_by_line().min_length(1).max_length(10).ascii_only(),
+            StringMatch::from("a,b").partial().ignore_punctuation().ignore_numbers(),
+            StringMatch::from("t*t").wildcard().collapse_whitespace(),
+        ];
+        let haystacks =
+            ["", "test", "Test", "TEST", "testing", "a test", "a,b", "a  b ", "a\nb", "é b"];
+        for orig in &needles {
+            let bytes = orig.to_msgpack().unwrap();
+            let deserialized = StringMatch::from_msgpack(&bytes).unwrap();
+            assert_eq!(&deserialized, orig);
+            for haystack in haystacks {
+                assert_eq!(deserialized.is_match(haystack), orig.is_match(haystack));
+            }
+        }
+
+        assert!(StringMatch::from_msgpack(&[]).is_err());
+        assert!(StringMatch::from_msgpack(&[0xc1]).is_err());
+    }
+}
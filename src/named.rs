@@ -0,0 +1,135 @@
+use crate::{MultiNeedle, Needle, NeedleIter, StringMatch};
+use alloc::string::String;
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Serialize};
+
+/// A `StringMatch` tagged with a human-readable name, such as the name of the rule it
+/// implements, so that the reason for a match can be reported.
+///
+/// Created by `StringMatch::with_name()`. Two `NamedStringMatch` values are only equal if both
+/// the name and the pattern are equal.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct NamedStringMatch {
+    name: String,
+    inner: StringMatch,
+}
+
+impl NamedStringMatch {
+    pub fn new(name: impl Into<String>, inner: StringMatch) -> Self {
+        Self {
+            name: name.into(),
+            inner,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn inner(&self) -> &StringMatch {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> StringMatch {
+        self.inner
+    }
+}
+
+impl StringMatch {
+    /// Tag this needle with a name.
+    pub fn with_name(self, name: impl Into<String>) -> NamedStringMatch {
+        NamedStringMatch::new(name, self)
+    }
+}
+
+impl Needle for NamedStringMatch {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.inner.is_match(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.inner.complexity_estimate()
+    }
+}
+
+impl NeedleIter for NamedStringMatch {}
+
+/// Collect named needles into a `MultiNeedle`, using their names. A later needle replaces an
+/// earlier one with the same name.
+impl FromIterator<NamedStringMatch> for MultiNeedle {
+    fn from_iter<T: IntoIterator<Item = NamedStringMatch>>(iter: T) -> Self {
+        let mut multi = MultiNeedle::new();
+        for named in iter {
+            multi.add(named.name, named.inner);
+        }
+        multi
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_named_string_match() {
+        let named = StringMatch::from("password").partial().case_insensitive().with_name("secrets");
+        assert_eq!(named.name(), "secrets");
+        assert_eq!(named.inner(), &StringMatch::from("password").partial().case_insensitive());
+        assert!(named.is_match("my PASSWORD is"));
+        assert!(!named.is_match("my passport"));
+        assert!(named.is_match_in(&mut vec!["a", "password"].into_iter()));
+        assert_eq!(named.complexity_estimate(), named.inner().complexity_estimate());
+
+        // The same pattern with a different name is not equal.
+        let pattern = StringMatch::from("x").partial();
+        assert_eq!(pattern.clone().with_name("a"), NamedStringMatch::new("a", pattern.clone()));
+        assert_ne!(pattern.clone().with_name("a"), pattern.clone().with_name("b"));
+        assert_ne!(pattern.clone().with_name("a"), StringMatch::from("y").partial().with_name("a"));
+        assert_eq!(pattern.clone().with_name("a").into_inner(), pattern);
+    }
+
+    #[test]
+    fn test_named_matches() {
+        let rules = vec![
+            StringMatch::from("DROP TABLE").partial().case_insensitive().with_name("sql"),
+            StringMatch::from("<script").partial().with_name("xss"),
+            StringMatch::from("..").prefix().with_name("traversal"),
+        ];
+        let triggered: Vec<&str> =
+            rules.iter().filter(|r| r.is_match("../<script>")).map(|r| r.name()).collect();
+        assert_eq!(triggered, ["xss", "traversal"]);
+
+        let multi: MultiNeedle = rules.into_iter().collect();
+        assert_eq!(multi.len(), 3);
+        assert_eq!(multi.triggered_names("drop table users"), ["sql"]);
+        assert!(multi.triggered_names("hello").is_empty());
+    }
+
+    #[cfg(feature = "serde_derive")]
+    #[test]
+    fn test_serde() {
+        let orig = StringMatch::from("a").prefix().with_name("starts with a");
+        let serialized = serde_json::to_string(&orig).unwrap();
+        let deserialized: NamedStringMatch = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash() {
+        use std::collections::HashSet;
+
+        let pattern = StringMatch::from("x");
+        let set: HashSet<_> = [
+            pattern.clone().with_name("a"),
+            pattern.clone().with_name("a"),
+            pattern.with_name("b"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 2);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::combinators::{AndNeedle, NotNeedle, OrNeedle};
+use crate::Needle;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+
+/// A boxed needle that can be shared between threads, for storing needles of different types
+/// together, e.g. in a `Vec<BoxedNeedle>`.
+pub type BoxedNeedle = Box<dyn Needle + Send + Sync>;
+
+/// A reference-counted needle that can be shared between threads.
+pub type ArcNeedle = Arc<dyn Needle + Send + Sync>;
+
+/// Combinator methods available on every `Needle`.
+///
+/// The returned combinators are themselves needles, so they can be chained,
+/// e.g. `a.and(b).or(c)`.
+pub trait NeedleExt: Needle + Sized {
+    /// Match only if both `self` and `other` match.
+    fn and<O>(self, other: O) -> AndNeedle<Self, O>
+    where
+        O: Needle,
+    {
+        AndNeedle::new(self, other)
+    }
+
+    /// Match if either `self` or `other` matches.
+    fn or<O>(self, other: O) -> OrNeedle<Self, O>
+    where
+        O: Needle,
+    {
+        OrNeedle::new(self, other)
+    }
+
+    /// Match only if `self` does not match.
+    fn not(self) -> NotNeedle<Self> {
+        NotNeedle::new(self)
+    }
+
+    /// Box this needle as a trait object.
+    fn into_boxed_needle(self) -> BoxedNeedle
+    where
+        Self: Send + Sync + 'static,
+    {
+        Box::new(self)
+    }
+
+    /// Box this needle as a trait object. This is the same as `into_boxed_needle()`.
+    fn boxed(self) -> BoxedNeedle
+    where
+        Self: Send + Sync + 'static,
+    {
+        self.into_boxed_needle()
+    }
+
+    /// Wrap this needle in an `Arc` as a trait object.
+    fn into_arc_needle(self) -> ArcNeedle
+    where
+        Self: Send + Sync + 'static,
+    {
+        Arc::new(self)
+    }
+}
+
+impl<T> NeedleExt for T where T: Needle + Sized {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{named_needle, StringMatch};
+
+    #[test]
+    fn test_boxed_needles() {
+        let needles: Vec<BoxedNeedle> = vec![
+            StringMatch::from("a").partial().into_boxed_needle(),
+            "exact".into_boxed_needle(),
+            named_needle("empty", |s: &str| s.is_empty()).into_boxed_needle(),
+        ];
+
+        assert!(needles[0].is_match("cat"));
+        assert!(!needles[0].is_match("dog"));
+        assert!(needles[1].is_match("exact"));
+        assert!(!needles[1].is_match("exactly"));
+        assert!(needles[2].is_match(""));
+        assert!(needles.iter().any(|n| n.is_match("")));
+        assert!(!needles.iter().any(|n| n.is_match("xyz")));
+
+        #[cfg(feature = "std")]
+        {
+            let needle = regex::Regex::new(r"^\d+$").unwrap().into_boxed_needle();
+            assert!(needle.is_match("123"));
+        }
+
+        let shared: ArcNeedle = StringMatch::from("b").into_arc_needle();
+        let cloned = Arc::clone(&shared);
+        assert!(shared.is_match("b"));
+        assert!(cloned.is_match("b"));
+        assert!(!cloned.is_match("a"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fluent_combinators() {
+        let needle = StringMatch::from("hello")
+            .partial()
+            .or(regex::Regex::new("world").unwrap())
+            .and(StringMatch::from("!").partial().not());
+        assert!(needle.is_match("hello there"));
+        assert!(needle.is_match("the world"));
+        assert!(!needle.is_match("hello!"));
+        assert!(!needle.is_match("goodbye"));
+
+        let boxed = needle.boxed();
+        assert!(boxed.is_match("hello world"));
+        assert!(!boxed.is_match("hello world!"));
+        let needles: Vec<BoxedNeedle> = vec![boxed, "x".boxed()];
+        assert!(needles.as_slice().is_match("x"));
+    }
+}
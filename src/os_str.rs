@@ -0,0 +1,53 @@
+use crate::{Needle, NeedleIter};
+use std::ffi::{OsStr, OsString};
+
+/// An `OsStr` needle matches a haystack that is exactly equal to its UTF-8 representation.
+///
+/// Strings that are not valid UTF-8 never match.
+impl Needle for &OsStr {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.to_str() == Some(haystack)
+    }
+}
+
+impl NeedleIter for &OsStr {}
+
+impl Needle for OsString {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.as_os_str().is_match(haystack)
+    }
+}
+
+impl NeedleIter for OsString {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_os_str() {
+        let needle = OsStr::new("hello");
+        assert!(needle.is_match("hello"));
+        assert!(!needle.is_match("Hello"));
+        assert!(!needle.is_match("hello world"));
+        assert!(needle.is_match_in(&mut vec!["a", "hello"].into_iter()));
+
+        let needle = OsString::from("héllo");
+        assert!(needle.is_match("héllo"));
+        assert!(!needle.is_match("hello"));
+        assert!(!OsString::new().is_match(" "));
+        assert!(OsString::new().is_match(""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_os_string() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let needle = OsString::from_vec(b"caf\xe9".to_vec());
+        assert!(needle.to_str().is_none());
+        assert!(!needle.is_match("caf\u{e9}"));
+        assert!(!needle.is_match("caf\u{fffd}"));
+        assert!(!needle.as_os_str().is_match("caf"));
+    }
+}
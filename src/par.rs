@@ -0,0 +1,77 @@
+use crate::Needle;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+/// Parallel versions of the `NeedleIter` methods, using rayon.
+///
+/// This is implemented for every needle that is `Sync`, which includes `StringMatch` and
+/// `Regex`.
+pub trait ParNeedleIter: Needle + Sync {
+    /// Return true if any of the haystacks match, searching them in parallel.
+    ///
+    /// The search stops early once a match is found, but which haystack matched first is
+    /// unspecified.
+    fn par_is_match_in<I>(&self, haystacks: I) -> bool
+    where
+        I: IntoParallelIterator,
+        I::Item: AsRef<str>,
+    {
+        haystacks.into_par_iter().any(|s| self.is_match(s.as_ref()))
+    }
+
+    /// Return the haystacks that match, testing them in parallel.
+    ///
+    /// The matches are returned in their original order.
+    fn par_filter_matching<I>(&self, haystacks: I) -> Vec<I::Item>
+    where
+        I: IntoParallelIterator,
+        I::Item: AsRef<str>,
+    {
+        haystacks.into_par_iter().filter(|s| self.is_match(s.as_ref())).collect()
+    }
+
+    /// Return the number of haystacks that match, testing them in parallel.
+    fn par_count_matches<I>(&self, haystacks: I) -> usize
+    where
+        I: IntoParallelIterator,
+        I::Item: AsRef<str>,
+    {
+        haystacks.into_par_iter().filter(|s| self.is_match(s.as_ref())).count()
+    }
+}
+
+impl<N> ParNeedleIter for N where N: Needle + Sync + ?Sized {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NeedleIter, StringMatch};
+    use regex::Regex;
+
+    fn haystacks() -> Vec<String> {
+        (0..10_000).map(|i| format!("line {} value {}", i, i * 7)).collect()
+    }
+
+    #[test]
+    fn test_par_stringmatch() {
+        let haystacks = haystacks();
+        let needle = StringMatch::from("value 7").partial();
+        let sequential: Vec<String> =
+            haystacks.iter().filter(|s| needle.is_match(s)).cloned().collect();
+        assert_eq!(needle.par_filter_matching(haystacks.clone()), sequential);
+        assert_eq!(needle.par_count_matches(&haystacks), sequential.len());
+        assert!(needle.par_is_match_in(&haystacks));
+        assert!(!StringMatch::from("missing").partial().par_is_match_in(&haystacks));
+        assert!(needle.par_filter_matching(Vec::<String>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_par_regex() {
+        let haystacks = haystacks();
+        let needle = Regex::new(r"value \d*99$").unwrap();
+        let refs: Vec<&str> = haystacks.iter().map(String::as_str).collect();
+        let sequential: Vec<&str> = needle.filter_matching(refs.clone().into_iter()).collect();
+        assert!(!sequential.is_empty());
+        assert_eq!(needle.par_filter_matching(refs.clone()), sequential);
+        assert_eq!(needle.par_is_match_in(refs.clone()), needle.is_match_in(&mut refs.into_iter()));
+    }
+}
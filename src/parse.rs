@@ -0,0 +1,474 @@
+#[cfg(feature = "unicode")]
+use crate::NormalizeForm;
+use crate::{StringMatch, StringMatchLength};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+impl fmt::Display for StringMatchLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StringMatchLength::Full => "full",
+            StringMatchLength::Partial => "partial",
+            StringMatchLength::Word => "word",
+            StringMatchLength::Prefix => "prefix",
+            StringMatchLength::Suffix => "suffix",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Displays as `[<length>, <case>, <options>] <text>`, e.g.
+/// `[partial, case-insensitive, trim, max-length=10] hello`.
+///
+/// Options that are not set are left out. The option flags are named after the builder
+/// methods that set them, such as `normalize-whitespace`, `line-by-line` and `min-length=3`.
+/// Word separators are written as `separators=...`, with commas, brackets, backslashes and
+/// whitespace escaped as `\u{..}`. The output can be parsed back into the same `StringMatch`
+/// using `str::parse()`.
+impl fmt::Display for StringMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let case = match self.case_sensitive {
+            true => "case-sensitive",
+            false => "case-insensitive",
+        };
+        write!(f, "[{}, {}", self.match_length, case)?;
+        if let Some(separators) = &self.separator_chars {
+            f.write_str(", separators=")?;
+            for c in separators.chars() {
+                match needs_escape(c) {
+                    true => write!(f, "{}", c.escape_unicode())?,
+                    false => write!(f, "{}", c)?,
+                }
+            }
+        }
+        let flags = [
+            (self.normalize_whitespace, "normalize-whitespace"),
+            (self.trim, "trim"),
+            (self.trim_end, "trim-end"),
+            #[cfg(feature = "unicode")]
+            (self.strip_diacritics, "strip-diacritics"),
+            #[cfg(feature = "unicode")]
+            (self.normalize_unicode == NormalizeForm::Nfc, "nfc"),
+            #[cfg(feature = "unicode")]
+            (self.normalize_unicode == NormalizeForm::Nfd, "nfd"),
+            (self.multiline, "line-by-line"),
+        ];
+        for (_, flag) in flags.iter().filter(|(set, _)| *set) {
+            write!(f, ", {}", flag)?;
+        }
+        if let Some(n) = self.min_length {
+            write!(f, ", min-length={}", n)?;
+        }
+        if let Some(n) = self.max_length {
+            write!(f, ", max-length={}", n)?;
+        }
+        let flags = [
+            (self.ascii_only, "ascii-only"),
+            (self.ignore_punctuation, "ignore-punctuation"),
+            (self.ignore_numbers, "ignore-numbers"),
+            (self.wildcard, "wildcard"),
+            (self.collapse_whitespace, "collapse-whitespace"),
+        ];
+        for (_, flag) in flags.iter().filter(|(set, _)| *set) {
+            write!(f, ", {}", flag)?;
+        }
+        write!(f, "] {}", self.text)
+    }
+}
+
+/// Return true if `c` must be escaped in the value of a flag.
+fn needs_escape(c: char) -> bool {
+    matches!(c, ',' | '[' | ']' | '\\') || c.is_whitespace() || c.is_control()
+}
+
+/// Replace each `\u{..}` escape in the value of a flag with the character it represents.
+fn unescape(value: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(i) = rest.find('\\') {
+        unescaped.push_str(&rest[..i]);
+        let (hex, after) = rest[i..].strip_prefix("\\u{")?.split_once('}')?;
+        unescaped.push(char::from_u32(u32::from_str_radix(hex, 16).ok()?)?);
+        rest = after;
+    }
+    unescaped.push_str(rest);
+    Some(unescaped)
+}
+
+/// The error returned when parsing a `StringMatch` from a string fails.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum StringMatchParseError {
+    /// The flags section was opened with `[` but never closed.
+    UnclosedBracket,
+    /// The flags section contained an unrecognised flag.
+    UnknownFlag(String),
+    /// The flags section specified more than one match length or case sensitivity, or the
+    /// same option more than once.
+    ConflictingFlags(String),
+    /// The flags section contained a flag with an invalid value, such as `min-length=x`.
+    InvalidFlagValue(String),
+    /// The environment variable passed to `StringMatch::from_env_with_flags()` was not set
+    /// or was not valid unicode.
+    #[cfg(feature = "std")]
+    EnvVar(std::env::VarError),
+}
+
+impl fmt::Display for StringMatchParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StringMatchParseError::UnclosedBracket => write!(f, "unclosed '[' in flags section"),
+            StringMatchParseError::UnknownFlag(flag) => write!(f, "unknown flag '{}'", flag),
+            StringMatchParseError::ConflictingFlags(flag) => {
+                write!(f, "flag '{}' conflicts with an earlier flag", flag)
+            }
+            StringMatchParseError::InvalidFlagValue(flag) => {
+                write!(f, "invalid value in flag '{}'", flag)
+            }
+            #[cfg(feature = "std")]
+            StringMatchParseError::EnvVar(e) => write!(f, "environment variable error: {}", e),
+        }
+    }
+}
+
+impl core::error::Error for StringMatchParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            StringMatchParseError::EnvVar(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::env::VarError> for StringMatchParseError {
+    fn from(e: std::env::VarError) -> Self {
+        StringMatchParseError::EnvVar(e)
+    }
+}
+
+/// Parse a pattern with optional leading flags, e.g. `[partial,ci]hello`, `[full]Hello`,
+/// or just `Hello`.
+///
+/// The flags are a comma-separated list of `full`, `partial`, `word`, `prefix`, `suffix`,
+/// `ci` (case-insensitive) and `cs` (case-sensitive), along with the option flags produced
+/// by `Display`. The long forms `case-insensitive` and `case-sensitive` are also accepted.
+/// Flags may appear in any order and any of them may be omitted, in which case the default
+/// (full, case-sensitive, with no other options) is used. A single space after the closing
+/// `]` is optional.
+///
+/// Text that itself starts with `[` must be preceded by a flags section, which may be empty,
+/// e.g. `[][not a flag]`.
+impl FromStr for StringMatch {
+    type Err = StringMatchParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (flags, text) = match s.strip_prefix('[') {
+            Some(rest) => {
+                let (flags, text) =
+                    rest.split_once(']').ok_or(StringMatchParseError::UnclosedBracket)?;
+                (flags, text.strip_prefix(' ').unwrap_or(text))
+            }
+            None => ("", s),
+        };
+
+        let mut sm = StringMatch::new(text);
+        // The flags seen so far, so that each setting can only be given once.
+        let mut seen = Vec::new();
+        for flag in flags.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let (name, value) = match flag.split_once('=') {
+                Some((name, value)) => (name.trim_end(), Some(value.trim_start())),
+                None => (flag, None),
+            };
+            let invalid = || StringMatchParseError::InvalidFlagValue(flag.to_string());
+            let setting = match (name, value) {
+                ("full", None) => {
+                    sm.match_length = StringMatchLength::Full;
+                    "length"
+                }
+                ("partial", None) => {
+                    sm.match_length = StringMatchLength::Partial;
+                    "length"
+                }
+                ("word", None) => {
+                    sm.match_length = StringMatchLength::Word;
+                    "length"
+                }
+                ("prefix", None) => {
+                    sm.match_length = StringMatchLength::Prefix;
+                    "length"
+                }
+                ("suffix", None) => {
+                    sm.match_length = StringMatchLength::Suffix;
+                    "length"
+                }
+                ("cs" | "case-sensitive", None) => {
+                    sm.case_sensitive = true;
+                    "case"
+                }
+                ("ci" | "case-insensitive", None) => {
+                    sm.case_sensitive = false;
+                    "case"
+                }
+                ("separators", Some(value)) => {
+                    sm.separator_chars = Some(unescape(value).ok_or_else(invalid)?);
+                    name
+                }
+                ("min-length", Some(value)) => {
+                    sm.min_length = Some(value.parse().map_err(|_| invalid())?);
+                    name
+                }
+                ("max-length", Some(value)) => {
+                    sm.max_length = Some(value.parse().map_err(|_| invalid())?);
+                    name
+                }
+                #[cfg(feature = "unicode")]
+                ("nfc", None) => {
+                    sm.normalize_unicode = NormalizeForm::Nfc;
+                    "normalize-unicode"
+                }
+                #[cfg(feature = "unicode")]
+                ("nfd", None) => {
+                    sm.normalize_unicode = NormalizeForm::Nfd;
+                    "normalize-unicode"
+                }
+                (_, None) => {
+                    let option = match name {
+                        "normalize-whitespace" => &mut sm.normalize_whitespace,
+                        "trim" => &mut sm.trim,
+                        "trim-end" => &mut sm.trim_end,
+                        #[cfg(feature = "unicode")]
+                        "strip-diacritics" => &mut sm.strip_diacritics,
+                        "line-by-line" => &mut sm.multiline,
+                        "ascii-only" => &mut sm.ascii_only,
+                        "ignore-punctuation" => &mut sm.ignore_punctuation,
+                        "ignore-numbers" => &mut sm.ignore_numbers,
+                        "wildcard" => &mut sm.wildcard,
+                        "collapse-whitespace" => &mut sm.collapse_whitespace,
+                        _ => return Err(StringMatchParseError::UnknownFlag(flag.to_string())),
+                    };
+                    *option = true;
+                    name
+                }
+                _ => return Err(StringMatchParseError::UnknownFlag(flag.to_string())),
+            };
+            if seen.contains(&setting) {
+                return Err(StringMatchParseError::ConflictingFlags(flag.to_string()));
+            }
+            seen.push(setting);
+        }
+        Ok(sm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(StringMatchLength::Full.to_string(), "full");
+        assert_eq!(StringMatchLength::Partial.to_string(), "partial");
+        assert_eq!(StringMatchLength::Word.to_string(), "word");
+        assert_eq!(StringMatchLength::Prefix.to_string(), "prefix");
+        assert_eq!(StringMatchLength::Suffix.to_string(), "suffix");
+
+        assert_eq!(StringMatch::new("hello").to_string(), "[full, case-sensitive] hello");
+        assert_eq!(
+            StringMatch::new("hello").partial().case_insensitive().to_string(),
+            "[partial, case-insensitive] hello"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let lengths = [
+            StringMatchLength::Full,
+            StringMatchLength::Partial,
+            StringMatchLength::Word,
+            StringMatchLength::Prefix,
+            StringMatchLength::Suffix,
+        ];
+        for text in ["hello", "", " leading space", "[brackets] inside", "héllo wörld"] {
+            for length in &lengths {
+                for case_sensitive in [true, false] {
+                    let sm = StringMatch {
+                        text: text.to_string(),
+                        match_length: length.clone(),
+                        case_sensitive,
+                        ..StringMatch::default()
+                    };
+                    assert_eq!(sm.to_string().parse::<StringMatch>().unwrap(), sm);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_options() {
+        let base = StringMatch::new("a, b]");
+        let needles = vec![
+            base.clone().word_with_separators(", ]\t\\[x"),
+            base.clone().word_with_separators(""),
+            base.clone().partial().normalize_whitespace(),
+            base.clone().trim(),
+            base.clone().trim_end_before_match(),
+            base.clone().line_by_line(),
+            base.clone().min_length(0),
+            base.clone().max_length(10),
+            base.clone().min_length(2).max_length(5),
+            base.clone().ascii_only(),
+            base.clone().ignore_punctuation(),
+            base.clone().ignore_numbers(),
+            base.clone().collapse_whitespace(),
+            StringMatch::from_wildcard("a*?").partial(),
+            base.clone()
+                .suffix()
+                .case_insensitive()
+                .trim()
+                .normalize_whitespace()
+                .line_by_line()
+                .max_length(3)
+                .ascii_only()
+                .ignore_punctuation()
+                .ignore_numbers()
+                .wildcard()
+                .collapse_whitespace(),
+        ];
+        #[cfg(feature = "unicode")]
+        let needles = [
+            needles,
+            vec![
+                base.clone().strip_diacritics(),
+                base.clone().nfc(),
+                base.clone().nfd().strip_diacritics().word_with_separators("-"),
+            ],
+        ]
+        .concat();
+        for sm in &needles {
+            assert_eq!(&sm.to_string().parse::<StringMatch>().unwrap(), sm, "{}", sm);
+        }
+
+        assert_eq!(
+            base.clone().partial().trim().max_length(10).to_string(),
+            "[partial, case-sensitive, trim, max-length=10] a, b]"
+        );
+        assert_eq!(
+            base.clone().word_with_separators(", ").to_string(),
+            "[word, case-sensitive, separators=\\u{2c}\\u{20}] a, b]"
+        );
+        assert_eq!(
+            "[separators=a\\u{5d}b,word]x".parse::<StringMatch>().unwrap(),
+            StringMatch::new("x").word_with_separators("a]b")
+        );
+        assert_eq!(
+            "[trim,min-length = 2]x".parse::<StringMatch>().unwrap(),
+            StringMatch::new("x").trim().min_length(2)
+        );
+
+        assert_eq!(
+            "[min-length=x]a".parse::<StringMatch>(),
+            Err(StringMatchParseError::InvalidFlagValue("min-length=x".to_string()))
+        );
+        assert_eq!(
+            "[separators=\\u{zz}]a".parse::<StringMatch>(),
+            Err(StringMatchParseError::InvalidFlagValue("separators=\\u{zz}".to_string()))
+        );
+        assert_eq!(
+            "[trim=1]a".parse::<StringMatch>(),
+            Err(StringMatchParseError::UnknownFlag("trim=1".to_string()))
+        );
+        assert_eq!(
+            "[max-length]a".parse::<StringMatch>(),
+            Err(StringMatchParseError::UnknownFlag("max-length".to_string()))
+        );
+        assert_eq!(
+            "[trim,ci,trim]a".parse::<StringMatch>(),
+            Err(StringMatchParseError::ConflictingFlags("trim".to_string()))
+        );
+        assert_eq!(
+            "[nfc,nfd]a".parse::<StringMatch>(),
+            match cfg!(feature = "unicode") {
+                true => Err(StringMatchParseError::ConflictingFlags("nfd".to_string())),
+                false => Err(StringMatchParseError::UnknownFlag("nfc".to_string())),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_dsl() {
+        let lengths = [
+            ("full", StringMatchLength::Full),
+            ("partial", StringMatchLength::Partial),
+            ("word", StringMatchLength::Word),
+            ("prefix", StringMatchLength::Prefix),
+            ("suffix", StringMatchLength::Suffix),
+        ];
+        for (length_flag, length) in &lengths {
+            for (case_flag, case_sensitive) in [("ci", false), ("cs", true)] {
+                let expected = StringMatch {
+                    text: "hello".to_string(),
+                    match_length: length.clone(),
+                    case_sensitive,
+                    ..StringMatch::default()
+                };
+                let dsl = format!("[{},{}]hello", length_flag, case_flag);
+                assert_eq!(dsl.parse::<StringMatch>().unwrap(), expected);
+                let dsl = format!("[{}, {}]hello", case_flag, length_flag);
+                assert_eq!(dsl.parse::<StringMatch>().unwrap(), expected);
+            }
+            let dsl = format!("[{}]hello", length_flag);
+            assert_eq!(dsl.parse::<StringMatch>().unwrap().match_length(), length);
+        }
+
+        // No flags at all.
+        assert_eq!("Hello".parse::<StringMatch>().unwrap(), StringMatch::new("Hello"));
+        assert_eq!("".parse::<StringMatch>().unwrap(), StringMatch::new(""));
+        assert_eq!("a [b]".parse::<StringMatch>().unwrap(), StringMatch::new("a [b]"));
+        assert_eq!(
+            "[ci]Hello".parse::<StringMatch>().unwrap(),
+            StringMatch::new("Hello").case_insensitive()
+        );
+        assert_eq!("[][x]".parse::<StringMatch>().unwrap(), StringMatch::new("[x]"));
+
+        assert_eq!("[ci".parse::<StringMatch>(), Err(StringMatchParseError::UnclosedBracket));
+        assert_eq!(
+            "[partial,xx]hello".parse::<StringMatch>(),
+            Err(StringMatchParseError::UnknownFlag("xx".to_string()))
+        );
+        assert_eq!(
+            "[ci,cs]hello".parse::<StringMatch>(),
+            Err(StringMatchParseError::ConflictingFlags("cs".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("[] hello".parse::<StringMatch>().unwrap(), StringMatch::new("hello"));
+        assert_eq!("[word]hi".parse::<StringMatch>().unwrap(), StringMatch::new("hi").word());
+        assert_eq!(
+            "[case-insensitive, suffix] hi".parse::<StringMatch>().unwrap(),
+            StringMatch::new("hi").suffix().case_insensitive()
+        );
+
+        assert_eq!(
+            "[full hello".parse::<StringMatch>(),
+            Err(StringMatchParseError::UnclosedBracket)
+        );
+        assert_eq!(
+            "[fuzzy] hello".parse::<StringMatch>(),
+            Err(StringMatchParseError::UnknownFlag("fuzzy".to_string()))
+        );
+        assert_eq!(
+            "[full, partial] hello".parse::<StringMatch>(),
+            Err(StringMatchParseError::ConflictingFlags("partial".to_string()))
+        );
+        assert_eq!(
+            StringMatchParseError::UnknownFlag("fuzzy".to_string()).to_string(),
+            "unknown flag 'fuzzy'"
+        );
+    }
+}
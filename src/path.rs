@@ -0,0 +1,182 @@
+use crate::{Needle, NeedleIter, StringMatch};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A path needle matches a haystack that is exactly equal to the path's UTF-8 representation.
+///
+/// Paths that are not valid UTF-8 never match.
+impl Needle for &Path {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.to_str() == Some(haystack)
+    }
+}
+
+impl NeedleIter for &Path {}
+
+impl Needle for PathBuf {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.as_path().is_match(haystack)
+    }
+}
+
+impl NeedleIter for PathBuf {}
+
+/// A `StringMatch` applied to paths.
+///
+/// Use `is_match_path()` to match a `Path` directly. Paths that are not valid UTF-8 never
+/// match. With `basename_only()`, only the final component of the path is matched, and a
+/// path with no final component (such as `/` or `..`) never matches.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PathNeedle {
+    inner: StringMatch,
+    basename_only: bool,
+}
+
+impl PathNeedle {
+    pub fn new(inner: StringMatch) -> Self {
+        Self {
+            inner,
+            basename_only: false,
+        }
+    }
+
+    /// Match only the file name of the path, as returned by `Path::file_name()`.
+    pub fn basename_only(mut self) -> Self {
+        self.basename_only = true;
+        self
+    }
+
+    pub fn is_basename_only(&self) -> bool {
+        self.basename_only
+    }
+
+    /// The wrapped `StringMatch`.
+    pub fn inner(&self) -> &StringMatch {
+        &self.inner
+    }
+
+    /// Test whether the path matches. Paths that are not valid UTF-8 never match.
+    pub fn is_match_path<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        let s = match self.basename_only {
+            true => path.file_name().and_then(|name| name.to_str()),
+            false => path.to_str(),
+        };
+        s.is_some_and(|s| self.inner.is_match(s))
+    }
+}
+
+impl From<StringMatch> for PathNeedle {
+    fn from(inner: StringMatch) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl fmt::Display for PathNeedle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.basename_only {
+            true => write!(f, "<basename> {}", self.inner),
+            false => write!(f, "{}", self.inner),
+        }
+    }
+}
+
+impl Needle for PathNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.is_match_path(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.inner.complexity_estimate()
+    }
+}
+
+impl NeedleIter for PathNeedle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_needle_impls() {
+        let path = Path::new("src/lib.rs");
+        assert!(path.is_match("src/lib.rs"));
+        assert!(!path.is_match("src/lib"));
+        assert!(!path.is_match("SRC/LIB.RS"));
+        assert!(path.is_match_in(&mut vec!["a", "src/lib.rs"].into_iter()));
+
+        let buf = PathBuf::from("données/résumé.txt");
+        assert!(buf.is_match("données/résumé.txt"));
+        assert!(!buf.is_match("donnees/resume.txt"));
+
+        let windows = PathBuf::from(r"C:\Users\me\file.txt");
+        assert!(windows.is_match(r"C:\Users\me\file.txt"));
+        assert!(!windows.is_match("C:/Users/me/file.txt"));
+    }
+
+    #[test]
+    fn test_path_needle() {
+        let needle = PathNeedle::new(StringMatch::from("lib").partial());
+        assert!(!needle.is_basename_only());
+        assert!(needle.is_match_path("src/lib.rs"));
+        assert!(needle.is_match_path(Path::new("lib/main.rs")));
+        assert!(needle.is_match("lib/main.rs"));
+        assert_eq!(needle.complexity_estimate(), needle.inner().complexity_estimate());
+        assert!(!needle.is_match_path("src/main.rs"));
+
+        let needle = needle.basename_only();
+        assert!(needle.is_basename_only());
+        assert!(needle.is_match_path("src/lib.rs"));
+        assert!(!needle.is_match_path("lib/main.rs"));
+        assert!(!needle.is_match_path("/"));
+
+        let needle: PathNeedle = StringMatch::from("RÉSUMÉ.TXT").case_insensitive().into();
+        let needle = needle.basename_only();
+        assert!(needle.is_match_path(PathBuf::from("données/résumé.txt")));
+        assert_eq!(needle.inner(), &StringMatch::from("RÉSUMÉ.TXT").case_insensitive());
+        assert_eq!(needle.to_string(), format!("<basename> {}", needle.inner()));
+
+        let needle = PathNeedle::new(StringMatch::from("me").word_with_separators(r"\"));
+        assert!(needle.is_match_path(r"C:\Users\me\file.txt"));
+        assert!(!needle.is_match_path("C:/Users/me/file.txt"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_path_needle_windows_basename() {
+        let needle = PathNeedle::new(StringMatch::from("file.txt")).basename_only();
+        assert!(needle.is_match_path(r"C:\Users\me\file.txt"));
+        assert!(needle.is_match_path("C:/Users/me/file.txt"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_needle_windows_basename() {
+        // Backslashes are not separators on unix, so the whole string is the file name.
+        let needle = PathNeedle::new(StringMatch::from("file.txt").suffix()).basename_only();
+        assert!(needle.is_match_path(r"C:\Users\me\file.txt"));
+        assert!(!PathNeedle::new(StringMatch::from("file.txt"))
+            .basename_only()
+            .is_match_path(r"C:\Users\me\file.txt"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_path() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"dir/caf\xe9.txt"));
+        assert!(path.to_str().is_none());
+        assert!(!path.is_match("dir/caf\u{e9}.txt"));
+        assert!(!path.to_path_buf().is_match("dir/caf\u{e9}.txt"));
+
+        let always = PathNeedle::new(StringMatch::from("").partial());
+        assert!(always.is_match_path("dir/any.txt"));
+        assert!(!always.is_match_path(path));
+        assert!(!always.clone().basename_only().is_match_path(path));
+        let utf8_basename = Path::new(OsStr::from_bytes(b"caf\xe9/name.txt"));
+        assert!(always.clone().basename_only().is_match_path(utf8_basename));
+        assert!(!always.is_match_path(utf8_basename));
+    }
+}
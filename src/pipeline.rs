@@ -0,0 +1,143 @@
+use crate::{Needle, NeedleIter};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+type Stage = Box<dyn Fn(String) -> String + Send + Sync>;
+
+/// A needle that passes each haystack through a sequence of transformations before
+/// matching it.
+///
+/// Stages are applied in the order they were added with `then()`, e.g.
+/// `NeedlePipeline::new(needle).then(trim).then(lowercase)`.
+pub struct NeedlePipeline<N> {
+    stages: Vec<Stage>,
+    needle: N,
+}
+
+impl<N> NeedlePipeline<N>
+where
+    N: Needle,
+{
+    /// Create a pipeline with no stages, which behaves the same as `needle`.
+    pub fn new(needle: N) -> Self {
+        Self {
+            stages: Vec::new(),
+            needle,
+        }
+    }
+
+    /// Append a transformation to the end of the pipeline.
+    pub fn then<F>(mut self, f: F) -> Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.stages.push(Box::new(f));
+        self
+    }
+
+    /// The number of transformations.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// The needle that the transformed haystack is matched against.
+    pub fn needle(&self) -> &N {
+        &self.needle
+    }
+
+    /// Apply all of the transformations to the haystack without matching it.
+    pub fn apply(&self, haystack: &str) -> String {
+        self.stages.iter().fold(haystack.to_string(), |text, stage| stage(text))
+    }
+}
+
+impl<N> fmt::Debug for NeedlePipeline<N>
+where
+    N: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NeedlePipeline")
+            .field("stages", &self.stages.len())
+            .field("needle", &self.needle)
+            .finish()
+    }
+}
+
+impl<N> Needle for NeedlePipeline<N>
+where
+    N: Needle,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        match self.stages.is_empty() {
+            true => self.needle.is_match(haystack),
+            false => self.needle.is_match(&self.apply(haystack)),
+        }
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.needle.complexity_estimate()
+    }
+}
+
+impl<N> NeedleIter for NeedlePipeline<N> where N: Needle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StringMatch;
+
+    fn trim(s: String) -> String {
+        s.trim().to_string()
+    }
+
+    fn strip_punctuation(s: String) -> String {
+        s.chars().filter(|c| !c.is_ascii_punctuation()).collect()
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let pipeline = NeedlePipeline::new(StringMatch::from("hello"));
+        assert!(pipeline.is_empty());
+        assert!(pipeline.is_match("hello"));
+        assert!(!pipeline.is_match(" hello"));
+        assert_eq!(pipeline.complexity_estimate(), 1);
+
+        let pipeline = NeedlePipeline::new(StringMatch::from("hello")).then(trim);
+        assert_eq!(pipeline.len(), 1);
+        assert!(pipeline.is_match(" hello\n"));
+        assert!(!pipeline.is_match(" Hello\n"));
+
+        let pipeline = NeedlePipeline::new(StringMatch::from("hello world"))
+            .then(trim)
+            .then(|s| s.to_lowercase())
+            .then(strip_punctuation);
+        assert_eq!(pipeline.len(), 3);
+        assert!(pipeline.is_match("  Hello, World!"));
+        assert_eq!(pipeline.apply("  Hello, World!"), "hello world");
+        assert!(pipeline.is_match_in(&mut vec!["nope", "HELLO WORLD."].into_iter()));
+        assert_eq!(pipeline.needle().text(), "hello world");
+    }
+
+    #[test]
+    fn test_pipeline_order() {
+        let trim_first =
+            NeedlePipeline::new(StringMatch::from("hi")).then(trim).then(strip_punctuation);
+        let strip_first =
+            NeedlePipeline::new(StringMatch::from("hi")).then(strip_punctuation).then(trim);
+        assert!(!trim_first.is_match("hi !"));
+        assert!(strip_first.is_match("hi !"));
+    }
+
+    #[test]
+    fn test_pipeline_debug() {
+        let pipeline = NeedlePipeline::new(StringMatch::from("a")).then(trim).then(trim);
+        let debug = format!("{:?}", pipeline);
+        assert!(debug.starts_with("NeedlePipeline { stages: 2, needle: StringMatch {"));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{Needle, NeedleIter, StringMatch};
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::fmt;
+
+type Preprocessor = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// A `StringMatch` that transforms each haystack before matching it.
+///
+/// Created by `StringMatch::preprocess()`. The transform is applied to the haystack only,
+/// not to the needle text.
+#[derive(Clone)]
+pub struct PreprocessedMatch {
+    inner: StringMatch,
+    preprocessor: Preprocessor,
+}
+
+impl PreprocessedMatch {
+    /// The wrapped `StringMatch`.
+    pub fn inner(&self) -> &StringMatch {
+        &self.inner
+    }
+
+    /// Apply the preprocessor to the haystack without matching it.
+    pub fn apply(&self, haystack: &str) -> String {
+        (self.preprocessor)(haystack)
+    }
+}
+
+impl fmt::Debug for PreprocessedMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreprocessedMatch")
+            .field("inner", &self.inner)
+            .field("preprocessor", &format_args!("<preprocessor>"))
+            .finish()
+    }
+}
+
+impl fmt::Display for PreprocessedMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<preprocessor> {}", self.inner)
+    }
+}
+
+impl Needle for PreprocessedMatch {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.inner.is_match(&self.apply(haystack))
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.inner.complexity_estimate()
+    }
+}
+
+impl NeedleIter for PreprocessedMatch {}
+
+impl StringMatch {
+    /// Apply `f` to each haystack before matching it.
+    ///
+    /// This is useful for normalization such as stripping punctuation. The needle text is
+    /// used as-is.
+    pub fn preprocess<F>(self, f: F) -> PreprocessedMatch
+    where
+        F: Fn(&str) -> String + Clone + Send + Sync + 'static,
+    {
+        PreprocessedMatch {
+            inner: self,
+            preprocessor: Arc::new(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preprocess() {
+        let lowered = StringMatch::from("hello").preprocess(|s: &str| s.to_lowercase());
+        let insensitive = StringMatch::from("hello").case_insensitive();
+        for haystack in ["hello", "HELLO", "Hello", "hello!", "HELLO world", ""] {
+            assert_eq!(lowered.is_match(haystack), insensitive.is_match(haystack));
+        }
+
+        // The needle is not preprocessed.
+        let upper = StringMatch::from("HELLO").preprocess(|s: &str| s.to_lowercase());
+        assert!(!upper.is_match("HELLO"));
+        assert!(!upper.is_match("hello"));
+
+        let stripped = StringMatch::from("hello world")
+            .preprocess(|s: &str| s.chars().filter(|c| !c.is_ascii_punctuation()).collect());
+        assert!(stripped.is_match("hello, world!"));
+        assert_eq!(stripped.apply("a.b"), "ab");
+        assert_eq!(stripped.inner().text(), "hello world");
+        assert!(stripped.is_match_in(&mut vec!["hi", "hello world."].into_iter()));
+    }
+
+    #[test]
+    fn test_preprocess_display() {
+        let needle = StringMatch::from("hello").partial().preprocess(|s: &str| s.to_string());
+        assert_eq!(needle.complexity_estimate(), needle.inner().complexity_estimate());
+        assert_eq!(needle.to_string(), "<preprocessor> [partial, case-sensitive] hello");
+        assert!(format!("{:?}", needle).contains("preprocessor: <preprocessor>"));
+    }
+}
@@ -0,0 +1,589 @@
+use crate::{StringMatch, StringMatchLength};
+use regex::Regex;
+
+/// A regex that never matches, for needles that cannot match any trimmed haystack.
+const NEVER: &str = r"[^\s\S]";
+
+/// How whitespace is handled at the edges of the needle and haystack.
+struct Edges {
+    /// The needle starts with whitespace and the haystack is trimmed at the start.
+    lead: bool,
+    /// The needle ends with whitespace and the haystack is trimmed at the end.
+    trail: bool,
+    trim_start: bool,
+    trim_end: bool,
+    /// Runs of whitespace in the haystack are replaced by a single space.
+    normalized: bool,
+    /// The regex for any character within a line.
+    any: &'static str,
+    /// The regex for a whitespace character within a line.
+    ws: &'static str,
+}
+
+impl Edges {
+    /// The pattern to put before the needle text in an unanchored match.
+    fn before(&self) -> String {
+        match self.lead {
+            true => format!(r"\S{}*", self.any),
+            false => String::new(),
+        }
+    }
+
+    /// The pattern to put after the needle text in an unanchored match.
+    fn after(&self) -> String {
+        match self.trail {
+            true => format!(r"{}*\S", self.any),
+            false => String::new(),
+        }
+    }
+}
+
+/// Return a regex that matches any of `patterns`, or nothing if there are none.
+fn alternation(patterns: impl IntoIterator<Item = Option<String>>) -> String {
+    let patterns: Vec<String> = patterns.into_iter().flatten().collect();
+    match patterns.is_empty() {
+        true => String::from(NEVER),
+        false => format!("(?:{})", patterns.join("|")),
+    }
+}
+
+/// The regex for an empty `Word` needle, which matches where two separators are adjacent,
+/// or at a separator at the start or end of the haystack, or in an empty haystack.
+fn empty_word_pattern(solid: Option<String>, spacing: Option<String>, edges: &Edges) -> String {
+    let (any, ws) = (edges.any, edges.ws);
+    let leading = match edges.trim_start {
+        true => format!("{ws}*"),
+        false => String::new(),
+    };
+    let trailing = match edges.trim_end {
+        true => format!("{ws}*"),
+        false => String::new(),
+    };
+    let mut patterns = vec![Some(format!("^{trailing}$"))];
+    if let Some(solid) = &solid {
+        patterns.push(Some(format!("^{leading}{solid}")));
+        patterns.push(Some(format!("{solid}{trailing}$")));
+        patterns.push(Some(format!("{solid}{solid}")));
+    }
+    if let Some(spacing) = &spacing {
+        // Whitespace that would be trimmed must have something else beyond it.
+        let before = match edges.trim_start {
+            true => format!(r"\S{any}*"),
+            false => String::new(),
+        };
+        let after = match edges.trim_end {
+            true => format!(r"{any}*\S"),
+            false => String::new(),
+        };
+        patterns.push(Some(format!("^{spacing}{after}")).filter(|_| !edges.trim_start));
+        patterns.push(Some(format!("{before}{spacing}$")).filter(|_| !edges.trim_end));
+        if let Some(solid) = &solid {
+            patterns.push(Some(format!("{solid}{spacing}|{spacing}{solid}")));
+        }
+        if !edges.normalized {
+            patterns.push(Some(format!("{before}{spacing}{spacing}{after}")));
+        }
+    }
+    alternation(patterns)
+}
+
+impl StringMatch {
+    /// Convert to a regex pattern that matches the same haystacks, for use with tools such
+    /// as grep or ripgrep.
+    ///
+    /// `Word` mode with Unicode word boundaries (the `unicode` feature without custom
+    /// separators) is approximated using `\b`, which differs for scripts written without
+    /// spaces. The `strip_diacritics()`, `normalize_unicode()`, `min_length()`, `max_length()`,
+    /// `ascii_only()`, `ignore_punctuation()` and `ignore_numbers()` options have no regex
+    /// equivalent and are ignored. For a `wildcard()` needle, `*` becomes `.*` and `?`
+    /// becomes `.`.
+    ///
+    /// Since `trim()` and `trim_end_before_match()` trim the haystack but not the needle,
+    /// whitespace at the trimmed edges of the needle must match whitespace inside the
+    /// haystack, and a `Full` needle with such whitespace gives a regex that never matches.
+    pub fn to_regex_pattern(&self) -> String {
+        let mut flags = String::new();
+        if !self.case_sensitive {
+            flags.push('i');
+        }
+        if self.multiline {
+            flags.push_str("mR");
+        } else if self.wildcard {
+            // Wildcards match newlines unless each line is matched separately.
+            flags.push('s');
+        }
+        let ws = match self.multiline {
+            // The same as `[^\S\r\n]`, which is much slower to compile case-insensitively.
+            true => r"[\s&&[^\r\n]]",
+            false => r"\s",
+        };
+        let source = match self.collapse_whitespace {
+            true => self.text.trim(),
+            false => self.text.as_str(),
+        };
+        let text = match self.normalize_whitespace || self.collapse_whitespace {
+            true => {
+                let mut text = String::new();
+                let mut in_whitespace = false;
+                for c in source.chars() {
+                    match c.is_whitespace() {
+                        true if in_whitespace => {}
+                        true => text.push_str(&format!("{ws}+")),
+                        false => text.push_str(&self.escape_char(c)),
+                    }
+                    in_whitespace = c.is_whitespace();
+                }
+                text
+            }
+            false => source.chars().map(|c| self.escape_char(c)).collect(),
+        };
+
+        // A trimmed haystack has no whitespace at either end, so whitespace at the edges of
+        // the needle must be matched inside the haystack, with some other character beyond it.
+        let any = match self.multiline {
+            true => ".",
+            false => "(?s:.)",
+        };
+        let trim_start = self.trim || self.collapse_whitespace;
+        let trim_end = trim_start || self.trim_end;
+        let edges = Edges {
+            lead: trim_start && source.starts_with(char::is_whitespace),
+            trail: trim_end && source.ends_with(char::is_whitespace),
+            trim_start,
+            trim_end,
+            normalized: self.normalize_whitespace || self.collapse_whitespace,
+            any,
+            ws,
+        };
+
+        let start = match trim_start {
+            true => format!("^{ws}*"),
+            false => String::from("^"),
+        };
+        let end = match trim_end {
+            true => format!("{ws}*$"),
+            false => String::from("$"),
+        };
+        let (before, after) = (edges.before(), edges.after());
+        let pattern = match self.match_length {
+            StringMatchLength::Full if edges.lead || edges.trail => String::from(NEVER),
+            StringMatchLength::Full => format!("{start}{text}{end}"),
+            StringMatchLength::Partial => format!("{before}{text}{after}"),
+            StringMatchLength::Prefix if edges.lead => String::from(NEVER),
+            StringMatchLength::Prefix => format!("{start}{text}{after}"),
+            StringMatchLength::Suffix if edges.trail => String::from(NEVER),
+            StringMatchLength::Suffix => format!("{before}{text}{end}"),
+            StringMatchLength::Word => self.word_pattern(&text, source, &start, &end, &edges),
+        };
+
+        match flags.is_empty() {
+            true => pattern,
+            false => format!("(?{flags}){pattern}"),
+        }
+    }
+
+    /// Escape `c` for use in a regex, translating the wildcards of a `wildcard()` needle.
+    fn escape_char(&self, c: char) -> String {
+        match c {
+            '*' if self.wildcard => String::from(".*"),
+            '?' if self.wildcard => String::from("."),
+            c => regex::escape(c.encode_utf8(&mut [0; 4])),
+        }
+    }
+
+    fn word_pattern(
+        &self,
+        text: &str,
+        source: &str,
+        start: &str,
+        end: &str,
+        edges: &Edges,
+    ) -> String {
+        // Wildcard needles always use separators to find words.
+        #[cfg(feature = "unicode")]
+        if self.separator_chars.is_none() && !self.wildcard {
+            use unicode_segmentation::UnicodeSegmentation;
+
+            if self.text.unicode_words().next().is_none() {
+                return String::from(r"^\W*$");
+            }
+            // `\b` only marks a boundary next to a word character.
+            let boundary = |c: Option<char>| match c.is_some_and(char::is_alphanumeric) {
+                true => r"\b",
+                false => "",
+            };
+            let start = boundary(source.chars().next());
+            let end = boundary(source.chars().next_back());
+            return format!("{}{start}{text}{end}{}", edges.before(), edges.after());
+        }
+
+        let separators = self.separator_chars.as_deref().unwrap_or(" ");
+        if separators.is_empty() {
+            return match edges.lead || edges.trail {
+                true => String::from(NEVER),
+                false => format!("{start}{text}{end}"),
+            };
+        }
+        let any = edges.any;
+        // Separators other than whitespace are never trimmed or normalized.
+        let solid: String = separators.chars().filter(|c| !c.is_whitespace()).collect();
+        let solid = (!solid.is_empty()).then(|| format!("[{}]", regex::escape(&solid)));
+        // Once whitespace is normalized, a whole run of it is a single space.
+        let spacing = match edges.normalized {
+            true => separators.contains(' ').then(|| String::from(edges.ws)),
+            false => {
+                let spacing: String = separators.chars().filter(|c| c.is_whitespace()).collect();
+                (!spacing.is_empty()).then(|| format!("[{}]", regex::escape(&spacing)))
+            }
+        };
+        if text.is_empty() {
+            return empty_word_pattern(solid, spacing, edges);
+        }
+
+        // Whitespace at the start of a normalized needle must be a whole run, so it can only
+        // follow the start of the haystack or a separator that is not whitespace. Otherwise a
+        // whitespace separator must not be trimmed from the haystack.
+        let before = match source.starts_with(char::is_whitespace) {
+            true => [
+                (!edges.trim_start).then(|| String::from("^")),
+                solid.clone(),
+                spacing.clone().filter(|_| !edges.normalized).map(|s| match edges.trim_start {
+                    true => format!(r"\S{any}*{s}"),
+                    false => s,
+                }),
+            ],
+            false => [Some(String::from(start)), solid.clone(), spacing.clone()],
+        };
+        let after = match source.ends_with(char::is_whitespace) {
+            true => [
+                (!edges.trim_end).then(|| String::from("$")),
+                solid,
+                spacing.filter(|_| !edges.normalized).map(|s| match edges.trim_end {
+                    true => format!(r"{s}{any}*\S"),
+                    false => s,
+                }),
+            ],
+            false => [solid, spacing, Some(String::from(end))],
+        };
+        format!("{}{text}{}", alternation(before), alternation(after))
+    }
+
+    /// Convert to a regex with `to_regex_pattern()` and compile it.
+    pub fn to_compiled_regex(&self) -> Result<Regex, regex::Error> {
+        Regex::new(&self.to_regex_pattern())
+    }
+
+    /// Convert a literal regex to a `StringMatch` that matches the same haystacks.
+    ///
+    /// The case sensitivity is taken from the regex flags: `(?i)` and `(?-i)` at the start,
+    /// or a `(?i:...)` or `(?-i:...)` group around the rest of the regex. The last flag wins,
+    /// e.g. `(?i)(?-i:abc)` is case-sensitive.
+    ///
+    /// The match length is not always `Full`, because an unanchored regex matches anywhere in
+    /// the haystack. Instead the `^` and `$` anchors select the `Full`, `Prefix` or `Suffix`
+    /// match length, and an unanchored regex gives `Partial`, so the conversion is lossless.
+    /// Returns `None` if the rest of the regex contains any characters that
+    /// `regex::escape()` would escape.
+    pub fn from_regex(regex: &Regex) -> Option<StringMatch> {
+        let mut case_sensitive = true;
+        let source = strip_case_flags(regex.as_str(), &mut case_sensitive);
+        let (source, start) = match source.strip_prefix('^') {
+            Some(rest) => (rest, true),
+            None => (source, false),
+        };
+        let (source, end) = match source.strip_suffix('$') {
+            Some(rest) => (rest, true),
+            None => (source, false),
+        };
+        let group = [("(?i:", Some(false)), ("(?-i:", Some(true)), ("(?:", None)]
+            .into_iter()
+            .find_map(|(open, flag)| Some((source.strip_prefix(open)?.strip_suffix(')')?, flag)));
+        let (text, start, end) = match group {
+            Some((inner, flag)) => {
+                case_sensitive = flag.unwrap_or(case_sensitive);
+                let (inner, inner_start) = match inner.strip_prefix('^') {
+                    Some(rest) => (rest, true),
+                    None => (inner, false),
+                };
+                let (inner, inner_end) = match inner.strip_suffix('$') {
+                    Some(rest) => (rest, true),
+                    None => (inner, false),
+                };
+                (inner, start || inner_start, end || inner_end)
+            }
+            None => (source, start, end),
+        };
+        if regex::escape(text) != text {
+            return None;
+        }
+
+        let match_length = match (start, end) {
+            (true, true) => StringMatchLength::Full,
+            (true, false) => StringMatchLength::Prefix,
+            (false, true) => StringMatchLength::Suffix,
+            (false, false) => StringMatchLength::Partial,
+        };
+        Some(
+            StringMatch::from(text)
+                .with_match_length(match_length)
+                .with_case_sensitive(case_sensitive),
+        )
+    }
+}
+
+/// Remove any `(?i)` and `(?-i)` flags from the start of a regex, updating
+/// `case_sensitive` for each one.
+fn strip_case_flags<'a>(mut source: &'a str, case_sensitive: &mut bool) -> &'a str {
+    loop {
+        if let Some(rest) = source.strip_prefix("(?i)") {
+            *case_sensitive = false;
+            source = rest;
+        } else if let Some(rest) = source.strip_prefix("(?-i)") {
+            *case_sensitive = true;
+            source = rest;
+        } else {
+            return source;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Needle;
+
+    /// Every string of up to four characters from a small alphabet of letters, whitespace and
+    /// punctuation.
+    fn generated_haystacks() -> Vec<String> {
+        let alphabet = ['a', 'B', ' ', '\n', ','];
+        let mut haystacks = vec![String::new()];
+        let mut previous = vec![String::new()];
+        for _ in 0..4 {
+            previous = previous
+                .iter()
+                .flat_map(|h| alphabet.iter().map(move |&c| format!("{h}{c}")))
+                .collect();
+            haystacks.extend(previous.iter().cloned());
+        }
+        haystacks
+    }
+
+    /// Check that the regex agrees with `is_match()` for `haystacks` and every generated
+    /// haystack.
+    fn assert_equivalent(sm: &StringMatch, haystacks: &[&str]) {
+        let re = sm.to_compiled_regex().unwrap();
+        let generated = generated_haystacks();
+        for haystack in haystacks.iter().copied().chain(generated.iter().map(String::as_str)) {
+            assert_eq!(
+                re.is_match(haystack),
+                sm.is_match(haystack),
+                "{sm:?} pattern {:?} haystack {haystack:?}",
+                re.as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_regex_pattern() {
+        assert_eq!(StringMatch::from("a.b").to_regex_pattern(), r"^a\.b$");
+        assert_eq!(StringMatch::from("a.b").partial().to_regex_pattern(), r"a\.b");
+        assert_eq!(StringMatch::from("a").prefix().to_regex_pattern(), "^a");
+        assert_eq!(StringMatch::from("a").suffix().to_regex_pattern(), "a$");
+        assert_eq!(StringMatch::from("a").case_insensitive().to_regex_pattern(), "(?i)^a$");
+        assert_eq!(StringMatch::from("a").trim().to_regex_pattern(), r"^\s*a\s*$");
+        assert_eq!(
+            StringMatch::from("a").word_with_separators(",").to_regex_pattern(),
+            "(?:^|[,])a(?:[,]|$)"
+        );
+        #[cfg(not(feature = "unicode"))]
+        assert_eq!(StringMatch::from("a").word().to_regex_pattern(), "(?:^|[ ])a(?:[ ]|$)");
+        #[cfg(feature = "unicode")]
+        assert_eq!(StringMatch::from("a b").word().to_regex_pattern(), r"\ba b\b");
+        #[cfg(feature = "unicode")]
+        assert_eq!(StringMatch::from("c++").word().to_regex_pattern(), r"\bc\+\+");
+        assert!(StringMatch::from("(").to_compiled_regex().is_ok());
+    }
+
+    #[test]
+    fn test_literal_metacharacters() {
+        let sm = StringMatch::from_literal("(test|foo)");
+        assert_eq!(sm, StringMatch::new("(test|foo)"));
+        assert!(sm.is_match("(test|foo)"));
+        assert!(!sm.is_match("test"));
+        assert_eq!(sm.to_regex_pattern(), r"^\(test\|foo\)$");
+        assert_equivalent(&sm, &["(test|foo)", "test", "foo", "(test)"]);
+        assert_equivalent(&sm.clone().partial(), &["a (test|foo) b", "test", "foo"]);
+        assert_equivalent(&StringMatch::from_literal(r"^a.*$\d").partial(), &[r"x^a.*$\dy", "ab1"]);
+    }
+
+    #[test]
+    fn test_wildcard_pattern() {
+        let sm = StringMatch::from_wildcard("a*b?.");
+        assert_eq!(sm.to_regex_pattern(), r"(?s)^a.*b.\.$");
+        assert_equivalent(&sm, &["ab1.", "axyzbc.", "a\nbc.", "ab.", "ab1x", "xab1."]);
+        assert_equivalent(&sm.clone().partial(), &["xab1.y", "ab.", "a b c."]);
+        assert_equivalent(&sm.clone().case_insensitive().prefix(), &["AB1.x", "xab1."]);
+        assert_equivalent(&sm.clone().line_by_line().suffix(), &["x\nab1.\ny", "a\nb1."]);
+        assert_equivalent(
+            &StringMatch::from_wildcard("b*").word(),
+            &["a bc d", "abc", "b", "a b", "a c"],
+        );
+    }
+
+    #[test]
+    fn test_from_regex() {
+        let sm = StringMatch::from_regex(&Regex::new("hello").unwrap()).unwrap();
+        assert_eq!(sm, StringMatch::from("hello").partial());
+        assert!(sm.is_match("say hello"));
+        assert!(StringMatch::from_regex(&Regex::new("hel.o").unwrap()).is_none());
+        assert!(StringMatch::from_regex(&Regex::new(r"hel\.o").unwrap()).is_none());
+        assert!(StringMatch::from_regex(&Regex::new("^a$$").unwrap()).is_none());
+        assert!(StringMatch::from_regex(&Regex::new("(?m)^a$").unwrap()).is_none());
+
+        let cases = [
+            ("^hello world$", StringMatch::from("hello world")),
+            ("^hello", StringMatch::from("hello").prefix()),
+            ("hello$", StringMatch::from("hello").suffix()),
+            ("(?i)^Hello$", StringMatch::from("Hello").case_insensitive()),
+            ("(?i)", StringMatch::from("").partial().case_insensitive()),
+            ("^$", StringMatch::from("")),
+        ];
+        for (pattern, expected) in cases {
+            let sm = StringMatch::from_regex(&Regex::new(pattern).unwrap());
+            assert_eq!(sm.as_ref(), Some(&expected), "{pattern}");
+            assert_eq!(expected.to_compiled_regex().unwrap().as_str(), pattern);
+        }
+
+        let flags = [
+            ("(?i:hello)", StringMatch::from("hello").partial().case_insensitive()),
+            ("(?-i)hello", StringMatch::from("hello").partial()),
+            ("(?i)(?-i)hello$", StringMatch::from("hello").suffix()),
+            ("(?i)(?-i:^hello)", StringMatch::from("hello").prefix()),
+            ("(?-i)(?i:^Hello$)", StringMatch::from("Hello").case_insensitive()),
+            ("^(?i:Hello)$", StringMatch::from("Hello").case_insensitive()),
+            ("(?:^hello$)", StringMatch::from("hello")),
+        ];
+        for (pattern, expected) in flags {
+            let regex = Regex::new(pattern).unwrap();
+            let sm = StringMatch::from_regex(&regex);
+            assert_eq!(sm.as_ref(), Some(&expected), "{pattern}");
+            for haystack in ["hello", "HELLO", "say hello", "hello there", "Hello"] {
+                assert_eq!(regex.is_match(haystack), expected.is_match(haystack), "{pattern}");
+            }
+        }
+        assert!(StringMatch::from_regex(&Regex::new("(?i:a)(?i:b)").unwrap()).is_none());
+        assert!(StringMatch::from_regex(&Regex::new("(?i:a.)").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_regex_matrix() {
+        let texts = ["", "a", "aB", "a B", "a  B", "a ", " a", " ", "a,B", "ü", "(x)"];
+        let lengths = [
+            StringMatchLength::Full,
+            StringMatchLength::Partial,
+            StringMatchLength::Word,
+            StringMatchLength::Prefix,
+            StringMatchLength::Suffix,
+        ];
+        let options: [fn(StringMatch) -> StringMatch; 10] = [
+            |sm| sm,
+            |sm| sm.trim(),
+            |sm| sm.trim_end_before_match(),
+            |sm| sm.normalize_whitespace(),
+            |sm| sm.normalize_whitespace().trim(),
+            |sm| sm.collapse_whitespace(),
+            |sm| sm.line_by_line(),
+            |sm| sm.line_by_line().trim(),
+            |sm| sm.line_by_line().trim_end_before_match().normalize_whitespace(),
+            |sm| sm.line_by_line().collapse_whitespace(),
+        ];
+        for text in texts {
+            for length in &lengths {
+                for case_sensitive in [true, false] {
+                    for (option, separators) in
+                        options.iter().flat_map(|o| [(o, None), (o, Some(",")), (o, Some(", "))])
+                    {
+                        if separators.is_some() && *length != StringMatchLength::Word {
+                            continue;
+                        }
+                        let sm = option(
+                            StringMatch::from(text)
+                                .with_match_length(length.clone())
+                                .with_case_sensitive(case_sensitive),
+                        );
+                        let sm = match separators {
+                            Some(separators) => sm.word_with_separators(separators),
+                            None => sm,
+                        };
+                        // `\b` only approximates Unicode word boundaries, which differ around
+                        // whitespace and punctuation.
+                        if cfg!(feature = "unicode")
+                            && sm.is_word_match()
+                            && sm.separators().is_none()
+                            && text.contains(|c: char| !c.is_alphanumeric())
+                        {
+                            continue;
+                        }
+                        assert_equivalent(&sm, &[]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_regex_options() {
+        let haystacks = [
+            "",
+            "foo  bar",
+            "foo\tbar",
+            "  foo bar\n",
+            "foo bar ",
+            "x,foo bar,y",
+            "x, foo bar",
+            "foo\nbar",
+            "x\nfoo bar\r\ny",
+            "x\nfoo  bar  \ny",
+        ];
+        let needles = [
+            StringMatch::from("foo bar").normalize_whitespace(),
+            StringMatch::from("foo bar").normalize_whitespace().partial(),
+            StringMatch::from("foo  bar").normalize_whitespace().prefix(),
+            StringMatch::from("foo bar").trim(),
+            StringMatch::from("foo bar").trim().prefix(),
+            StringMatch::from("foo bar").trim_end_before_match(),
+            StringMatch::from("foo bar").trim_end_before_match().suffix(),
+            StringMatch::from("foo bar").word_with_separators(","),
+            StringMatch::from("foo bar").word_with_separators(",").trim(),
+            StringMatch::from("foo bar").word_with_separators(""),
+            StringMatch::from("foo bar").line_by_line(),
+            StringMatch::from("foo bar").line_by_line().prefix(),
+            StringMatch::from("foo bar").line_by_line().suffix(),
+            StringMatch::from("foo bar").line_by_line().trim().normalize_whitespace(),
+            StringMatch::from("bar").line_by_line().suffix(),
+            StringMatch::from(" foo  bar ").collapse_whitespace(),
+            StringMatch::from("foo\tbar").collapse_whitespace().partial(),
+            StringMatch::from("foo bar ").collapse_whitespace().prefix(),
+            StringMatch::from(" foo bar").collapse_whitespace().suffix(),
+            StringMatch::from("foo bar").collapse_whitespace().line_by_line(),
+        ];
+        for sm in &needles {
+            assert_equivalent(sm, &haystacks);
+        }
+    }
+
+    #[test]
+    fn test_regex_trimmed_edges() {
+        let sm = StringMatch::from("a ").partial().trim();
+        assert!(!sm.is_match("a "));
+        assert!(sm.is_match("a b"));
+        assert_equivalent(&sm, &["a ", "a b", " a  "]);
+
+        let sm = StringMatch::from(" a").prefix().trim();
+        assert_eq!(sm.to_regex_pattern(), r"[^\s\S]");
+        assert_equivalent(&sm, &[" a", "a", "  a b"]);
+        assert_equivalent(&StringMatch::from("a ").suffix().trim_end_before_match(), &["a "]);
+        assert_equivalent(&StringMatch::from(" a").suffix().trim(), &[" a", "b a", "b a "]);
+        assert_equivalent(&StringMatch::from(" a ").trim(), &[" a ", "a"]);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{Needle, NeedleIter};
+use regex::{RegexSet, SetMatches};
+
+impl Needle for RegexSet {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.is_match(haystack)
+    }
+}
+
+/// Needle that matches if any of several regular expressions match, using a single scan.
+///
+/// In addition to the plain `Needle` interface, this can report which patterns matched.
+#[derive(Debug, Clone)]
+pub struct RegexSetNeedle(RegexSet);
+
+impl RegexSetNeedle {
+    pub fn new<I, S>(patterns: I) -> Result<Self, regex::Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        RegexSet::new(patterns).map(Self)
+    }
+
+    /// Return the set of patterns that match the haystack.
+    pub fn matched_patterns(&self, haystack: &str) -> SetMatches {
+        self.0.matches(haystack)
+    }
+
+    pub fn regex_set(&self) -> &RegexSet {
+        &self.0
+    }
+}
+
+impl From<RegexSet> for RegexSetNeedle {
+    fn from(set: RegexSet) -> Self {
+        Self(set)
+    }
+}
+
+impl TryFrom<&[&str]> for RegexSetNeedle {
+    type Error = regex::Error;
+
+    fn try_from(patterns: &[&str]) -> Result<Self, Self::Error> {
+        Self::new(patterns)
+    }
+}
+
+impl Needle for RegexSetNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.0.is_match(haystack)
+    }
+}
+
+impl NeedleIter for RegexSetNeedle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regex_set() {
+        let set = RegexSet::new([r"^\d+$", r"^T", r"st$"]).unwrap();
+        assert!(!Needle::is_match(&set, "abc"));
+        assert!(Needle::is_match(&set, "123"));
+        assert!(Needle::is_match(&set, "Test"));
+
+        let empty = RegexSet::empty();
+        assert!(!Needle::is_match(&empty, "Test"));
+    }
+
+    #[test]
+    fn test_regex_set_needle() {
+        let patterns: &[&str] = &[r"^\d+$", r"^T", r"st$"];
+        let needle = RegexSetNeedle::try_from(patterns).unwrap();
+
+        assert!(!needle.is_match("abc"));
+        assert!(!needle.matched_patterns("abc").matched_any());
+
+        assert!(needle.is_match("123"));
+        assert_eq!(needle.matched_patterns("123").into_iter().collect::<Vec<_>>(), vec![0]);
+
+        assert!(needle.is_match("Test"));
+        assert_eq!(needle.matched_patterns("Test").into_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        assert!(needle.is_match_in(&mut vec!["abc", "best"].into_iter()));
+        assert_eq!(needle.regex_set().len(), 3);
+
+        let invalid: &[&str] = &["(unclosed"];
+        assert!(RegexSetNeedle::try_from(invalid).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::StringMatch;
+use std::collections::HashMap;
+use std::sync::{OnceLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A thread-safe library of named `StringMatch` patterns, so that a pattern can be defined
+/// once and reused by name.
+///
+/// Either create a registry and pass it to the code that needs it, or use the shared
+/// instance returned by `PatternRegistry::global()`. Lookups return a copy of the pattern, so
+/// registering a new pattern under the same name does not affect copies already returned.
+#[derive(Debug, Default)]
+pub struct PatternRegistry {
+    patterns: RwLock<HashMap<String, StringMatch>>,
+}
+
+impl PatternRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry shared by the whole process.
+    pub fn global() -> &'static PatternRegistry {
+        static GLOBAL: OnceLock<PatternRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(PatternRegistry::new)
+    }
+
+    /// Register a pattern under `name`, returning the pattern it replaced, if any.
+    pub fn register(&self, name: impl Into<String>, pattern: StringMatch) -> Option<StringMatch> {
+        self.write().insert(name.into(), pattern)
+    }
+
+    /// Return a copy of the pattern registered under `name`.
+    pub fn get(&self, name: &str) -> Option<StringMatch> {
+        self.read().get(name).cloned()
+    }
+
+    /// Remove the pattern registered under `name`, returning it if it was present.
+    pub fn remove(&self, name: &str) -> Option<StringMatch> {
+        self.write().remove(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.read().contains_key(name)
+    }
+
+    /// The names of all registered patterns, in no particular order.
+    pub fn names(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    // A panic while the lock is held cannot leave the map half-updated, so a poisoned lock is
+    // still safe to use.
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, StringMatch>> {
+        self.patterns.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, StringMatch>> {
+        self.patterns.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Needle;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_registry() {
+        let registry = PatternRegistry::new();
+        assert!(registry.is_empty());
+        assert_eq!(registry.get("email"), None);
+
+        let email = StringMatch::from("@example.com").suffix().case_insensitive();
+        assert_eq!(registry.register("email", email.clone()), None);
+        assert!(registry.contains("email"));
+        assert_eq!(registry.len(), 1);
+        let found = registry.get("email").unwrap();
+        assert_eq!(found, email);
+        assert!(found.is_match("someone@EXAMPLE.com"));
+        assert!(!found.is_match("someone@example.org"));
+
+        // Registering under the same name replaces the pattern.
+        let replacement = StringMatch::from("@example.org").suffix();
+        assert_eq!(registry.register("email", replacement.clone()), Some(email));
+        assert_eq!(registry.len(), 1);
+        let found = registry.get("email").unwrap();
+        assert!(found.is_match("someone@example.org"));
+        assert!(!found.is_match("someone@example.com"));
+
+        registry.register(String::from("greeting"), StringMatch::from("hello").partial());
+        let mut names = registry.names();
+        names.sort();
+        assert_eq!(names, ["email", "greeting"]);
+        assert_eq!(registry.remove("email"), Some(replacement));
+        assert_eq!(registry.remove("email"), None);
+        assert!(!registry.contains("email"));
+    }
+
+    #[test]
+    fn test_registry_global() {
+        // The global registry is shared with other tests, so use a name unique to this test.
+        let name = "stringmatch_test_registry_global";
+        assert!(std::ptr::eq(PatternRegistry::global(), PatternRegistry::global()));
+        PatternRegistry::global().register(name, StringMatch::from("a").prefix());
+        let handle = thread::spawn(move || PatternRegistry::global().get(name));
+        assert_eq!(handle.join().unwrap(), Some(StringMatch::from("a").prefix()));
+        PatternRegistry::global().remove(name);
+    }
+
+    #[test]
+    fn test_registry_threads() {
+        let registry = Arc::new(PatternRegistry::new());
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let registry = Arc::clone(&registry);
+                thread::spawn(move || {
+                    for j in 0..100 {
+                        let name = format!("{}-{}", i, j);
+                        registry.register(name.as_str(), StringMatch::from(name.as_str()));
+                        assert!(registry.get(&name).unwrap().is_match(&name));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(registry.len(), 800);
+    }
+}
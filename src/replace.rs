@@ -0,0 +1,141 @@
+use crate::find::FindableNeedle;
+use crate::StringMatch;
+use alloc::string::{String, ToString};
+
+impl StringMatch {
+    /// Replace the first match in the haystack with `replacement`.
+    ///
+    /// In `Full` mode this returns the replacement if the whole haystack matches, and the
+    /// haystack unchanged otherwise. In other modes this mirrors `str::replacen(.., 1)`.
+    /// The unmatched parts of the haystack keep their original case.
+    pub fn replace(&self, haystack: &str, replacement: &str) -> String {
+        self.replace_spans(haystack, Some(1), |_| replacement.to_string())
+    }
+
+    /// Replace all non-overlapping matches in the haystack with `replacement`.
+    ///
+    /// This mirrors `str::replace`.
+    pub fn replace_all(&self, haystack: &str, replacement: &str) -> String {
+        self.replace_spans(haystack, None, |_| replacement.to_string())
+    }
+
+    /// Replace the first match in the haystack with the result of calling `f` on the
+    /// matched text.
+    pub fn replace_with<F>(&self, haystack: &str, f: F) -> String
+    where
+        F: Fn(&str) -> String,
+    {
+        self.replace_spans(haystack, Some(1), f)
+    }
+
+    /// Replace all non-overlapping matches in the haystack with the result of calling `f` on
+    /// each matched text.
+    ///
+    /// In `Full` mode `f` is called with the whole haystack if it matches.
+    pub fn replace_all_with<F>(&self, haystack: &str, f: F) -> String
+    where
+        F: Fn(&str) -> String,
+    {
+        self.replace_spans(haystack, None, f)
+    }
+
+    fn replace_spans<F>(&self, haystack: &str, limit: Option<usize>, f: F) -> String
+    where
+        F: Fn(&str) -> String,
+    {
+        let mut result = String::with_capacity(haystack.len());
+        let mut last = 0;
+        for span in self.find_all(haystack).take(limit.unwrap_or(usize::MAX)) {
+            result.push_str(&haystack[last..span.byte_start]);
+            result.push_str(&f(span.as_str(haystack)));
+            last = span.byte_end;
+        }
+        result.push_str(&haystack[last..]);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_full() {
+        assert_eq!(StringMatch::from("abc").replace("abc", "x"), "x");
+        assert_eq!(StringMatch::from("abc").replace("abcd", "x"), "abcd");
+        assert_eq!(StringMatch::from("abc").replace_all("abc", "x"), "x");
+        assert_eq!(StringMatch::from("ABC").case_insensitive().replace("abc", "x"), "x");
+        assert_eq!(StringMatch::from("").replace("", "x"), "x");
+        assert_eq!(StringMatch::from("").replace("a", "x"), "a");
+    }
+
+    #[test]
+    fn test_replace_partial() {
+        let needle = StringMatch::from("ab").partial();
+        assert_eq!(needle.replace("abcab", "x"), "abcab".replacen("ab", "x", 1));
+        assert_eq!(needle.replace_all("abcab", "x"), "abcab".replace("ab", "x"));
+        assert_eq!(needle.replace_all("cd", "x"), "cd");
+
+        // Overlapping occurrences.
+        let needle = StringMatch::from("aa").partial();
+        assert_eq!(needle.replace_all("aaa", "x"), "aaa".replace("aa", "x"));
+        assert_eq!(needle.replace_all("aaaa", "x"), "xx");
+
+        // Empty needle.
+        let needle = StringMatch::from("").partial();
+        assert_eq!(needle.replace("ab", "-"), "ab".replacen("", "-", 1));
+        assert_eq!(needle.replace_all("ab", "-"), "ab".replace("", "-"));
+
+        // Case-insensitive replacement keeps the rest of the haystack intact.
+        let needle = StringMatch::from("ab").partial().case_insensitive();
+        assert_eq!(needle.replace("XaBxAB", "-"), "X-xAB");
+        assert_eq!(needle.replace_all("XaBxAB", "-"), "X-x-");
+        assert_eq!(needle.replace_all("ÉaBé", "-"), "É-é");
+    }
+
+    #[test]
+    fn test_replace_word() {
+        let needle = StringMatch::from("a").word();
+        assert_eq!(needle.replace("a aa a", "b"), "b aa a");
+        assert_eq!(needle.replace_all("a aa a", "b"), "b aa b");
+        assert_eq!(needle.replace_all("aa", "b"), "aa");
+        assert_eq!(
+            StringMatch::from("A").word().case_insensitive().replace_all("a Ab A", "b"),
+            "b Ab b"
+        );
+    }
+
+    #[test]
+    fn test_replace_prefix_suffix() {
+        assert_eq!(StringMatch::from("a").prefix().replace_all("aaa", "b"), "baa");
+        assert_eq!(StringMatch::from("a").suffix().replace_all("aaa", "b"), "aab");
+    }
+
+    #[test]
+    fn test_replace_with() {
+        let needle = StringMatch::from("ab").partial().case_insensitive();
+        assert_eq!(needle.replace_with("xAbyab", |m| m.to_uppercase()), "xAByab");
+        assert_eq!(needle.replace_with("xy", |m| m.to_uppercase()), "xy");
+        assert_eq!(StringMatch::from("abc").replace_with("abc", |m| format!("[{}]", m)), "[abc]");
+    }
+
+    #[test]
+    fn test_replace_all_with() {
+        let needle = StringMatch::from("ab").partial().case_insensitive();
+        assert_eq!(needle.replace_all_with("xAbyab", |m| m.repeat(2)), "xAbAbyabab");
+        assert_eq!(needle.replace_all_with("xAbyab", |_| String::new()), "xy");
+        assert_eq!(needle.replace_all_with("xy", |m| m.to_uppercase()), "xy");
+        assert_eq!(needle.replace_all_with("", |m| m.to_uppercase()), "");
+        assert_eq!(needle.replace_all_with("ÉabéAB", |m| format!("<{}>", m)), "É<ab>é<AB>");
+
+        let needle = StringMatch::from("é").partial();
+        assert_eq!(needle.replace_all_with("aéбé", |m| m.to_uppercase()), "aÉбÉ");
+
+        let needle = StringMatch::from("a b").word();
+        assert_eq!(needle.replace_all_with("a b ab a b", |m| m.replace(' ', "_")), "a_b ab a_b");
+
+        let needle = StringMatch::from("abc").case_insensitive();
+        assert_eq!(needle.replace_all_with("ABC", |m| format!("[{}]", m)), "[ABC]");
+        assert_eq!(needle.replace_all_with("ABCD", |m| format!("[{}]", m)), "ABCD");
+    }
+}
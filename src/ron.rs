@@ -0,0 +1,53 @@
+use crate::StringMatch;
+
+impl StringMatch {
+    /// Serialize to the RON text format.
+    pub fn to_ron(&self) -> Result<String, ::ron::Error> {
+        ::ron::to_string(self)
+    }
+
+    /// Deserialize from the RON text format, as written by `to_ron()`.
+    pub fn from_ron(s: &str) -> Result<Self, ::ron::error::SpannedError> {
+        ::ron::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Needle;
+
+    #[test]
+    fn test_ron_roundtrip() {
+        let needles = [
+            StringMatch::from("a=\"b\"\\c\né").partial().case_insensitive(),
+            StringMatch::from("a b").word_with_separators(",\"").min_length(2),
+        ];
+        for orig in &needles {
+            let deserialized = StringMatch::from_ron(&orig.to_ron().unwrap()).unwrap();
+            assert_eq!(&deserialized, orig);
+        }
+        assert!(needles[0].is_match("X A=\"B\"\\C\nÉ X"));
+    }
+
+    #[test]
+    fn test_ron_handwritten() {
+        // RON allows the struct name, leaves out fields that have defaults and writes the
+        // match length and an optional separator list as RON values rather than strings.
+        let needle = StringMatch::from_ron(
+            "StringMatch(text: \"ab\", match_length: Word, case_sensitive: false, \
+             separator_chars: Some(\",\"))",
+        )
+        .unwrap();
+        assert_eq!(needle, StringMatch::from("ab").case_insensitive().word_with_separators(","));
+        assert!(needle.is_match("x,AB"));
+        assert!(!needle.is_match("x AB"));
+
+        assert!(StringMatch::from_ron("").is_err());
+        assert!(StringMatch::from_ron("(text: 1)").is_err());
+        assert!(StringMatch::from_ron(
+            "(text: \"a\", match_length: \"Partial\", case_sensitive: true)"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{FindableNeedle, Needle, StringMatch};
+use alloc::vec::Vec;
+
+/// A needle that can score how well a haystack matches, for ranking results.
+pub trait ScoredNeedle: Needle {
+    /// Return a score from 0.0 (no match) to 1.0 (perfect match).
+    fn score(&self, haystack: &str) -> f64;
+
+    /// Return the item with the highest score, or None if no item matches.
+    ///
+    /// If several items have the same highest score, the first one is returned.
+    fn best_match<'a, I, S>(&self, haystacks: I) -> Option<S>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        let mut best: Option<(f64, S)> = None;
+        for h in haystacks {
+            let score = self.score(h.clone().into());
+            if score > 0.0 && best.as_ref().is_none_or(|(best_score, _)| score > *best_score) {
+                best = Some((score, h));
+            }
+        }
+        best.map(|(_, h)| h)
+    }
+
+    /// Score every item and return the matching ones, highest score first.
+    fn rank_matches<'a, I, S>(&self, haystacks: I) -> RankedMatches<S>
+    where
+        I: Iterator<Item = S>,
+        S: Into<&'a str> + Clone,
+    {
+        haystacks
+            .filter_map(|h| {
+                let score = self.score(h.clone().into());
+                (score > 0.0).then_some((score, h))
+            })
+            .collect()
+    }
+}
+
+/// Items paired with their scores, sorted by descending score.
+///
+/// Items with equal scores keep their original order. Created by
+/// `ScoredNeedle::rank_matches()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedMatches<S>(Vec<(f64, S)>);
+
+impl<S> RankedMatches<S> {
+    /// Sort the scored items by descending score.
+    pub fn new(mut scored: Vec<(f64, S)>) -> Self {
+        scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        Self(scored)
+    }
+
+    /// Return the `k` highest-scoring items, highest score first.
+    pub fn top(self, k: usize) -> Vec<S> {
+        self.0.into_iter().take(k).map(|(_, s)| s).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(f64, S)> {
+        self.0.iter()
+    }
+}
+
+impl<S> FromIterator<(f64, S)> for RankedMatches<S> {
+    fn from_iter<T: IntoIterator<Item = (f64, S)>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<S> IntoIterator for RankedMatches<S> {
+    type Item = (f64, S);
+    type IntoIter = alloc::vec::IntoIter<(f64, S)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// A `Full` match scores 1.0. The other match lengths score the fraction of the haystack's
+/// characters covered by the first match, so shorter haystacks score higher. A match always
+/// scores above 0.0, even for an empty needle.
+impl ScoredNeedle for StringMatch {
+    fn score(&self, haystack: &str) -> f64 {
+        if self.is_full_match() {
+            return match self.is_match(haystack) {
+                true => 1.0,
+                false => 0.0,
+            };
+        }
+        match self.find(haystack) {
+            Some(span) => {
+                let haystack_len = haystack.chars().count();
+                match haystack_len {
+                    0 => 1.0,
+                    _ => {
+                        let span_len = span.as_str(haystack).chars().count();
+                        (span_len as f64 / haystack_len as f64).clamp(f64::MIN_POSITIVE, 1.0)
+                    }
+                }
+            }
+            None => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_score_in_range<N: ScoredNeedle>(needle: &N, haystacks: &[&str]) {
+        for haystack in haystacks {
+            let score = needle.score(haystack);
+            assert!((0.0..=1.0).contains(&score), "{haystack:?} scored {score}");
+            assert_eq!(score > 0.0, needle.is_match(haystack), "{haystack:?}");
+        }
+    }
+
+    #[test]
+    fn test_score() {
+        assert_eq!(StringMatch::from("abc").score("abc"), 1.0);
+        assert_eq!(StringMatch::from("abc").score("abcd"), 0.0);
+        assert_eq!(StringMatch::from("ab").partial().score("abcd"), 0.5);
+        assert_eq!(StringMatch::from("ab").partial().score("ab"), 1.0);
+        assert_eq!(StringMatch::from("ab").partial().score("xyz"), 0.0);
+        assert_eq!(StringMatch::from("ab").word().score("ab cd"), 0.4);
+        assert_eq!(StringMatch::from("é").prefix().score("éa"), 0.5);
+        assert_eq!(StringMatch::from("").partial().score(""), 1.0);
+        assert!(StringMatch::from("").partial().score("abc") > 0.0);
+
+        let haystacks = ["", "a", "abc", "ABC", "xabcx", "a b c", "ab abc", "日本語", "  abc  "];
+        let needles = [
+            StringMatch::from("abc"),
+            StringMatch::from("abc").partial(),
+            StringMatch::from("ABC").partial().case_insensitive(),
+            StringMatch::from("abc").word(),
+            StringMatch::from("abc").trim(),
+            StringMatch::from("b").suffix(),
+            StringMatch::from("").partial(),
+            StringMatch::from("本").partial(),
+        ];
+        for needle in &needles {
+            assert_score_in_range(needle, &haystacks);
+        }
+    }
+
+    #[test]
+    fn test_best_match() {
+        let sm = StringMatch::from("ab").partial();
+        let haystacks = ["xyz", "abcdef", "abc", "ab c", "zab"];
+        assert_eq!(sm.best_match(haystacks.into_iter()), Some("abc"));
+        assert_eq!(sm.best_match(vec!["ab", "ab"].into_iter()), Some("ab"));
+        assert_eq!(sm.best_match(vec!["xyz", "cd"].into_iter()), None);
+        assert_eq!(sm.best_match(Vec::<&str>::new().into_iter()), None);
+        assert_eq!(StringMatch::from("ab").best_match(haystacks.into_iter()), None);
+    }
+
+    #[test]
+    fn test_rank_matches() {
+        let sm = StringMatch::from("ab").partial();
+        let haystacks = ["abcd", "xyz", "ab", "abcdefgh", "abc", "zzab"];
+        let ranked = sm.rank_matches(haystacks.into_iter());
+        assert_eq!(ranked.len(), 5);
+        assert!(!ranked.is_empty());
+        assert_eq!(ranked.iter().next(), Some(&(1.0, "ab")));
+        let scores: Vec<f64> = ranked.iter().map(|(score, _)| *score).collect();
+        assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+
+        // "abcd" and "zzab" tie, and keep their original order.
+        assert_eq!(ranked.clone().top(3), vec!["ab", "abc", "abcd"]);
+        assert_eq!(ranked.clone().top(4), vec!["ab", "abc", "abcd", "zzab"]);
+        assert_eq!(ranked.clone().top(100).len(), 5);
+        assert!(ranked.clone().top(0).is_empty());
+
+        let pairs: Vec<(f64, &str)> = ranked.into_iter().collect();
+        assert_eq!(pairs[4], (0.25, "abcdefgh"));
+
+        let ranked = sm.rank_matches(vec!["x", "y"].into_iter());
+        assert!(ranked.is_empty());
+        let ranked: RankedMatches<&str> =
+            vec![(0.5, "a"), (0.9, "b"), (0.5, "c")].into_iter().collect();
+        assert_eq!(ranked.top(3), vec!["b", "a", "c"]);
+    }
+}
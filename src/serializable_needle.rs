@@ -0,0 +1,228 @@
+use crate::{Needle, NeedleIter, SerializableRegex, StringMatch};
+use regex::{Regex, RegexBuilder};
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Serialize};
+
+/// A needle that is either a `StringMatch` or a `Regex`, and (with the `serde_derive` feature)
+/// can be serialized, e.g. for storing patterns in a config file.
+///
+/// The `Regex` variant is serialized as its pattern string and case sensitivity, and the
+/// pattern is compiled again when deserializing. Deserializing an invalid pattern fails with
+/// the regex compilation error.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(try_from = "RawNeedle", into = "RawNeedle"))]
+pub enum SerializableNeedle {
+    Match(StringMatch),
+    Regex(RegexNeedle),
+}
+
+impl SerializableNeedle {
+    /// Compile `pattern` into a `Regex` needle.
+    pub fn regex(pattern: &str, case_insensitive: bool) -> Result<Self, regex::Error> {
+        RegexNeedle::new(pattern, case_insensitive).map(Self::Regex)
+    }
+
+    /// The regex pattern, or `None` for a `StringMatch`.
+    pub fn pattern(&self) -> Option<&str> {
+        match self {
+            Self::Match(_) => None,
+            Self::Regex(regex) => Some(regex.regex().as_str()),
+        }
+    }
+}
+
+/// The compiled regex of a `SerializableNeedle::Regex`, along with its case sensitivity.
+///
+/// The two can only be set together, so the regex compiles the same way again when it is
+/// deserialized.
+#[derive(Debug, Clone)]
+pub struct RegexNeedle {
+    regex: Regex,
+    case_insensitive: bool,
+}
+
+impl RegexNeedle {
+    pub fn new(pattern: &str, case_insensitive: bool) -> Result<Self, regex::Error> {
+        let regex = RegexBuilder::new(pattern).case_insensitive(case_insensitive).build()?;
+        Ok(Self {
+            regex,
+            case_insensitive,
+        })
+    }
+
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+}
+
+/// The regex is assumed to be compiled with the default options, as by `Regex::new()`.
+impl From<Regex> for RegexNeedle {
+    fn from(regex: Regex) -> Self {
+        Self {
+            regex,
+            case_insensitive: false,
+        }
+    }
+}
+
+impl Needle for RegexNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.regex.is_match(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.regex.complexity_estimate()
+    }
+}
+
+impl NeedleIter for RegexNeedle {}
+
+impl From<StringMatch> for SerializableNeedle {
+    fn from(sm: StringMatch) -> Self {
+        Self::Match(sm)
+    }
+}
+
+impl From<Regex> for SerializableNeedle {
+    fn from(regex: Regex) -> Self {
+        Self::Regex(regex.into())
+    }
+}
+
+impl From<SerializableRegex> for SerializableNeedle {
+    fn from(regex: SerializableRegex) -> Self {
+        regex.into_inner().into()
+    }
+}
+
+impl Needle for SerializableNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        match self {
+            Self::Match(sm) => sm.is_match(haystack),
+            Self::Regex(regex) => regex.is_match(haystack),
+        }
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        match self {
+            Self::Match(sm) => sm.complexity_estimate(),
+            Self::Regex(regex) => regex.complexity_estimate(),
+        }
+    }
+}
+
+impl NeedleIter for SerializableNeedle {}
+
+/// The serialized form of `SerializableNeedle`.
+#[cfg(feature = "serde_derive")]
+#[derive(Serialize, Deserialize)]
+enum RawNeedle {
+    Match(StringMatch),
+    Regex {
+        pattern: String,
+        #[serde(default)]
+        case_insensitive: bool,
+    },
+}
+
+#[cfg(feature = "serde_derive")]
+impl TryFrom<RawNeedle> for SerializableNeedle {
+    type Error = regex::Error;
+
+    fn try_from(raw: RawNeedle) -> Result<Self, Self::Error> {
+        match raw {
+            RawNeedle::Match(sm) => Ok(Self::Match(sm)),
+            RawNeedle::Regex {
+                pattern,
+                case_insensitive,
+            } => Self::regex(&pattern, case_insensitive),
+        }
+    }
+}
+
+#[cfg(feature = "serde_derive")]
+impl From<SerializableNeedle> for RawNeedle {
+    fn from(needle: SerializableNeedle) -> Self {
+        match needle {
+            SerializableNeedle::Match(sm) => Self::Match(sm),
+            SerializableNeedle::Regex(regex) => Self::Regex {
+                pattern: regex.regex().as_str().to_string(),
+                case_insensitive: regex.is_case_insensitive(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HAYSTACKS: [&str; 7] = ["", "Test", "test", "TEST", "a test", "Testing", "123"];
+
+    #[test]
+    fn test_serializable_needle() {
+        let needle = SerializableNeedle::from(StringMatch::from("test").partial());
+        assert_eq!(needle.pattern(), None);
+        let sm = StringMatch::from("test").partial();
+        for haystack in HAYSTACKS {
+            assert_eq!(needle.is_match(haystack), sm.is_match(haystack));
+        }
+
+        let needle = SerializableNeedle::regex(r"^te\w+$", true).unwrap();
+        assert_eq!(needle.pattern(), Some(r"^te\w+$"));
+        let SerializableNeedle::Regex(regex) = &needle else {
+            panic!("expected a regex needle");
+        };
+        assert!(regex.is_case_insensitive());
+        assert!(regex.is_match("TEST"));
+        let re = Regex::new(r"(?i)^te\w+$").unwrap();
+        for haystack in HAYSTACKS {
+            assert_eq!(needle.is_match(haystack), re.is_match(haystack));
+        }
+
+        let needle = SerializableNeedle::from(Regex::new(r"^\d+$").unwrap());
+        assert!(needle.is_match("123"));
+        assert!(matches!(&needle, SerializableNeedle::Regex(r) if !r.is_case_insensitive()));
+        assert!(needle.is_match_in(&mut vec!["a", "1"].into_iter()));
+        let needle = SerializableNeedle::from(SerializableRegex::new("es").unwrap());
+        assert!(needle.is_match("Test"));
+        assert!(!needle.is_match("TEST"));
+
+        assert!(SerializableNeedle::regex("(unclosed", false).is_err());
+    }
+
+    #[cfg(feature = "serde_derive")]
+    #[test]
+    fn test_serde() {
+        let needles = [
+            SerializableNeedle::from(StringMatch::from("test")),
+            SerializableNeedle::from(StringMatch::from("TES").prefix().case_insensitive()),
+            SerializableNeedle::regex(r"^te\w+$", true).unwrap(),
+            SerializableNeedle::regex(r"\d", false).unwrap(),
+        ];
+        for orig in &needles {
+            let serialized = serde_json::to_string(orig).unwrap();
+            let deserialized: SerializableNeedle = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(deserialized.pattern(), orig.pattern());
+            for haystack in HAYSTACKS {
+                assert_eq!(deserialized.is_match(haystack), orig.is_match(haystack));
+            }
+        }
+
+        let serialized = serde_json::to_string(&needles[2]).unwrap();
+        assert_eq!(serialized, r#"{"Regex":{"pattern":"^te\\w+$","case_insensitive":true}}"#);
+        let needle: SerializableNeedle =
+            serde_json::from_str(r#"{"Regex":{"pattern":"a"}}"#).unwrap();
+        assert!(needle.is_match("a") && !needle.is_match("A"));
+
+        let err = serde_json::from_str::<SerializableNeedle>(r#"{"Regex":{"pattern":"(a"}}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unclosed group"), "{}", err);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{Needle, NeedleIter};
+use core::ops::Deref;
+use core::str::FromStr;
+use regex::Regex;
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A `Regex` that (with the `serde_derive` feature) can be serialized as its pattern string.
+///
+/// Two `SerializableRegex` values are equal if their pattern strings are equal.
+#[derive(Debug, Clone)]
+pub struct SerializableRegex(Regex);
+
+impl SerializableRegex {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self)
+    }
+
+    pub fn into_inner(self) -> Regex {
+        self.0
+    }
+}
+
+impl Deref for SerializableRegex {
+    type Target = Regex;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Regex> for SerializableRegex {
+    fn from(regex: Regex) -> Self {
+        Self(regex)
+    }
+}
+
+impl TryFrom<&str> for SerializableRegex {
+    type Error = regex::Error;
+
+    fn try_from(pattern: &str) -> Result<Self, Self::Error> {
+        Self::new(pattern)
+    }
+}
+
+impl FromStr for SerializableRegex {
+    type Err = regex::Error;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Self::new(pattern)
+    }
+}
+
+impl PartialEq for SerializableRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for SerializableRegex {}
+
+impl Needle for SerializableRegex {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.0.is_match(haystack)
+    }
+}
+
+impl NeedleIter for SerializableRegex {}
+
+#[cfg(feature = "serde_derive")]
+impl Serialize for SerializableRegex {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+#[cfg(feature = "serde_derive")]
+impl<'de> Deserialize<'de> for SerializableRegex {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializable_regex() {
+        let re = SerializableRegex::new(r"^T.+t$").unwrap();
+        assert!(re.is_match("Test"));
+        assert!(!re.is_match("test"));
+        assert_eq!(re.as_str(), r"^T.+t$"); // Deref to Regex.
+        assert!(re.is_match_in(&mut vec!["a", "Tent"].into_iter()));
+
+        assert_eq!(
+            SerializableRegex::try_from("a+").unwrap(),
+            SerializableRegex::new("a+").unwrap()
+        );
+        assert_eq!("a+".parse::<SerializableRegex>().unwrap().as_str(), "a+");
+        assert_ne!(SerializableRegex::new("a+").unwrap(), SerializableRegex::new("a*").unwrap());
+        assert!(SerializableRegex::new("(unclosed").is_err());
+        assert!("(unclosed".parse::<SerializableRegex>().is_err());
+        assert_eq!(SerializableRegex::from(Regex::new("x").unwrap()).into_inner().as_str(), "x");
+    }
+
+    #[cfg(feature = "serde_derive")]
+    #[test]
+    fn test_serde() {
+        let orig = SerializableRegex::new(r"(?i)^te\w+$").unwrap();
+        let serialized = serde_json::to_string(&orig).unwrap();
+        assert_eq!(serialized, r#""(?i)^te\\w+$""#);
+        let deserialized: SerializableRegex = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+        for haystack in ["Test", "test", "TESTING", "te", "a test"] {
+            assert_eq!(deserialized.is_match(haystack), orig.is_match(haystack));
+        }
+
+        assert!(serde_json::from_str::<SerializableRegex>(r#""(unclosed""#).is_err());
+    }
+}
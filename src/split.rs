@@ -0,0 +1,203 @@
+use crate::find::{FindableNeedle, MatchSpan};
+use crate::{Needle, StringMatch};
+use alloc::vec::Vec;
+
+impl StringMatch {
+    /// Return an iterator over the substrings of the haystack separated by matches.
+    ///
+    /// In `Full` mode the result is either the whole haystack (no match) or nothing (the
+    /// haystack matched). In other modes this behaves like `str::split`, so consecutive
+    /// matches, or matches at either end of the haystack, produce empty slices.
+    pub fn split<'n, 'h: 'n>(&'n self, haystack: &'h str) -> impl Iterator<Item = &'h str> + 'n {
+        split_iter(self, haystack, None)
+    }
+
+    /// Like `split()`, but returns at most `n` substrings. The last substring contains the
+    /// remainder of the haystack.
+    pub fn splitn<'n, 'h: 'n>(
+        &'n self,
+        haystack: &'h str,
+        n: usize,
+    ) -> impl Iterator<Item = &'h str> + 'n {
+        split_iter(self, haystack, Some(n))
+    }
+
+    /// Return the substrings of the haystack between matches. This is the same as
+    /// `split()`, collected into a `Vec`.
+    pub fn split_on_matches<'h>(&self, haystack: &'h str) -> Vec<&'h str> {
+        split_iter(self, haystack, None).collect()
+    }
+}
+
+struct SplitIter<'a, I> {
+    haystack: &'a str,
+    spans: I,
+    /// The end of the previous match.
+    last: usize,
+    /// The number of substrings still allowed, if limited.
+    remaining: Option<usize>,
+    done: bool,
+}
+
+fn split_iter<'n, 'h: 'n>(
+    needle: &'n StringMatch,
+    haystack: &'h str,
+    limit: Option<usize>,
+) -> SplitIter<'h, impl Iterator<Item = MatchSpan> + 'n> {
+    SplitIter {
+        haystack,
+        spans: needle.find_all(haystack),
+        last: 0,
+        remaining: limit,
+        done: needle.is_full_match() && needle.is_match(haystack),
+    }
+}
+
+impl<'a, I> Iterator for SplitIter<'a, I>
+where
+    I: Iterator<Item = MatchSpan>,
+{
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.remaining {
+            Some(0) => {
+                self.done = true;
+                return None;
+            }
+            Some(1) => {
+                self.done = true;
+                return Some(&self.haystack[self.last..]);
+            }
+            Some(ref mut n) => *n -= 1,
+            None => {}
+        }
+
+        match self.spans.next() {
+            Some(span) => {
+                let segment = &self.haystack[self.last..span.byte_start];
+                self.last = span.byte_end;
+                Some(segment)
+            }
+            None => {
+                self.done = true;
+                Some(&self.haystack[self.last..])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(needle: &StringMatch, haystack: &str) -> Vec<String> {
+        needle.split(haystack).map(String::from).collect()
+    }
+
+    fn splitn(needle: &StringMatch, haystack: &str, n: usize) -> Vec<String> {
+        needle.splitn(haystack, n).map(String::from).collect()
+    }
+
+    #[test]
+    fn test_split_full() {
+        let needle = StringMatch::from("abc");
+        assert!(split(&needle, "abc").is_empty());
+        assert_eq!(split(&needle, "abcd"), vec!["abcd"]);
+        assert_eq!(split(&needle, ""), vec![""]);
+        assert!(split(&StringMatch::from("ABC").case_insensitive(), "abc").is_empty());
+        assert!(split(&StringMatch::from(""), "").is_empty());
+    }
+
+    #[test]
+    fn test_split_partial() {
+        let needle = StringMatch::from(",").partial();
+        assert_eq!(split(&needle, "a,b,c"), "a,b,c".split(',').collect::<Vec<_>>());
+        assert_eq!(split(&needle, "a,,b"), vec!["a", "", "b"]);
+        assert_eq!(split(&needle, ",a,"), vec!["", "a", ""]);
+        assert_eq!(split(&needle, ""), vec![""]);
+        assert_eq!(split(&needle, "abc"), vec!["abc"]);
+
+        // Needle longer than haystack.
+        assert_eq!(split(&StringMatch::from("abcd").partial(), "abc"), vec!["abc"]);
+        // Needle is the entire haystack.
+        assert_eq!(split(&StringMatch::from("abc").partial(), "abc"), vec!["", ""]);
+        // Empty needle behaves like `str::split`.
+        assert_eq!(
+            split(&StringMatch::from("").partial(), "ab"),
+            "ab".split("").collect::<Vec<_>>()
+        );
+
+        let needle = StringMatch::from("X").partial().case_insensitive();
+        assert_eq!(split(&needle, "axbXc"), vec!["a", "b", "c"]);
+        assert_eq!(split(&StringMatch::from("X").partial(), "axbXc"), vec!["axb", "c"]);
+    }
+
+    #[test]
+    fn test_split_word() {
+        let needle = StringMatch::from("and").word();
+        assert_eq!(split(&needle, "this and that"), vec!["this ", " that"]);
+        assert_eq!(split(&needle, "sand and band"), vec!["sand ", " band"]);
+        assert_eq!(split(&needle, "and"), vec!["", ""]);
+        assert_eq!(split(&needle, "android"), vec!["android"]);
+    }
+
+    #[test]
+    fn test_split_on_matches() {
+        let needle = StringMatch::from("ab").partial();
+        for haystack in ["xaby", "xabyabz", "x ab y ab z ab w", "no match", "xababy"] {
+            let parts = needle.split_on_matches(haystack);
+            assert_eq!(parts.len(), needle.count(haystack) + 1, "{:?}", haystack);
+            assert_eq!(parts, needle.split(haystack).collect::<Vec<_>>());
+        }
+        assert_eq!(needle.split_on_matches("xabyabz"), vec!["x", "y", "z"]);
+        assert_eq!(needle.split_on_matches("xababy"), vec!["x", "", "y"]);
+
+        // Matches at either end give empty leading and trailing parts.
+        assert_eq!(needle.split_on_matches("abx"), vec!["", "x"]);
+        assert_eq!(needle.split_on_matches("xab"), vec!["x", ""]);
+        assert_eq!(needle.split_on_matches("abxab"), vec!["", "x", ""]);
+
+        let needle = StringMatch::from("and").word();
+        assert_eq!(
+            needle.split_on_matches("this and that and band"),
+            vec!["this ", " that ", " band"]
+        );
+        assert_eq!(needle.split_on_matches("and so"), vec!["", " so"]);
+
+        let needle = StringMatch::from("abc");
+        assert!(needle.split_on_matches("abc").is_empty());
+        assert_eq!(needle.split_on_matches("abcd"), vec!["abcd"]);
+    }
+
+    #[test]
+    fn test_splitn() {
+        let needle = StringMatch::from(",").partial();
+        assert_eq!(splitn(&needle, "a,b,c", 2), "a,b,c".splitn(2, ',').collect::<Vec<_>>());
+        assert_eq!(splitn(&needle, "a,b,c", 1), vec!["a,b,c"]);
+        assert!(splitn(&needle, "a,b,c", 0).is_empty());
+        assert_eq!(splitn(&needle, "a,b,c", 10), vec!["a", "b", "c"]);
+        assert!(splitn(&StringMatch::from("abc"), "abc", 2).is_empty());
+        assert_eq!(splitn(&StringMatch::from("abc"), "x", 2), vec!["x"]);
+    }
+
+    #[test]
+    fn test_split_outlives_needle() {
+        fn fields(haystack: &str) -> Vec<&str> {
+            let needle = StringMatch::from(",").partial();
+            needle.split(haystack).collect()
+        }
+
+        fn first_field(haystack: &str) -> Vec<&str> {
+            let needle = StringMatch::from(",").partial();
+            needle.splitn(haystack, 2).collect()
+        }
+
+        assert_eq!(fields("a,b"), vec!["a", "b"]);
+        assert_eq!(first_field("a,b,c"), vec!["a", "b,c"]);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::Needle;
+use alloc::vec::Vec;
+
+/// A needle that keeps state between calls, so the result can depend on earlier haystacks.
+pub trait StatefulNeedle {
+    fn is_match(&mut self, haystack: &str) -> bool;
+
+    /// Forget all earlier haystacks.
+    fn reset(&mut self);
+}
+
+/// A stateful needle that matches when its needles have matched consecutive haystacks, in
+/// order.
+///
+/// Each call to `is_match()` returns true if the haystack and the ones before it matched the
+/// whole sequence. Partial progress is then forgotten, so the sequence starts again from the
+/// beginning. An empty sequence never matches.
+///
+/// e.g. `SequenceNeedle::new(vec!["first", "second", "third"])` matches `"third"` only if the
+/// previous two haystacks were `"first"` and `"second"`.
+#[derive(Debug, Clone)]
+pub struct SequenceNeedle<N> {
+    needles: Vec<N>,
+    /// The number of needles matched by each partial sequence in progress, in ascending order.
+    /// Several can be in progress at once, e.g. after `a, a` for the sequence `a, a, b`.
+    active: Vec<usize>,
+}
+
+impl<N> SequenceNeedle<N>
+where
+    N: Needle,
+{
+    pub fn new(needles: Vec<N>) -> Self {
+        Self {
+            needles,
+            active: Vec::new(),
+        }
+    }
+
+    /// The number of needles matched so far by the longest partial sequence in progress.
+    pub fn progress(&self) -> usize {
+        self.active.last().copied().unwrap_or(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.needles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.needles.is_empty()
+    }
+}
+
+impl<N> FromIterator<N> for SequenceNeedle<N>
+where
+    N: Needle,
+{
+    fn from_iter<T: IntoIterator<Item = N>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<N> StatefulNeedle for SequenceNeedle<N>
+where
+    N: Needle,
+{
+    fn is_match(&mut self, haystack: &str) -> bool {
+        if self.needles.is_empty() {
+            return false;
+        }
+        let mut active = Vec::with_capacity(self.active.len() + 1);
+        for pos in core::iter::once(0).chain(self.active.iter().copied()) {
+            if self.needles[pos].is_match(haystack) {
+                active.push(pos + 1);
+            }
+        }
+        if active.last() == Some(&self.needles.len()) {
+            self.active.clear();
+            return true;
+        }
+        self.active = active;
+        false
+    }
+
+    fn reset(&mut self) {
+        self.active.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StringMatch;
+
+    fn feed<S: StatefulNeedle>(needle: &mut S, haystacks: &[&str]) -> Vec<bool> {
+        haystacks.iter().map(|h| needle.is_match(h)).collect()
+    }
+
+    #[test]
+    fn test_sequence_needle() {
+        let mut needle = SequenceNeedle::new(vec!["first", "second", "third"]);
+        assert_eq!(needle.len(), 3);
+        assert_eq!(feed(&mut needle, &["first", "second", "third"]), [false, false, true]);
+        assert_eq!(needle.progress(), 0);
+
+        // The sequence starts again once it completes.
+        assert_eq!(feed(&mut needle, &["first", "second", "third"]), [false, false, true]);
+
+        // Out of order.
+        assert_eq!(feed(&mut needle, &["second", "first", "third"]), [false, false, false]);
+        assert_eq!(feed(&mut needle, &["third", "second", "first"]), [false, false, false]);
+
+        // An interruption restarts the sequence, and the interrupting haystack can start it.
+        needle.reset();
+        assert_eq!(feed(&mut needle, &["first", "other", "second", "third"]), [false; 4]);
+        assert_eq!(
+            feed(&mut needle, &["first", "first", "second", "third"]),
+            [false, false, false, true]
+        );
+
+        // Reset restarts from the beginning.
+        assert_eq!(feed(&mut needle, &["first", "second"]), [false, false]);
+        assert_eq!(needle.progress(), 2);
+        needle.reset();
+        assert_eq!(needle.progress(), 0);
+        assert_eq!(feed(&mut needle, &["third"]), [false]);
+        assert_eq!(feed(&mut needle, &["first", "second", "third"]), [false, false, true]);
+    }
+
+    #[test]
+    fn test_sequence_needle_overlapping() {
+        let mut needle = SequenceNeedle::new(vec!["a", "a", "b"]);
+        assert_eq!(feed(&mut needle, &["a", "a", "a", "b"]), [false, false, false, true]);
+        assert_eq!(feed(&mut needle, &["a", "a", "a"]), [false; 3]);
+        assert_eq!(needle.progress(), 2);
+        assert_eq!(feed(&mut needle, &["b"]), [true]);
+
+        let needle_fn = |s: &str| s.len() == 1;
+        let mut needle = SequenceNeedle::new(vec![needle_fn, needle_fn]);
+        assert_eq!(feed(&mut needle, &["x", "y", "z", "w"]), [false, true, false, true]);
+    }
+
+    #[test]
+    fn test_sequence_needle_types() {
+        let mut needle: SequenceNeedle<StringMatch> =
+            ["a", "b"].into_iter().map(|s| StringMatch::from(s).prefix()).collect();
+        assert_eq!(feed(&mut needle, &["apple", "banana"]), [false, true]);
+
+        let mut needle = SequenceNeedle::new(vec!["x"]);
+        assert_eq!(feed(&mut needle, &["x", "y", "x"]), [true, false, true]);
+
+        let mut needle = SequenceNeedle::<&str>::new(Vec::new());
+        assert!(needle.is_empty());
+        assert_eq!(feed(&mut needle, &["", "a"]), [false, false]);
+    }
+}
@@ -0,0 +1,392 @@
+use crate::{find_separated_word, CompiledStringMatch, Needle, StringMatch, StringMatchLength};
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+/// A needle that matches text arriving in chunks, e.g. a file read in fixed-size buffers.
+///
+/// Call `feed()` for each chunk and then `finish()` to find out whether the whole text
+/// matched. Call `reset()` before matching a new text.
+pub trait StreamingNeedle {
+    /// Add the next chunk of the haystack.
+    fn feed(&mut self, chunk: &str);
+
+    /// Return true if the haystack fed so far matches.
+    ///
+    /// This does not clear the state, so more chunks can still be fed afterwards.
+    fn finish(&mut self) -> bool;
+
+    /// Clear the state, ready to match a new haystack.
+    fn reset(&mut self);
+}
+
+/// How much of the haystack a `StreamingMatch` needs to keep.
+#[derive(Debug, Clone)]
+enum Strategy {
+    /// A `Partial` match only needs enough of the previous chunks to find a match that
+    /// crosses a chunk boundary, i.e. `keep` characters.
+    Window {
+        needle: CompiledStringMatch,
+        keep: usize,
+    },
+    /// A `Word` match with separators needs the last `keep` characters, which is enough for a
+    /// word that crosses a chunk boundary and the separator before it.
+    Word {
+        needle: String,
+        keep: usize,
+    },
+    /// A `Full` match with no whitespace, diacritic or normalization options can only match a
+    /// haystack of at most `limit` characters, so buffering stops once the haystack is longer.
+    Bounded {
+        limit: usize,
+    },
+    /// Buffer the whole haystack.
+    Buffer,
+}
+
+/// A `StringMatch` that matches text fed to it in chunks, created by
+/// `StringMatch::streaming()`.
+///
+/// The result is always the same as calling `is_match()` on the concatenated chunks. Only
+/// `Partial`, `Full` and `Word` matches without the whitespace, trim, punctuation, number,
+/// diacritic, normalization, wildcard or `line_by_line()` options avoid buffering; every
+/// other needle buffers the whole haystack. This includes `Word` matches with Unicode word
+/// boundaries (the `unicode` feature without custom separators), since a boundary can
+/// depend on any amount of the surrounding text.
+#[derive(Debug, Clone)]
+pub struct StreamingMatch {
+    source: StringMatch,
+    strategy: Strategy,
+    buffer: String,
+    /// The number of characters fed so far.
+    chars: usize,
+    /// True if every chunk fed so far was ASCII.
+    ascii: bool,
+    /// True once a `Window` or `Word` match has been found.
+    matched: bool,
+    /// True once characters have been dropped from the start of a `Word` buffer.
+    drained: bool,
+    /// True once a `Bounded` haystack is too long to match.
+    overflowed: bool,
+}
+
+impl StreamingMatch {
+    pub fn new(source: StringMatch) -> Self {
+        let transformed = source.normalize_whitespace
+            || source.collapse_whitespace
+            || source.trim
+            || source.trim_end
+            || source.multiline
+            || source.is_diacritic_insensitive()
+            || source.is_unicode_normalized()
+            || source.has_ignored_chars()
+            || source.wildcard;
+        // Every haystack character produces at least one character once lowercased, so a match
+        // covers at most as many haystack characters as there are in the compiled needle.
+        let needle_chars = source.compiled_text().chars().count();
+        let strategy = match source.match_length {
+            StringMatchLength::Partial if !transformed => {
+                let needle = StringMatch {
+                    min_length: None,
+                    max_length: None,
+                    ascii_only: false,
+                    ..source.clone()
+                };
+                Strategy::Window {
+                    needle: needle.compile(),
+                    keep: needle_chars.saturating_sub(1),
+                }
+            }
+            StringMatchLength::Word
+                if !transformed
+                    && (source.separator_chars.is_some() || !cfg!(feature = "unicode")) =>
+            {
+                Strategy::Word {
+                    needle: source.compiled_text().into_owned(),
+                    keep: needle_chars + 1,
+                }
+            }
+            StringMatchLength::Full if !transformed => Strategy::Bounded {
+                limit: needle_chars,
+            },
+            _ => Strategy::Buffer,
+        };
+        Self {
+            source,
+            strategy,
+            buffer: String::new(),
+            chars: 0,
+            ascii: true,
+            matched: false,
+            drained: false,
+            overflowed: false,
+        }
+    }
+
+    /// Return true if the buffer contains the `Word` needle. The end of the buffer is only
+    /// treated as a separator if `finished` is true, since the word could continue in the next
+    /// chunk.
+    fn is_word_in_buffer(&self, needle: &str, finished: bool) -> bool {
+        let buffer = match self.source.case_sensitive {
+            true => Cow::Borrowed(self.buffer.as_str()),
+            false => Cow::Owned(self.buffer.to_lowercase()),
+        };
+        let separators = self.source.separators();
+        find_separated_word(needle, &buffer, separators, !self.drained, finished).is_some()
+    }
+
+    /// The `StringMatch` this was created from.
+    pub fn source(&self) -> &StringMatch {
+        &self.source
+    }
+}
+
+impl From<StringMatch> for StreamingMatch {
+    fn from(source: StringMatch) -> Self {
+        Self::new(source)
+    }
+}
+
+impl StreamingNeedle for StreamingMatch {
+    fn feed(&mut self, chunk: &str) {
+        self.chars += chunk.chars().count();
+        self.ascii &= chunk.is_ascii();
+        match &self.strategy {
+            Strategy::Window {
+                needle,
+                keep,
+            } => {
+                if self.matched {
+                    return;
+                }
+                self.buffer.push_str(chunk);
+                if needle.is_match(&self.buffer) {
+                    self.matched = true;
+                    self.buffer.clear();
+                    return;
+                }
+                let start = match keep {
+                    0 => self.buffer.len(),
+                    _ => self.buffer.char_indices().rev().nth(keep - 1).map_or(0, |(i, _)| i),
+                };
+                self.buffer.drain(..start);
+            }
+            Strategy::Word {
+                needle,
+                keep,
+            } => {
+                if self.matched {
+                    return;
+                }
+                self.buffer.push_str(chunk);
+                if self.is_word_in_buffer(needle, false) {
+                    self.matched = true;
+                    self.buffer.clear();
+                    return;
+                }
+                let start = self.buffer.char_indices().rev().nth(keep - 1).map_or(0, |(i, _)| i);
+                self.drained |= start > 0;
+                self.buffer.drain(..start);
+            }
+            Strategy::Bounded {
+                limit,
+            } => {
+                if self.overflowed {
+                    return;
+                }
+                if self.chars > *limit {
+                    self.overflowed = true;
+                    self.buffer = String::new();
+                    return;
+                }
+                self.buffer.push_str(chunk);
+            }
+            Strategy::Buffer => self.buffer.push_str(chunk),
+        }
+    }
+
+    fn finish(&mut self) -> bool {
+        match &self.strategy {
+            Strategy::Window {
+                needle,
+                ..
+            } => {
+                (self.matched || needle.is_match(&self.buffer))
+                    && (!self.source.ascii_only || self.ascii)
+                    && self.source.is_length_allowed(self.chars)
+            }
+            Strategy::Word {
+                needle,
+                ..
+            } => {
+                (self.matched || self.is_word_in_buffer(needle, true))
+                    && (!self.source.ascii_only || self.ascii)
+                    && self.source.is_length_allowed(self.chars)
+            }
+            Strategy::Bounded {
+                ..
+            } => !self.overflowed && self.source.is_match(&self.buffer),
+            Strategy::Buffer => self.source.is_match(&self.buffer),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.chars = 0;
+        self.ascii = true;
+        self.matched = false;
+        self.drained = false;
+        self.overflowed = false;
+    }
+}
+
+impl StringMatch {
+    /// Create a `StreamingMatch` for matching a haystack that arrives in chunks.
+    pub fn streaming(&self) -> StreamingMatch {
+        StreamingMatch::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Split the haystack into chunks of pseudo-random sizes between 1 and `max` bytes,
+    /// rounded up to the next character boundary.
+    fn chunks(haystack: &str, seed: u64, max: usize) -> Vec<&str> {
+        let mut state = seed;
+        let mut pos = 0;
+        let mut chunks = Vec::new();
+        while pos < haystack.len() {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let mut end = (pos + 1 + (state >> 33) as usize % max).min(haystack.len());
+            while !haystack.is_char_boundary(end) {
+                end += 1;
+            }
+            chunks.push(&haystack[pos..end]);
+            pos = end;
+        }
+        chunks
+    }
+
+    fn stream_match(stream: &mut StreamingMatch, chunks: &[&str]) -> bool {
+        stream.reset();
+        for chunk in chunks {
+            stream.feed(chunk);
+        }
+        stream.finish()
+    }
+
+    #[test]
+    fn test_streaming_matches_is_match() {
+        let text = "The quick brown fox jumps over the lazy dög. ".repeat(40);
+        let haystacks = [
+            String::new(),
+            String::from("fox"),
+            String::from("FOX"),
+            text.clone(),
+            format!("{}needle in a haystack", text),
+            format!("{}NEEDLE", text),
+            format!("needle{}", text),
+            format!("{}nee\ndle needle", text),
+        ];
+        let needles = [
+            StringMatch::from("fox"),
+            StringMatch::from("FOX").case_insensitive(),
+            StringMatch::from("").partial(),
+            StringMatch::from("fox").partial(),
+            StringMatch::from("dög. The").partial(),
+            StringMatch::from("needle").partial(),
+            StringMatch::from("NEEDLE").partial().case_insensitive(),
+            StringMatch::from("needle").partial().ascii_only(),
+            StringMatch::from("needle").partial().max_length(100),
+            StringMatch::from("needle").partial().min_length(100),
+            StringMatch::from("needle").partial().line_by_line(),
+            StringMatch::from("nee dle").partial().normalize_whitespace(),
+            StringMatch::from(" nee  dle ").partial().collapse_whitespace(),
+            StringMatch::from(text.as_str()),
+            StringMatch::from(text.to_uppercase().as_str()).case_insensitive(),
+            StringMatch::from(text.trim()).trim_end_before_match(),
+            StringMatch::from("needle").word(),
+            StringMatch::from("needle").word_with_separators(" \n"),
+            StringMatch::from("NEEDLE").word_with_separators(" ").case_insensitive(),
+            StringMatch::from("dle").word_with_separators(" \n"),
+            StringMatch::from("fox").word_with_separators(" ").max_length(10),
+            StringMatch::from("").word_with_separators(" "),
+            StringMatch::from("").word_with_separators("\n"),
+            StringMatch::from("needle").prefix(),
+            StringMatch::from("NEEDLE").suffix().case_insensitive(),
+            StringMatch::from_wildcard("ne?dle*fox").partial(),
+        ];
+        for needle in &needles {
+            let mut stream = needle.streaming();
+            for haystack in &haystacks {
+                let expected = needle.is_match(haystack);
+                for (seed, max) in [(1, 1), (2, 3), (3, 16), (4, 100), (5, 1000)] {
+                    let chunks = chunks(haystack, seed, max);
+                    assert_eq!(chunks.concat(), *haystack);
+                    assert_eq!(
+                        stream_match(&mut stream, &chunks),
+                        expected,
+                        "{:?} {:?} {}",
+                        needle,
+                        haystack,
+                        max
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_buffers_little() {
+        let haystack = "abcdefghij".repeat(1000);
+        let mut stream = StringMatch::from("jab").partial().case_insensitive().streaming();
+        for chunk in chunks(&haystack, 7, 50) {
+            stream.feed(chunk);
+            assert!(stream.buffer.len() <= 50 + 2);
+        }
+        assert!(stream.finish());
+
+        let mut stream = StringMatch::from("xyz").partial().streaming();
+        for chunk in chunks(&haystack, 8, 50) {
+            stream.feed(chunk);
+            assert!(stream.buffer.chars().count() <= 2);
+        }
+        assert!(!stream.finish());
+
+        let mut stream = StringMatch::from("xyz").word_with_separators("j").streaming();
+        for chunk in chunks(&haystack, 10, 50) {
+            stream.feed(chunk);
+            assert!(stream.buffer.chars().count() <= 4);
+        }
+        assert!(!stream.finish());
+        stream.feed("xy");
+        stream.feed("z");
+        assert!(stream.finish());
+        stream.feed("a");
+        assert!(!stream.finish());
+
+        let mut stream = StringMatch::from("abc").streaming();
+        for chunk in chunks(&haystack, 9, 50) {
+            stream.feed(chunk);
+        }
+        assert!(stream.buffer.is_empty());
+        assert!(!stream.finish());
+    }
+
+    #[test]
+    fn test_streaming_reset() {
+        let mut stream: StreamingMatch = StringMatch::from("ab").partial().into();
+        assert!(!stream.finish());
+        stream.feed("xa");
+        assert!(!stream.finish());
+        stream.feed("bx");
+        assert!(stream.finish());
+        assert!(stream.finish());
+        stream.reset();
+        stream.feed("b");
+        assert!(!stream.finish());
+        assert_eq!(stream.source(), &StringMatch::from("ab").partial());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Needle, NeedleIter, StringMatch};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+/// A `StringMatch` that is compared, ordered and hashed by its text only, so that a
+/// `HashMap` or `BTreeMap` keyed by `TextKey` can be probed with a `&str`.
+///
+/// Two keys with the same text but different options are equal, so a map holds at most one
+/// needle per text. `StringMatch` itself compares every option and cannot be probed this way.
+#[derive(Debug, Clone)]
+pub struct TextKey {
+    inner: StringMatch,
+}
+
+impl TextKey {
+    pub fn new(inner: StringMatch) -> Self {
+        Self {
+            inner,
+        }
+    }
+
+    pub fn inner(&self) -> &StringMatch {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> StringMatch {
+        self.inner
+    }
+}
+
+impl From<StringMatch> for TextKey {
+    fn from(inner: StringMatch) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl PartialEq for TextKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.text() == other.inner.text()
+    }
+}
+
+impl Eq for TextKey {}
+
+impl PartialOrd for TextKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TextKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.text().cmp(other.inner.text())
+    }
+}
+
+impl Hash for TextKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.text().hash(state);
+    }
+}
+
+impl Borrow<str> for TextKey {
+    fn borrow(&self) -> &str {
+        self.inner.text()
+    }
+}
+
+impl Needle for TextKey {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.inner.is_match(haystack)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.inner.complexity_estimate()
+    }
+}
+
+impl NeedleIter for TextKey {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn test_text_key() {
+        let mut map = HashMap::new();
+        map.insert(TextKey::new(StringMatch::new("key")), 1);
+        map.insert(StringMatch::new("other").partial().into(), 2);
+        assert_eq!(map.get("key"), Some(&1));
+        assert_eq!(map.get("other"), Some(&2));
+        assert_eq!(map.get("missing"), None);
+
+        // Keys with the same text are the same key, whatever their options.
+        map.insert(StringMatch::new("key").partial().into(), 3);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("key"), Some(&3));
+
+        let mut tree = BTreeMap::new();
+        tree.insert(TextKey::new(StringMatch::new("b")), 1);
+        tree.insert(TextKey::new(StringMatch::new("a").case_insensitive()), 2);
+        assert_eq!(tree.get("a"), Some(&2));
+        assert_eq!(tree.keys().map(|k| k.inner().text()).collect::<Vec<_>>(), ["a", "b"]);
+
+        let key = TextKey::new(StringMatch::new("abc").partial());
+        assert_eq!(key, TextKey::new(StringMatch::new("abc")));
+        assert!(key.is_match("xabcx"));
+        assert_eq!(key.complexity_estimate(), key.inner().complexity_estimate());
+        assert_eq!(key.into_inner(), StringMatch::new("abc").partial());
+    }
+}
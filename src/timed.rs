@@ -0,0 +1,147 @@
+use crate::{Needle, NeedleIter};
+use std::fmt;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// The error returned by `TimedNeedle::is_match_timed()` when the inner needle takes too long.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TimeoutError {
+    timeout: Duration,
+}
+
+impl TimeoutError {
+    /// The timeout that was exceeded.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "needle did not finish matching within {:?}", self.timeout)
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// Needle that gives up if the inner needle takes longer than a timeout, e.g. to guard
+/// against slow fuzzy matching or a pathological closure.
+///
+/// Each call runs the inner needle on a new thread with its own copy of the haystack. When
+/// the timeout expires the thread is left to finish in the background, since threads cannot
+/// be cancelled, and its result is discarded. If the inner needle panics, the panic is
+/// propagated to the caller.
+#[derive(Debug)]
+pub struct TimedNeedle<N> {
+    inner: Arc<N>,
+    timeout: Duration,
+}
+
+impl<N> TimedNeedle<N>
+where
+    N: Needle + Send + Sync + 'static,
+{
+    pub fn new(inner: N, timeout: Duration) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            timeout,
+        }
+    }
+
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Return the result of the inner needle, or an error if it did not finish in time.
+    pub fn is_match_timed(&self, haystack: &str) -> Result<bool, TimeoutError> {
+        let (sender, receiver) = mpsc::channel();
+        let inner = Arc::clone(&self.inner);
+        let haystack = haystack.to_string();
+        let handle = thread::spawn(move || {
+            // The receiver is gone if the timeout has already expired.
+            let _ = sender.send(inner.is_match(&haystack));
+        });
+        match receiver.recv_timeout(self.timeout) {
+            Ok(matched) => Ok(matched),
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(TimeoutError {
+                timeout: self.timeout,
+            }),
+            // The sender was dropped without sending, so the inner needle panicked.
+            Err(mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
+                Err(payload) => std::panic::resume_unwind(payload),
+                Ok(()) => unreachable!("the needle thread always sends a result"),
+            },
+        }
+    }
+}
+
+impl<N> Clone for TimedNeedle<N> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// Returns false if the inner needle does not finish within the timeout.
+impl<N> Needle for TimedNeedle<N>
+where
+    N: Needle + Send + Sync + 'static,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        self.is_match_timed(haystack).unwrap_or(false)
+    }
+
+    fn complexity_estimate(&self) -> u32 {
+        self.inner.complexity_estimate()
+    }
+}
+
+impl<N> NeedleIter for TimedNeedle<N> where N: Needle + Send + Sync + 'static {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StringMatch;
+    use std::time::Instant;
+
+    #[test]
+    fn test_timed_needle() {
+        let needle = TimedNeedle::new(StringMatch::from("a").partial(), Duration::from_secs(10));
+        assert_eq!(needle.timeout(), Duration::from_secs(10));
+        assert_eq!(needle.is_match_timed("cat"), Ok(true));
+        assert_eq!(needle.is_match_timed("dog"), Ok(false));
+        assert!(needle.is_match("cat"));
+        assert!(needle.clone().is_match_in(&mut vec!["dog", "bat"].into_iter()));
+        assert_eq!(needle.inner(), &StringMatch::from("a").partial());
+    }
+
+    #[test]
+    fn test_timed_needle_timeout() {
+        let slow = |_: &str| {
+            thread::sleep(Duration::from_secs(10));
+            true
+        };
+        let needle = TimedNeedle::new(slow, Duration::from_millis(100));
+
+        let start = Instant::now();
+        let err = needle.is_match_timed("a").unwrap_err();
+        assert_eq!(err.timeout(), Duration::from_millis(100));
+        assert_eq!(err.to_string(), "needle did not finish matching within 100ms");
+        assert!(!needle.is_match("a"));
+        assert!(start.elapsed() < Duration::from_secs(2), "{:?}", start.elapsed());
+    }
+
+    #[test]
+    #[should_panic(expected = "needle panicked")]
+    fn test_timed_needle_panic() {
+        let needle = TimedNeedle::new(|_: &str| panic!("needle panicked"), Duration::from_secs(10));
+        needle.is_match("a");
+    }
+}
@@ -0,0 +1,54 @@
+use crate::StringMatch;
+
+impl StringMatch {
+    /// Serialize to the TOML text format.
+    ///
+    /// The result is a TOML document with one key per option. The match length is written
+    /// as a string, such as `match_length = "Partial"`.
+    pub fn to_toml(&self) -> Result<String, ::toml::ser::Error> {
+        ::toml::to_string(self)
+    }
+
+    /// Deserialize from the TOML text format, as written by `to_toml()`.
+    pub fn from_toml(s: &str) -> Result<Self, ::toml::de::Error> {
+        ::toml::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Needle;
+
+    #[test]
+    fn test_toml_roundtrip() {
+        let needles = [
+            StringMatch::from("a=\"b\"\\c\né").partial().case_insensitive(),
+            StringMatch::from("a b").word_with_separators(",\"").min_length(2),
+        ];
+        for orig in &needles {
+            let deserialized = StringMatch::from_toml(&orig.to_toml().unwrap()).unwrap();
+            assert_eq!(&deserialized, orig);
+        }
+        assert!(needles[0].is_match("X A=\"B\"\\C\nÉ X"));
+
+        // TOML has no null, so options that are not set are left out of the document.
+        let toml = StringMatch::from("a").to_toml().unwrap();
+        assert!(!toml.contains("separator_chars"));
+        assert!(!toml.contains("min_length"));
+    }
+
+    #[test]
+    fn test_toml_handwritten() {
+        let needle = StringMatch::from_toml(
+            "text = 'C:\\dir'\nmatch_length = \"Prefix\"\ncase_sensitive = false\n",
+        )
+        .unwrap();
+        assert_eq!(needle, StringMatch::from("C:\\dir").prefix().case_insensitive());
+        assert!(needle.is_match("c:\\DIR\\file"));
+
+        assert!(StringMatch::from_toml("").is_err());
+        assert!(StringMatch::from_toml("text = 1").is_err());
+        assert!(StringMatch::from_toml("text = \"a\"\nmatch_length = \"Middle\"").is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{Needle, NeedleIter};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: BTreeMap<char, Node>,
+    /// The index of the prefix that ends at this node, if any.
+    prefix: Option<usize>,
+}
+
+/// Needle that matches if the haystack starts with any of a set of prefixes.
+///
+/// The prefixes are stored in a trie, so matching takes time proportional to the length of
+/// the longest matching prefix rather than the number of prefixes. In case-insensitive mode
+/// the prefixes are stored lowercased and each haystack character is lowercased as it is
+/// read.
+#[derive(Debug, Clone)]
+pub struct TrieNeedle {
+    root: Node,
+    prefixes: Vec<String>,
+    /// If true, use a case-sensitive match. Default is true.
+    case_sensitive: bool,
+}
+
+impl TrieNeedle {
+    pub fn new(prefixes: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        let mut trie = Self {
+            root: Node::default(),
+            prefixes: Vec::new(),
+            case_sensitive: true,
+        };
+        for prefix in prefixes {
+            trie.insert(prefix.as_ref());
+        }
+        trie
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    pub fn case_insensitive(self) -> Self {
+        if !self.case_sensitive {
+            return self;
+        }
+        let mut trie = Self {
+            root: Node::default(),
+            prefixes: Vec::new(),
+            case_sensitive: false,
+        };
+        for prefix in self.prefixes {
+            trie.insert(&prefix);
+        }
+        trie
+    }
+
+    /// Add a prefix, which is lowercased in case-insensitive mode.
+    pub fn insert(&mut self, prefix: &str) {
+        let prefix = match self.case_sensitive {
+            true => String::from(prefix),
+            false => prefix.to_lowercase(),
+        };
+        let mut node = &mut self.root;
+        for c in prefix.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        if node.prefix.is_none() {
+            node.prefix = Some(self.prefixes.len());
+            self.prefixes.push(prefix);
+        }
+    }
+
+    /// The number of distinct prefixes. In case-insensitive mode, prefixes that differ only
+    /// by case count once.
+    pub fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    /// Return the longest prefix that the haystack starts with, if any.
+    ///
+    /// In case-insensitive mode the prefix is returned lowercased.
+    pub fn matching_prefix(&self, haystack: &str) -> Option<&str> {
+        let mut node = &self.root;
+        let mut longest = node.prefix;
+        let mut step = |c: char| match node.children.get(&c) {
+            Some(child) => {
+                node = child;
+                longest = node.prefix.or(longest);
+                true
+            }
+            None => false,
+        };
+        match self.case_sensitive {
+            true => haystack.chars().all(&mut step),
+            false => haystack.chars().flat_map(char::to_lowercase).all(&mut step),
+        };
+        longest.map(|i| self.prefixes[i].as_str())
+    }
+}
+
+impl<S> FromIterator<S> for TrieNeedle
+where
+    S: AsRef<str>,
+{
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl Needle for TrieNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.matching_prefix(haystack).is_some()
+    }
+}
+
+impl NeedleIter for TrieNeedle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnyNeedle, StringMatch};
+    use alloc::format;
+    use alloc::vec;
+
+    #[test]
+    fn test_trie_needle() {
+        let needle = TrieNeedle::new(["foo", "foobar", "bar", "ba"]);
+        assert_eq!(needle.len(), 4);
+        assert!(needle.is_case_sensitive());
+        assert_eq!(needle.matching_prefix("foobarbaz"), Some("foobar"));
+        assert_eq!(needle.matching_prefix("foobaz"), Some("foo"));
+        assert_eq!(needle.matching_prefix("bat"), Some("ba"));
+        assert_eq!(needle.matching_prefix("fo"), None);
+        assert_eq!(needle.matching_prefix("Foo"), None);
+        assert_eq!(needle.matching_prefix(""), None);
+        assert!(needle.is_match("bar"));
+        assert!(!needle.is_match("xfoo"));
+        assert!(needle.is_match_in(&mut vec!["x", "food"].into_iter()));
+
+        let needle = needle.case_insensitive();
+        assert!(!needle.is_case_sensitive());
+        assert_eq!(needle.matching_prefix("FOOBAR"), Some("foobar"));
+        assert_eq!(needle.matching_prefix("Bay"), Some("ba"));
+
+        let mut needle: TrieNeedle = ["ÄB", "äbc"].into_iter().collect();
+        assert_eq!(needle.len(), 2);
+        needle = needle.case_insensitive();
+        assert_eq!(needle.len(), 2);
+        assert_eq!(needle.matching_prefix("ÄBCD"), Some("äbc"));
+        needle.insert("Äbcd");
+        assert_eq!(needle.len(), 3);
+        assert_eq!(needle.matching_prefix("äbCDe"), Some("äbcd"));
+
+        // An empty prefix matches everything.
+        let needle = TrieNeedle::new([""]);
+        assert_eq!(needle.matching_prefix("anything"), Some(""));
+        assert!(needle.is_match(""));
+
+        let needle = TrieNeedle::new(Vec::<String>::new());
+        assert!(needle.is_empty());
+        assert!(!needle.is_match(""));
+    }
+
+    #[test]
+    fn test_trie_needle_matches_any_needle() {
+        let prefixes: Vec<String> =
+            (0..500).map(|i| format!("{}-{}", ["Ab", "abc", "Zé", "x"][i % 4], i * 7)).collect();
+        let haystacks: Vec<String> = (0..2000)
+            .map(|i| format!("{}-{}{}", ["ab", "ABC", "zÉ", "X", "y"][i % 5], i, i % 3))
+            .chain([String::new(), String::from("abc"), String::from("Ab-")])
+            .collect();
+
+        for case_sensitive in [true, false] {
+            let trie = match case_sensitive {
+                true => TrieNeedle::new(&prefixes),
+                false => TrieNeedle::new(&prefixes).case_insensitive(),
+            };
+            let any: AnyNeedle = prefixes
+                .iter()
+                .map(|p| match case_sensitive {
+                    true => StringMatch::from(p.as_str()).prefix(),
+                    false => StringMatch::from(p.as_str()).prefix().case_insensitive(),
+                })
+                .collect();
+            for haystack in &haystacks {
+                assert_eq!(trie.is_match(haystack), any.is_match(haystack), "{:?}", haystack);
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_properties::{GeneralCategory, GeneralCategoryGroup, UnicodeGeneralCategory};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The Unicode normalization form applied by `StringMatch::normalize_unicode()`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub enum NormalizeForm {
+    /// Do not normalize. This is the default.
+    #[default]
+    None,
+    /// Canonical composition, e.g. `"e\u{301}"` becomes `"é"`.
+    Nfc,
+    /// Canonical decomposition, e.g. `"é"` becomes `"e\u{301}"`.
+    Nfd,
+}
+
+/// Normalize `s` to the given form.
+pub(crate) fn normalize(s: &str, form: NormalizeForm) -> String {
+    match form {
+        NormalizeForm::None => String::from(s),
+        NormalizeForm::Nfc => s.nfc().collect(),
+        NormalizeForm::Nfd => s.nfd().collect(),
+    }
+}
+
+/// Normalize `s` to the given form, returning each character of the result along with the
+/// byte offset in `s` of the grapheme cluster that produced it.
+///
+/// Each grapheme cluster is normalized separately, which gives the same result as
+/// normalizing the whole string since canonical composition does not cross cluster
+/// boundaries.
+pub(crate) fn normalize_with_offsets(s: &str, form: NormalizeForm) -> Vec<(usize, char)> {
+    s.grapheme_indices(true)
+        .flat_map(|(i, g)| normalize(g, form).chars().map(move |c| (i, c)).collect::<Vec<_>>())
+        .collect()
+}
+
+/// Decompose `c` (NFD) and drop any nonspacing marks, e.g. `'é'` becomes `'e'`.
+pub(crate) fn strip_char_diacritics(c: char) -> impl Iterator<Item = char> {
+    core::iter::once(c).nfd().filter(|c| c.general_category() != GeneralCategory::NonspacingMark)
+}
+
+/// Remove diacritics from `s`. See `strip_char_diacritics()`.
+pub(crate) fn strip_diacritics(s: &str) -> String {
+    s.chars().flat_map(strip_char_diacritics).collect()
+}
+
+/// Return true if `c` is in the Unicode punctuation category (`General_Category=P`).
+pub(crate) fn is_punctuation(c: char) -> bool {
+    c.general_category_group() == GeneralCategoryGroup::Punctuation
+}
+
+/// Return true if `c` is a Unicode decimal digit (`General_Category=Nd`).
+pub(crate) fn is_decimal_digit(c: char) -> bool {
+    c.general_category() == GeneralCategory::DecimalNumber
+}
+
+/// Return the byte range of the first occurrence of `needle` in `haystack` that starts and
+/// ends on a Unicode (UAX #29) word boundary.
+///
+/// The whole needle must match, including any punctuation, so `"c++"` does not match `"c"`.
+/// Scripts that are written without spaces, such as Thai and CJK, are segmented into
+/// individual characters (or grapheme clusters) since no dictionary is used. A needle with
+/// no words only matches a haystack with no words.
+pub(crate) fn find_unicode_word(needle: &str, haystack: &str) -> Option<Range<usize>> {
+    if needle.unicode_words().next().is_none() {
+        return haystack.unicode_words().next().is_none().then_some(0..0);
+    }
+    let boundaries: Vec<usize> = haystack
+        .split_word_bound_indices()
+        .map(|(i, _)| i)
+        .chain(core::iter::once(haystack.len()))
+        .collect();
+    let is_boundary = |i: usize| boundaries.binary_search(&i).is_ok();
+
+    let mut pos = 0;
+    while let Some(offset) = str::find(&haystack[pos..], needle) {
+        let start = pos + offset;
+        let end = start + needle.len();
+        if is_boundary(start) && is_boundary(end) {
+            return Some(start..end);
+        }
+        pos = start + haystack[start..].chars().next()?.len_utf8();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FindableNeedle, MatchSpan, Needle, NormalizeForm, StringMatch};
+    use core::ops::Range;
+
+    #[test]
+    fn test_unicode_words() {
+        // Punctuation is a word boundary.
+        assert!(StringMatch::from("world").word().is_match("hello, world!"));
+        assert!(StringMatch::from("hello, world").word().is_match("hello, world!"));
+        assert!(!StringMatch::from("hello world").word().is_match("hello, world!"));
+        assert!(!StringMatch::from("wor").word().is_match("hello, world!"));
+        assert!(StringMatch::from("don't").word().is_match("please don't stop"));
+        assert!(StringMatch::from("a").word().is_match("aa a aa"));
+        assert!(!StringMatch::from("aaa aa").word().is_match("aa aaa aaa"));
+        assert!(StringMatch::from("ab ab").word().is_match("abab ab ab"));
+
+        // Punctuation in the needle must match too.
+        assert!(!StringMatch::from("c++").word().is_match("c"));
+        assert!(!StringMatch::from("c++").word().is_match("learn c now"));
+        assert!(StringMatch::from("c++").word().is_match("learn c++ now"));
+        assert!(StringMatch::from("c").word().is_match("learn c++ now"));
+        assert!(StringMatch::from("(a)").word().is_match("x (a) y"));
+
+        // Thai is segmented per character cluster, so runs of clusters can be matched.
+        assert!(StringMatch::from("ไทย").word().is_match("ภาษาไทยง่าย"));
+        assert!(!StringMatch::from("ไทย").word().is_match("ภาษาอังกฤษ"));
+
+        // CJK ideographs are individual words.
+        assert!(StringMatch::from("日本").word().is_match("日本語のテキスト"));
+        assert!(StringMatch::from("テキスト").word().is_match("日本語のテキスト"));
+        assert!(!StringMatch::from("テキ").word().is_match("日本語のテキスト"));
+
+        // Combining characters belong to the word they follow.
+        assert!(StringMatch::from("cafe\u{301}").word().is_match("un cafe\u{301} noir"));
+        assert!(!StringMatch::from("cafe").word().is_match("un cafe\u{301} noir"));
+        assert!(!StringMatch::from("ง").word().is_match("ง่าย"));
+
+        assert!(StringMatch::from("").word().is_match(""));
+        assert!(StringMatch::from("").word().is_match(", !"));
+        assert!(!StringMatch::from("").word().is_match("a"));
+
+        assert!(StringMatch::from("WORLD").word().case_insensitive().is_match("Hello, World!"));
+
+        // Custom separators bypass Unicode segmentation.
+        assert!(!StringMatch::from("world").word_with_separators(" ").is_match("hello, world!"));
+    }
+
+    #[test]
+    fn test_strip_diacritics() {
+        let sm = StringMatch::from("cafe").strip_diacritics();
+        assert!(sm.is_diacritic_insensitive());
+        assert!(!StringMatch::from("cafe").is_diacritic_insensitive());
+        assert!(sm.is_match("café"));
+        assert!(sm.is_match("cafe\u{301}"));
+        assert!(sm.is_match("cafe"));
+        assert!(!sm.is_match("Café"));
+        assert!(!StringMatch::from("cafe").is_match("café"));
+        // The needle is stripped too.
+        assert!(StringMatch::from("café").strip_diacritics().is_match("cafe"));
+
+        // Latin.
+        assert!(StringMatch::from("Creme Brulee").strip_diacritics().is_match("Crème Brûlée"));
+        assert!(StringMatch::from("naive").strip_diacritics().is_match("naïve"));
+        assert!(StringMatch::from("Ångstrom").strip_diacritics().is_match("Ångström"));
+        // Greek, including polytonic.
+        assert!(StringMatch::from("Ωμεγα").strip_diacritics().is_match("Ωμέγα"));
+        assert!(StringMatch::from("αλφα").strip_diacritics().is_match("ἄλφα"));
+        // Vietnamese, with stacked diacritics.
+        assert!(StringMatch::from("Tieng Viet").strip_diacritics().is_match("Tiếng Việt"));
+        assert!(StringMatch::from("pho").strip_diacritics().is_match("phở"));
+        // Letters that don't decompose are left alone.
+        assert!(!StringMatch::from("do").strip_diacritics().is_match("đo"));
+
+        // Pure ASCII is unaffected.
+        for text in ["hello", "Hello, World!", "", "a-b_c 123"] {
+            assert!(StringMatch::from(text).strip_diacritics().is_match(text));
+        }
+        assert!(!StringMatch::from("hello").strip_diacritics().is_match("hallo"));
+
+        // Composes with case insensitivity and match length.
+        let sm = StringMatch::from("CAFE").strip_diacritics().case_insensitive();
+        assert!(sm.is_match("café"));
+        assert!(sm.clone().partial().is_match("un café noir"));
+        assert!(sm.clone().word().is_match("un café noir"));
+        assert!(!sm.clone().word().is_match("un cafés noir"));
+        assert!(sm.clone().prefix().is_match("Cafétéria"));
+    }
+
+    #[test]
+    fn test_strip_diacritics_find() {
+        let sm = StringMatch::from("cafe").partial().strip_diacritics().case_insensitive();
+        let haystack = "un CAFÉ, un cafe\u{301}";
+        let spans: Vec<&str> = sm.find_all(haystack).map(|span| span.as_str(haystack)).collect();
+        assert_eq!(spans, ["CAFÉ", "cafe\u{301}"]);
+        assert_eq!(sm.replace_all(haystack, "tea"), "un tea, un tea");
+    }
+
+    #[cfg(feature = "serde_derive")]
+    #[test]
+    fn test_strip_diacritics_serde() {
+        let orig = StringMatch::from("cafe").strip_diacritics();
+        let serialized = serde_json::to_string(&orig).unwrap();
+        assert!(serialized.contains(r#""strip_diacritics":true"#));
+        let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+
+        let orig = StringMatch::from("café").nfd();
+        let serialized = serde_json::to_string(&orig).unwrap();
+        assert!(serialized.contains(r#""normalize_unicode":"Nfd""#));
+        let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+    }
+
+    #[test]
+    fn test_unicode_word_spans() {
+        let haystack = "hello,  world!";
+        let span = StringMatch::from("hello,  world").word().find(haystack).unwrap();
+        assert_eq!(span, MatchSpan::new(haystack, 0, 13));
+        assert_eq!(span.as_str(haystack), "hello,  world");
+        assert_eq!(StringMatch::from("o").word().count("o o, o!"), 3);
+    }
+
+    #[test]
+    fn test_normalize_unicode() {
+        assert!(StringMatch::new("é").nfc().is_match("e\u{301}"));
+        assert!(StringMatch::new("e\u{301}").nfc().is_match("é"));
+        assert!(StringMatch::new("é").nfd().is_match("e\u{301}"));
+        assert!(StringMatch::new("é").nfd().is_match("é"));
+        assert!(!StringMatch::new("é").is_match("e\u{301}"));
+        assert_eq!(StringMatch::new("é").nfd().normalize_form(), NormalizeForm::Nfd);
+        assert_eq!(StringMatch::new("é").normalize_form(), NormalizeForm::None);
+        assert!(StringMatch::new("é").nfc().is_unicode_normalized());
+        assert!(!StringMatch::new("é").is_unicode_normalized());
+        assert_eq!(
+            StringMatch::new("é").normalize_unicode(NormalizeForm::Nfc),
+            StringMatch::new("é").nfc()
+        );
+
+        // Case-insensitive matching applies to the normalized text.
+        assert!(StringMatch::new("CAFÉ").nfc().case_insensitive().is_match("cafe\u{301}"));
+        assert!(StringMatch::new("CAFE\u{301}").nfd().case_insensitive().is_match("café"));
+        assert!(StringMatch::new("é").partial().nfc().case_insensitive().is_match("xE\u{301}x"));
+        assert!(!StringMatch::new("e").partial().nfc().is_match("e\u{301}"));
+        assert!(StringMatch::new("e").partial().nfd().is_match("é"));
+
+        // Hangul jamo compose into a syllable.
+        assert!(StringMatch::new("\u{ac00}").nfc().is_match("\u{1100}\u{1161}"));
+    }
+
+    #[test]
+    fn test_find_normalized() {
+        let sm = StringMatch::new("é").partial().nfc();
+        assert_eq!(sm.find("cafe\u{301}!").map(Range::from), Some(3..6));
+        assert_eq!(sm.find("café!").map(Range::from), Some(3..5));
+        assert_eq!(sm.clone().case_insensitive().count("É e\u{301} é"), 3);
+
+        let sm = StringMatch::new("e").partial().nfd();
+        assert_eq!(sm.find("café").map(Range::from), Some(3..5));
+        assert_eq!(StringMatch::new("e\u{301}").word().nfc().find("un cafe\u{301}"), None);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{Needle, NeedleIter};
+use std::collections::{BTreeSet, HashSet};
+use std::hash::BuildHasher;
+use std::io;
+use std::path::Path;
+
+impl<S> Needle for HashSet<String, S>
+where
+    S: BuildHasher,
+{
+    fn is_match(&self, haystack: &str) -> bool {
+        self.contains(haystack)
+    }
+}
+
+impl<S> NeedleIter for HashSet<String, S> where S: BuildHasher {}
+
+impl Needle for BTreeSet<String> {
+    fn is_match(&self, haystack: &str) -> bool {
+        self.contains(haystack)
+    }
+}
+
+impl NeedleIter for BTreeSet<String> {}
+
+/// Needle that matches any haystack that is exactly equal to one of a set of words.
+///
+/// Lookups are O(1). In case-insensitive mode the words are stored lowercased and the
+/// haystack is lowercased before lookup.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VocabularyNeedle {
+    words: HashSet<String>,
+    /// If true, use a case-sensitive match. Default is true.
+    case_sensitive: bool,
+}
+
+impl VocabularyNeedle {
+    pub fn new(words: HashSet<String>) -> Self {
+        Self {
+            words,
+            case_sensitive: true,
+        }
+    }
+
+    /// Read the words from a file containing one word per line.
+    ///
+    /// A trailing `'\r'` is removed from each line, and empty lines are skipped.
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(contents.lines().filter(|line| !line.is_empty()).collect())
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Add a word, which is lowercased in case-insensitive mode.
+    pub fn insert(&mut self, word: impl Into<String>) {
+        let word = word.into();
+        match self.case_sensitive {
+            true => self.words.insert(word),
+            false => self.words.insert(word.to_lowercase()),
+        };
+    }
+
+    /// Remove a word. Returns false if the word was not present.
+    ///
+    /// In case-insensitive mode this removes the word regardless of case.
+    pub fn remove(&mut self, word: &str) -> bool {
+        match self.case_sensitive {
+            true => self.words.remove(word),
+            false => self.words.remove(&word.to_lowercase()),
+        }
+    }
+
+    /// The number of distinct words. In case-insensitive mode, words that differ only by
+    /// case count once.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn case_insensitive(mut self) -> Self {
+        if self.case_sensitive {
+            self.words = self.words.into_iter().map(|w| w.to_lowercase()).collect();
+            self.case_sensitive = false;
+        }
+        self
+    }
+}
+
+/// The default `VocabularyNeedle` is empty and case-sensitive.
+impl Default for VocabularyNeedle {
+    fn default() -> Self {
+        Self::new(HashSet::new())
+    }
+}
+
+impl From<HashSet<String>> for VocabularyNeedle {
+    fn from(words: HashSet<String>) -> Self {
+        Self::new(words)
+    }
+}
+
+impl<S> FromIterator<S> for VocabularyNeedle
+where
+    S: Into<String>,
+{
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        Self::new(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Needle for VocabularyNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        match self.case_sensitive {
+            true => self.words.contains(haystack),
+            false => self.words.contains(&haystack.to_lowercase()),
+        }
+    }
+}
+
+impl NeedleIter for VocabularyNeedle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("Word{}", i)).collect()
+    }
+
+    #[test]
+    fn test_sets() {
+        let hash_set: HashSet<String> = ["Test", "foo"].iter().map(|s| s.to_string()).collect();
+        assert!(hash_set.is_match("Test"));
+        assert!(hash_set.is_match("foo"));
+        assert!(!hash_set.is_match("test"));
+        assert!(!hash_set.is_match("Tes"));
+        assert!(hash_set.is_match_in(&mut vec!["bar", "foo"].into_iter()));
+
+        let btree_set: BTreeSet<String> = ["Test", "foo"].iter().map(|s| s.to_string()).collect();
+        assert!(btree_set.is_match("Test"));
+        assert!(btree_set.is_match("foo"));
+        assert!(!btree_set.is_match("test"));
+        assert!(!btree_set.is_match("Tes"));
+        assert!(!btree_set.is_match_in(&mut vec!["bar", "baz"].into_iter()));
+    }
+
+    #[test]
+    fn test_large_set() {
+        let list = words(10_000);
+        let set: HashSet<String> = list.iter().cloned().collect();
+        let scan = |s: &str| list.iter().any(|w| w == s);
+
+        for haystack in ["Word0", "Word9999", "Word5000", "Word10000", "word1", ""] {
+            assert_eq!(set.is_match(haystack), scan.is_match(haystack));
+        }
+    }
+
+    #[test]
+    fn test_vocabulary_needle() {
+        let vocab = VocabularyNeedle::new(words(10_000).into_iter().collect());
+        assert!(vocab.is_case_sensitive());
+        assert!(vocab.is_match("Word42"));
+        assert!(!vocab.is_match("word42"));
+        assert!(!vocab.is_match("Word"));
+
+        let vocab = vocab.case_insensitive();
+        assert!(!vocab.is_case_sensitive());
+        assert!(vocab.is_match("Word42"));
+        assert!(vocab.is_match("word42"));
+        assert!(vocab.is_match("WORD42"));
+        assert!(!vocab.is_match("Word"));
+        assert!(vocab.is_match_in(&mut vec!["x", "WORD1"].into_iter()));
+
+        let mut vocab = VocabularyNeedle::default();
+        assert!(vocab.is_case_sensitive());
+        assert!(!vocab.is_match(""));
+        vocab.insert("A");
+        assert!(vocab.is_match("A"));
+        assert!(!vocab.is_match("a"));
+    }
+
+    #[test]
+    fn test_vocabulary_needle_edit() {
+        let mut vocab = VocabularyNeedle::from_iter(vec!["apple", "Banana"]);
+        assert_eq!(vocab.len(), 2);
+        vocab.insert(String::from("cherry"));
+        vocab.insert("apple");
+        assert_eq!(vocab.len(), 3);
+        assert!(vocab.is_match("cherry"));
+        assert!(vocab.remove("apple"));
+        assert!(!vocab.remove("apple"));
+        assert!(!vocab.remove("banana"));
+        assert!(!vocab.is_match("apple"));
+        assert_eq!(vocab.len(), 2);
+
+        let mut vocab = vocab.case_insensitive();
+        vocab.insert("DATE");
+        assert!(vocab.is_match("date"));
+        assert!(vocab.is_match("Date"));
+        vocab.insert("Date");
+        assert_eq!(vocab.len(), 3);
+        assert!(vocab.remove("BANANA"));
+        assert!(!vocab.is_match("banana"));
+
+        let mut vocab: VocabularyNeedle = Vec::<String>::new().into_iter().collect();
+        assert!(vocab.is_empty());
+        for word in words(100_000) {
+            vocab.insert(word);
+        }
+        assert_eq!(vocab.len(), 100_000);
+        assert!(vocab.is_match("Word0"));
+        assert!(vocab.is_match("Word99999"));
+        assert!(!vocab.is_match("Word100000"));
+    }
+
+    #[test]
+    fn test_vocabulary_from_file() {
+        let path =
+            std::env::temp_dir().join(format!("stringmatch-vocab-{}.txt", std::process::id()));
+        std::fs::write(&path, "alpha\nBeta\r\n\ngamma delta\n").unwrap();
+        let vocab = VocabularyNeedle::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        let vocab = vocab.unwrap();
+        assert_eq!(vocab.len(), 3);
+        assert!(vocab.is_match("alpha"));
+        assert!(vocab.is_match("Beta"));
+        assert!(vocab.is_match("gamma delta"));
+        assert!(!vocab.is_match(""));
+        assert!(!vocab.is_match("Beta\r"));
+
+        let missing = std::env::temp_dir().join("stringmatch-vocab-missing.txt");
+        assert!(VocabularyNeedle::from_file(&missing).is_err());
+    }
+}
@@ -0,0 +1,488 @@
+use crate::{Needle, NeedleIter, StringMatch, StringMatchLength, PUNCTUATION_GAP};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Serialize};
+
+/// Needle supporting simple wildcards: `*` matches any sequence of characters (including none)
+/// and `?` matches exactly one character. All other characters are literal.
+///
+/// By default the pattern must match the whole haystack.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub struct WildcardNeedle {
+    pattern: String,
+    /// If true, the pattern may match any substring of the haystack. Default is false.
+    partial: bool,
+    /// If true, use a case-sensitive match. Default is true.
+    case_sensitive: bool,
+}
+
+impl WildcardNeedle {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            partial: false,
+            case_sensitive: true,
+        }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn is_full_match(&self) -> bool {
+        !self.partial
+    }
+
+    pub fn is_partial_match(&self) -> bool {
+        self.partial
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    pub fn partial(mut self) -> Self {
+        self.partial = true;
+        self
+    }
+
+    pub fn full(mut self) -> Self {
+        self.partial = false;
+        self
+    }
+
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_sensitive = false;
+        self
+    }
+
+    pub fn case_sensitive(mut self) -> Self {
+        self.case_sensitive = true;
+        self
+    }
+}
+
+/// A single element of a wildcard pattern.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Token {
+    /// A literal character.
+    Char(char),
+    /// `?`, which matches exactly one character.
+    AnyChar,
+    /// `*`, which matches any sequence of characters, including none.
+    AnyChars,
+    /// `PUNCTUATION_GAP`, which matches any sequence of whitespace characters, including none.
+    Whitespace,
+}
+
+impl Token {
+    /// Return true if the token can match without consuming any characters.
+    fn is_optional(self) -> bool {
+        matches!(self, Token::AnyChars | Token::Whitespace)
+    }
+}
+
+/// Split a pattern into tokens. `*` and `?` are wildcards if `wildcard` is true, and
+/// `PUNCTUATION_GAP` matches whitespace if `gaps` is true.
+fn tokenize(pattern: &str, wildcard: bool, gaps: bool) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        let token = match c {
+            '*' if wildcard => Token::AnyChars,
+            '?' if wildcard => Token::AnyChar,
+            PUNCTUATION_GAP if gaps => Token::Whitespace,
+            c => Token::Char(c),
+        };
+        // Consecutive `*`s match the same as a single one.
+        if !(token == Token::AnyChars && tokens.last() == Some(&Token::AnyChars)) {
+            tokens.push(token);
+        }
+    }
+    tokens
+}
+
+/// Record that a match starting at `start` has reached token `pos`, along with every later
+/// token that can be reached by skipping optional tokens. Each state keeps the earliest start.
+fn add_state(states: &mut [Option<usize>], tokens: &[Token], mut pos: usize, start: usize) {
+    loop {
+        if states[pos].is_some_and(|s| s <= start) {
+            return;
+        }
+        states[pos] = Some(start);
+        if pos == tokens.len() || !tokens[pos].is_optional() {
+            return;
+        }
+        pos += 1;
+    }
+}
+
+/// Search `haystack` for the pattern in a single pass, tracking every partial match at once.
+///
+/// A match may only start at a byte offset for which `is_start` is true and end at one for
+/// which `is_end` is true. Both are given the characters before and after the offset, if
+/// any. If `longest` is true this returns the leftmost match, choosing the longest of the
+/// matches that start there. Otherwise it returns the first match to end. This takes time
+/// proportional to the length of the haystack times the length of the pattern.
+fn search(
+    tokens: &[Token],
+    haystack: &str,
+    is_start: impl Fn(Option<char>, Option<char>) -> bool,
+    is_end: impl Fn(Option<char>, Option<char>) -> bool,
+    longest: bool,
+) -> Option<Range<usize>> {
+    // `states[i]` is the earliest start of a partial match that has matched the first `i`
+    // tokens, if any.
+    let mut states = alloc::vec![None; tokens.len() + 1];
+    let mut next_states = states.clone();
+    let mut best: Option<Range<usize>> = None;
+    let mut prev = None;
+    let mut chars = haystack.char_indices().peekable();
+    loop {
+        let (offset, next) = match chars.peek() {
+            Some(&(offset, c)) => (offset, Some(c)),
+            None => (haystack.len(), None),
+        };
+        // A match starting any later can't be the leftmost one.
+        if best.is_none() && is_start(prev, next) {
+            add_state(&mut states, tokens, 0, offset);
+        }
+        if let Some(start) = states[tokens.len()] {
+            if is_end(prev, next) && best.as_ref().is_none_or(|b| start <= b.start) {
+                best = Some(start..offset);
+                if !longest {
+                    return best;
+                }
+            }
+        }
+        let Some((_, c)) = chars.next() else {
+            return best;
+        };
+
+        next_states.fill(None);
+        for (pos, start) in states.iter().enumerate() {
+            let Some(start) = *start else {
+                continue;
+            };
+            if pos == tokens.len() || best.as_ref().is_some_and(|b| start > b.start) {
+                continue;
+            }
+            match tokens[pos] {
+                Token::Char(t) if t == c => add_state(&mut next_states, tokens, pos + 1, start),
+                Token::Char(_) => {}
+                Token::AnyChar => add_state(&mut next_states, tokens, pos + 1, start),
+                Token::AnyChars => add_state(&mut next_states, tokens, pos, start),
+                Token::Whitespace if c.is_whitespace() => {
+                    add_state(&mut next_states, tokens, pos, start)
+                }
+                Token::Whitespace => {}
+            }
+        }
+        core::mem::swap(&mut states, &mut next_states);
+        if best.is_some() && states.iter().all(Option::is_none) {
+            return best;
+        }
+        prev = Some(c);
+    }
+}
+
+/// Search for `tokens` using the given match length. In `Word` mode words are separated by
+/// `separators`, or by spaces.
+fn search_with_length(
+    tokens: &[Token],
+    haystack: &str,
+    match_length: &StringMatchLength,
+    separators: Option<&str>,
+    longest: bool,
+) -> Option<Range<usize>> {
+    let is_separator = |c: Option<char>| match (c, separators) {
+        (None, _) => true,
+        (Some(c), Some(seps)) => seps.contains(c),
+        (Some(c), None) => c == ' ',
+    };
+    let is_start = |prev: Option<char>, _| match match_length {
+        StringMatchLength::Full | StringMatchLength::Prefix => prev.is_none(),
+        StringMatchLength::Partial | StringMatchLength::Suffix => true,
+        StringMatchLength::Word => is_separator(prev),
+    };
+    let is_end = |_, next: Option<char>| match match_length {
+        StringMatchLength::Full | StringMatchLength::Suffix => next.is_none(),
+        StringMatchLength::Partial | StringMatchLength::Prefix => true,
+        StringMatchLength::Word => is_separator(next),
+    };
+    search(tokens, haystack, is_start, is_end, longest)
+}
+
+/// Tokenize a needle prepared by `sm`, for which `StringMatch::is_pattern()` is true.
+fn tokenize_needle(sm: &StringMatch, needle: &str) -> Vec<Token> {
+    tokenize(needle, sm.wildcard, sm.ignore_punctuation)
+}
+
+/// Return true if a prepared needle, containing wildcards or punctuation gaps, matches a
+/// prepared haystack using the match length and separators of `sm`.
+pub(crate) fn pattern_in_haystack(sm: &StringMatch, needle: &str, haystack: &str) -> bool {
+    let tokens = tokenize_needle(sm, needle);
+    search_with_length(&tokens, haystack, &sm.match_length, sm.separators(), false).is_some()
+}
+
+/// Return the byte range of the leftmost match of a prepared needle, containing wildcards or
+/// punctuation gaps, in a prepared haystack. Of the matches that start there, the longest is
+/// chosen.
+pub(crate) fn find_pattern(sm: &StringMatch, needle: &str, haystack: &str) -> Option<Range<usize>> {
+    let tokens = tokenize_needle(sm, needle);
+    search_with_length(&tokens, haystack, &sm.match_length, sm.separators(), true)
+}
+
+impl Needle for WildcardNeedle {
+    fn is_match(&self, haystack: &str) -> bool {
+        let match_length = match self.partial {
+            true => StringMatchLength::Partial,
+            false => StringMatchLength::Full,
+        };
+        let is_match = |pattern: &str, haystack: &str| {
+            let tokens = tokenize(pattern, true, false);
+            search_with_length(&tokens, haystack, &match_length, None, false).is_some()
+        };
+        match self.case_sensitive {
+            true => is_match(&self.pattern, haystack),
+            false => is_match(&self.pattern.to_lowercase(), &haystack.to_lowercase()),
+        }
+    }
+}
+
+impl NeedleIter for WildcardNeedle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard() {
+        let needle = WildcardNeedle::new("foo*");
+        assert!(needle.is_full_match());
+        assert!(needle.is_case_sensitive());
+        assert_eq!(needle.pattern(), "foo*");
+        assert!(needle.is_match("foo")); // `*` matches empty.
+        assert!(needle.is_match("foobar"));
+        assert!(!needle.is_match("xfoo"));
+        assert!(!needle.is_match("FOO"));
+
+        assert!(WildcardNeedle::new("*").is_match(""));
+        assert!(WildcardNeedle::new("**").is_match("anything"));
+        assert!(!WildcardNeedle::new("?").is_match(""));
+        assert!(WildcardNeedle::new("").is_match(""));
+        assert!(!WildcardNeedle::new("").is_match("a"));
+
+        assert!(WildcardNeedle::new("a?c").is_match("abc"));
+        assert!(WildcardNeedle::new("a?c").is_match("aéc"));
+        assert!(WildcardNeedle::new("??").is_match("日本"));
+        assert!(!WildcardNeedle::new("a?c").is_match("ac"));
+        assert!(!WildcardNeedle::new("a?c").is_match("abbc"));
+
+        // Nested wildcards, requiring backtracking.
+        assert!(WildcardNeedle::new("*a*b?c*").is_match("xxaxxbyc"));
+        assert!(WildcardNeedle::new("*a*b?c*").is_match("abbcc"));
+        assert!(!WildcardNeedle::new("*a*b?c*").is_match("abc"));
+        assert!(WildcardNeedle::new("a*a*a").is_match("aaa"));
+        assert!(!WildcardNeedle::new("a*a*a").is_match("aa"));
+        assert!(WildcardNeedle::new("*?*?*").is_match("ab"));
+        assert!(!WildcardNeedle::new("*?*?*").is_match("a"));
+
+        // Other characters are literal.
+        assert!(WildcardNeedle::new("a.b").is_match("a.b"));
+        assert!(!WildcardNeedle::new("a.b").is_match("axb"));
+        assert!(WildcardNeedle::new("[ab]").is_match("[ab]"));
+    }
+
+    #[test]
+    fn test_wildcard_modes() {
+        let needle = WildcardNeedle::new("b?r").partial();
+        assert!(needle.is_partial_match());
+        assert!(!needle.is_full_match());
+        assert!(needle.is_match("foobarbaz"));
+        assert!(needle.is_match("bar"));
+        assert!(!needle.is_match("foobaz"));
+        assert!(!needle.clone().full().is_match("foobarbaz"));
+
+        let needle = WildcardNeedle::new("FOO*").case_insensitive();
+        assert!(!needle.is_case_sensitive());
+        assert!(needle.is_match("foobar"));
+        assert!(needle.is_match("FoO"));
+        assert!(!needle.clone().case_sensitive().is_match("foobar"));
+        assert!(WildcardNeedle::new("é?").case_insensitive().is_match("ÉA"));
+
+        assert!(needle.is_match_in(&mut vec!["bar", "food"].into_iter()));
+    }
+
+    #[test]
+    fn test_from_wildcard() {
+        let patterns =
+            ["", "*", "**", "?", "??", "foo*", "*foo", "a?c", "*a*b?c*", "a*a*a", "a.b", "[ab]"];
+        let haystacks = [
+            "", "a", "ab", "abc", "aéc", "日本", "foo", "foobar", "xfoo", "FOO", "aaa", "a.b",
+            "axb", "[ab]", "xxaxxbyc", "abbcc", "a\nb",
+        ];
+        for pattern in patterns {
+            let sm = StringMatch::from_wildcard(pattern);
+            assert!(sm.is_wildcard());
+            assert_eq!(sm.text(), pattern);
+            let variants = [
+                (sm.clone(), WildcardNeedle::new(pattern)),
+                (sm.clone().partial(), WildcardNeedle::new(pattern).partial()),
+                (sm.clone().case_insensitive(), WildcardNeedle::new(pattern).case_insensitive()),
+                (
+                    sm.clone().partial().case_insensitive(),
+                    WildcardNeedle::new(pattern).partial().case_insensitive(),
+                ),
+            ];
+            for (sm, wildcard) in &variants {
+                for haystack in haystacks {
+                    assert_eq!(
+                        sm.is_match(haystack),
+                        wildcard.is_match(haystack),
+                        "{sm:?} {haystack:?}"
+                    );
+                    assert_eq!(
+                        sm.clone().compile().is_match(haystack),
+                        wildcard.is_match(haystack)
+                    );
+                }
+            }
+        }
+        assert!(!StringMatch::new("a*").is_wildcard());
+        assert!(!StringMatch::new("a*").is_match("ab"));
+        assert!(StringMatch::from_wildcard("FOO*").case_insensitive().is_match("fOobar"));
+        assert!(StringMatch::from_wildcard("é?").case_insensitive().is_match("ÉA"));
+    }
+
+    #[test]
+    fn test_wildcard_options() {
+        let sm = StringMatch::from_wildcard("b?r").prefix();
+        assert!(sm.is_match("bar baz"));
+        assert!(!sm.is_match("foobar"));
+        let sm = StringMatch::from_wildcard("b*z").suffix();
+        assert!(sm.is_match("foo bar baz"));
+        assert!(!sm.is_match("bazaar"));
+
+        let sm = StringMatch::from_wildcard("b?r").word();
+        assert!(sm.is_match("foo bar baz"));
+        assert!(!sm.is_match("foobar baz"));
+        assert!(StringMatch::from_wildcard("f* b*").word().is_match("x foo bar y"));
+        assert!(StringMatch::from_wildcard("b?r").word_with_separators(",").is_match("a,bar,c"));
+
+        // The wildcards survive the options that transform the needle.
+        let sm = StringMatch::from_wildcard("a*, b?").ignore_punctuation().normalize_whitespace();
+        assert!(sm.is_match("a... ,b!c"));
+        assert!(!sm.is_match("a,b"));
+        assert!(StringMatch::from_wildcard("foo?").line_by_line().is_match("x\nfoo1\ny"));
+        assert!(!StringMatch::from_wildcard("foo*").ascii_only().is_match("fooé"));
+    }
+
+    #[test]
+    fn test_wildcard_find() {
+        use crate::FindableNeedle;
+
+        let spans = |sm: &StringMatch, haystack: &str| -> Vec<(usize, usize)> {
+            sm.find_all(haystack).map(|s| (s.byte_start, s.byte_end)).collect()
+        };
+        let sm = StringMatch::from_wildcard("a?c").partial();
+        assert_eq!(spans(&sm, "abc aéc ac"), [(0, 3), (4, 8)]);
+        let sm = StringMatch::from_wildcard("a*c").partial();
+        assert_eq!(spans(&sm, "xabcac x"), [(1, 6)]);
+        let sm = StringMatch::from_wildcard("b*").word();
+        assert_eq!(spans(&sm, "abc bcd b"), [(4, 9)]);
+        let sm = StringMatch::from_wildcard("b?").word();
+        assert_eq!(spans(&sm, "abc bc bd b"), [(4, 6), (7, 9)]);
+        let sm = StringMatch::from_wildcard("A?").partial().case_insensitive();
+        assert_eq!(sm.replace_all("xaby Ac", "-"), "x-y -");
+    }
+
+    /// Return true if the whole of `text` matches `pattern`, by trying every way of matching
+    /// each `*`.
+    fn brute_force_match(pattern: &[char], text: &[char]) -> bool {
+        match pattern.split_first() {
+            None => text.is_empty(),
+            Some(('*', rest)) => (0..=text.len()).any(|i| brute_force_match(rest, &text[i..])),
+            Some((&p, rest)) => {
+                !text.is_empty()
+                    && (p == '?' || p == text[0])
+                    && brute_force_match(rest, &text[1..])
+            }
+        }
+    }
+
+    #[test]
+    fn test_wildcard_find_leftmost_longest() {
+        use crate::FindableNeedle;
+
+        let patterns = ["a*b", "*", "?", "a?", "*a", "a*", "b*a*b", "?*?", "ab", "", "a**b?"];
+        let haystacks = ["", "a", "ab", "ba", "aab", "abab", "bbaab", "abcab", "é aéb", "xaby"];
+        for pattern in patterns {
+            let pattern_chars: Vec<char> = pattern.chars().collect();
+            for haystack in haystacks {
+                let text: Vec<char> = haystack.chars().collect();
+                let char_offset = |i: usize| text[..i].iter().map(|c| c.len_utf8()).sum::<usize>();
+                let expected = (0..=text.len()).find_map(|s| {
+                    let e = (s..=text.len())
+                        .rev()
+                        .find(|&e| brute_force_match(&pattern_chars, &text[s..e]))?;
+                    Some(char_offset(s)..char_offset(e))
+                });
+                let sm = StringMatch::from_wildcard(pattern).partial();
+                assert_eq!(sm.find(haystack).map(Range::from), expected, "{pattern} {haystack}");
+                assert_eq!(sm.is_match(haystack), expected.is_some());
+            }
+        }
+
+        // This takes time proportional to the haystack length, rather than its square.
+        let haystack = "a".repeat(100_000);
+        let sm = StringMatch::from_wildcard("a*b").partial();
+        assert_eq!(sm.find(&haystack), None);
+        assert_eq!(sm.count(&haystack), 0);
+        assert_eq!(sm.replace_all(&haystack, "-"), haystack);
+        let sm = StringMatch::from_wildcard("a?a").partial();
+        assert_eq!(sm.count(&haystack), 33_333);
+    }
+
+    #[cfg(feature = "serde_derive")]
+    #[test]
+    fn test_serde() {
+        let orig = WildcardNeedle::new("a*b?").partial().case_insensitive();
+        let serialized = serde_json::to_string(&orig).unwrap();
+        let deserialized: WildcardNeedle = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, orig);
+
+        let orig = StringMatch::from_wildcard("a*b?").partial().case_insensitive();
+        let serialized = serde_json::to_string(&orig).unwrap();
+        let deserialized: StringMatch = serde_json::from_str(&serialized).unwrap();
+        assert!(deserialized.is_wildcard());
+        assert_eq!(deserialized, orig);
+        assert!(deserialized.is_match("xAxxBcx"));
+    }
+
+    #[test]
+    fn test_wildcard_display() {
+        let sm = StringMatch::from_wildcard("a*").partial();
+        assert_eq!(sm.to_string(), "[partial, case-sensitive, wildcard] a*");
+        let parsed: StringMatch = sm.to_string().parse().unwrap();
+        assert_eq!(parsed, sm);
+        assert!(parsed.is_match("xab"));
+        assert!(!"[full] a*".parse::<StringMatch>().unwrap().is_match("ab"));
+    }
+
+    #[test]
+    fn test_literal() {
+        let sm = StringMatch::from_wildcard("a*").literal();
+        assert!(!sm.is_wildcard());
+        assert!(!sm.is_match("ab"));
+        assert!(sm.is_match("a*"));
+        assert_eq!(sm, StringMatch::from_literal("a*"));
+        assert_eq!(StringMatch::from_wildcard("a*").escape(), sm);
+
+        let sm = StringMatch::from_wildcard("(test|foo)").partial().literal();
+        assert!(sm.is_match("x(test|foo)y"));
+        assert!(!sm.is_match("test"));
+    }
+}